@@ -14,6 +14,10 @@ pub struct Coin {
     pub derivation_path: String,
     pub curve: String,
     pub public_key_type: String,
+    /// Chain id of an EVM network, required for the Ethereum blockchain. Transactions for other
+    /// chain ids are rejected
+    #[serde(default)]
+    pub chain_id: Option<u64>,
 
     #[serde(skip_serializing)]
     #[serde(skip_deserializing)]
@@ -50,6 +54,7 @@ impl std::str::FromStr for ProtoCoin {
             "ethereum" => Ok(ProtoCoin::Ethereum),
             "polkadot" => Ok(ProtoCoin::Polkadot),
             "solana" => Ok(ProtoCoin::Solana),
+            "arweave" => Ok(ProtoCoin::Arweave),
             _ => Err(Error::NotSupportedCoin),
         }
     }
@@ -1,3 +1,4 @@
+use super::api::{AddressChecksum, ChecksumValidation};
 use super::coin::Coin;
 use super::private_key::PrivateKey;
 use super::public_key::PublicKey;
@@ -19,6 +20,16 @@ pub trait Entry {
     fn get_supported_import_types(&self) -> Vec<ChainImportType>;
     fn get_supported_export_types(&self) -> Vec<ChainExportType>;
     fn validate_address(&self, address: &str) -> bool;
+
+    /// Validate `address` of `coin` and report its checksum, on chains with checksummed addresses
+    fn validate_address_checksum(
+        &self,
+        _coin: &Coin,
+        address: &str,
+        _validation: ChecksumValidation,
+    ) -> (bool, AddressChecksum) {
+        (self.validate_address(address), AddressChecksum::NoChecksum)
+    }
     fn derive_address(
         &self,
         coin: &Coin,
@@ -28,4 +39,21 @@ pub trait Entry {
     ) -> Result<String, Error>;
     fn sign(&self, coin: &Coin, private_key: &PrivateKey, payload: &[u8])
         -> Result<Vec<u8>, Error>;
+
+    /// Sign an off-chain message with the chain's canonical message signing scheme
+    fn sign_message(
+        &self,
+        coin: &Coin,
+        private_key: &PrivateKey,
+        message: &[u8],
+    ) -> Result<Vec<u8>, Error>;
+
+    /// Whether `signature` is a signature of `message` made by the owner of `address`
+    fn verify_message(
+        &self,
+        coin: &Coin,
+        message: &[u8],
+        signature: &[u8],
+        address: &str,
+    ) -> Result<bool, Error>;
 }
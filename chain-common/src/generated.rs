@@ -1,2 +1,5 @@
 pub mod api;
+pub mod arweave;
 pub mod ethereum;
+pub mod polkadot;
+pub mod solana;
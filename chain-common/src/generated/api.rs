@@ -54,6 +54,9 @@ pub enum Coin {
     Ethereum = 0,
     Polkadot = 1,
     Solana = 2,
+    /// Polkadot accounts of polkadot-js, sr25519 keys with Substrate derivation paths
+    PolkadotSr25519 = 3,
+    Arweave = 4,
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
@@ -249,6 +252,7 @@ pub struct ImportMnemonicStoredKeyResp {
 /// Create a new StoredKey with JSON, please aware that this request DOES create an account with the specific coin
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ImportJsonStoredKeyParam {
+    /// An Ethereum KeyStoreJson, or an account exported by polkadot-js
     #[prost(string, tag="1")]
     pub json: ::prost::alloc::string::String,
     /// The name of created ACCOUNT
@@ -304,7 +308,10 @@ pub struct SignTransactionParam {
     pub password: ::prost::alloc::string::String,
     #[prost(enumeration="Coin", tag="4")]
     pub coin: i32,
-    #[prost(oneof="sign_transaction_param::Input", tags="5")]
+    /// Id of an EVM network in the coin registry, e.g. "polygon", selected instead of coin if set
+    #[prost(string, tag="9")]
+    pub network: ::prost::alloc::string::String,
+    #[prost(oneof="sign_transaction_param::Input", tags="5, 6, 7, 8")]
     pub input: ::core::option::Option<sign_transaction_param::Input>,
 }
 /// Nested message and enum types in `SignTransactionParam`.
@@ -313,11 +320,17 @@ pub mod sign_transaction_param {
     pub enum Input {
         #[prost(message, tag="5")]
         SignInput(super::super::ethereum::SignInput),
+        #[prost(message, tag="6")]
+        SolanaSignInput(super::super::solana::SignInput),
+        #[prost(message, tag="7")]
+        PolkadotSignInput(super::super::polkadot::SignInput),
+        #[prost(message, tag="8")]
+        ArweaveSignInput(super::super::arweave::SignInput),
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SignTransactionResp {
-    #[prost(oneof="sign_transaction_resp::Output", tags="1")]
+    #[prost(oneof="sign_transaction_resp::Output", tags="1, 2, 3, 4")]
     pub output: ::core::option::Option<sign_transaction_resp::Output>,
 }
 /// Nested message and enum types in `SignTransactionResp`.
@@ -326,9 +339,373 @@ pub mod sign_transaction_resp {
     pub enum Output {
         #[prost(message, tag="1")]
         SignOutput(super::super::ethereum::SignOutput),
+        #[prost(message, tag="2")]
+        SolanaSignOutput(super::super::solana::SignOutput),
+        #[prost(message, tag="3")]
+        PolkadotSignOutput(super::super::polkadot::SignOutput),
+        #[prost(message, tag="4")]
+        ArweaveSignOutput(super::super::arweave::SignOutput),
+    }
+}
+/// Sign EIP-712 typed structured data (eth_signTypedData), only supported on Ethereum
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignTypedDataParam {
+    #[prost(bytes="vec", tag="1")]
+    pub stored_key_data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="2")]
+    pub derivation_path: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub password: ::prost::alloc::string::String,
+    #[prost(enumeration="Coin", tag="4")]
+    pub coin: i32,
+    /// The typed data JSON with types, primaryType, domain and message
+    #[prost(string, tag="5")]
+    pub typed_data: ::prost::alloc::string::String,
+    #[prost(enumeration="super::ethereum::TypedDataVersion", tag="6")]
+    pub version: i32,
+    /// Id of an EVM network in the coin registry, e.g. "polygon", selected instead of coin if set
+    #[prost(string, tag="7")]
+    pub network: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignTypedDataResp {
+    /// 65-byte signature r || s || v, v is 27 or 28
+    #[prost(bytes="vec", tag="1")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
+/// Sign an off-chain message with the coin's message signing scheme:
+/// EIP-191 personal_sign on Ethereum, raw ed25519 on Solana
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignMessageParam {
+    #[prost(bytes="vec", tag="1")]
+    pub stored_key_data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="2")]
+    pub derivation_path: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub password: ::prost::alloc::string::String,
+    #[prost(enumeration="Coin", tag="4")]
+    pub coin: i32,
+    #[prost(bytes="vec", tag="5")]
+    pub message: ::prost::alloc::vec::Vec<u8>,
+    /// Id of an EVM network in the coin registry, e.g. "polygon", selected instead of coin if set
+    #[prost(string, tag="6")]
+    pub network: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignMessageResp {
+    /// 65-byte r || s || v on Ethereum, 64-byte ed25519 signature on Solana
+    #[prost(bytes="vec", tag="1")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
+/// Verify an off-chain message signature against the signer's address
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VerifyMessageParam {
+    #[prost(enumeration="Coin", tag="1")]
+    pub coin: i32,
+    #[prost(bytes="vec", tag="2")]
+    pub message: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="3")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="4")]
+    pub address: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct VerifyMessageResp {
+    #[prost(bool, tag="1")]
+    pub valid: bool,
+}
+/// Recover the signer of an Ethereum signature offline
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecoverSignerParam {
+    /// Used only for typedData
+    #[prost(enumeration="super::ethereum::TypedDataVersion", tag="4")]
+    pub typed_data_version: i32,
+    #[prost(bytes="vec", tag="5")]
+    pub r: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="6")]
+    pub s: ::prost::alloc::vec::Vec<u8>,
+    /// The recovery id, 27/28 or an EIP-155 value chain_id * 2 + 35/36
+    #[prost(uint64, tag="7")]
+    pub v: u64,
+    #[prost(oneof="recover_signer_param::Input", tags="1, 2, 3")]
+    pub input: ::core::option::Option<recover_signer_param::Input>,
+}
+/// Nested message and enum types in `RecoverSignerParam`.
+pub mod recover_signer_param {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Input {
+        /// The signed 32-byte digest, e.g. a transaction hash
+        #[prost(bytes, tag="1")]
+        Hash(::prost::alloc::vec::Vec<u8>),
+        /// An EIP-191 personal_sign message
+        #[prost(bytes, tag="2")]
+        Message(::prost::alloc::vec::Vec<u8>),
+        /// An EIP-712 typed data JSON
+        #[prost(string, tag="3")]
+        TypedData(::prost::alloc::string::String),
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RecoverSignerResp {
+    /// 65-byte uncompressed public key
+    #[prost(bytes="vec", tag="1")]
+    pub public_key: ::prost::alloc::vec::Vec<u8>,
+    /// EIP-55 checksummed address
+    #[prost(string, tag="2")]
+    pub address: ::prost::alloc::string::String,
+}
+/// Decode a raw legacy, EIP-2930 or EIP-1559 transaction, either signed or unsigned
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DecodeTransactionParam {
+    #[prost(bytes="vec", tag="1")]
+    pub raw_transaction: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DecodeTransactionResp {
+    #[prost(message, optional, tag="1")]
+    pub transaction: ::core::option::Option<super::ethereum::DecodedTransaction>,
+}
+/// Encode Ethereum contract calldata
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AbiEncodeParam {
+    /// A function signature such as `transfer(address to, uint256 amount)`, a JSON ABI function
+    /// fragment or a full JSON ABI
+    #[prost(string, tag="1")]
+    pub abi: ::prost::alloc::string::String,
+    /// Name or signature of the function to call, may be empty if the ABI has a single function
+    #[prost(string, tag="2")]
+    pub function_name: ::prost::alloc::string::String,
+    /// JSON array of the arguments in order, or JSON object keyed by parameter names
+    #[prost(string, tag="3")]
+    pub args: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AbiEncodeResp {
+    #[prost(bytes="vec", tag="1")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    /// Canonical function signature, e.g. `transfer(address,uint256)`
+    #[prost(string, tag="2")]
+    pub signature: ::prost::alloc::string::String,
+}
+/// Decode Ethereum contract calldata with the function of the ABI matching its selector
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AbiDecodeParam {
+    #[prost(string, tag="1")]
+    pub abi: ::prost::alloc::string::String,
+    #[prost(bytes="vec", tag="2")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AbiDecodeResp {
+    #[prost(string, tag="1")]
+    pub function_name: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub signature: ::prost::alloc::string::String,
+    /// JSON array of `{"name", "type", "value"}` objects
+    #[prost(string, tag="3")]
+    pub args: ::prost::alloc::string::String,
+}
+/// Preview an Ethereum transaction before signing it: decode the recognised calldata and flag risks
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PreviewTransactionParam {
+    #[prost(message, optional, tag="1")]
+    pub input: ::core::option::Option<super::ethereum::SignInput>,
+    /// Whether the recipient has contract code, which is only known on chain
+    #[prost(bool, tag="2")]
+    pub recipient_is_contract: bool,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PreviewTransactionResp {
+    #[prost(message, optional, tag="1")]
+    pub preview: ::core::option::Option<super::ethereum::TransactionPreview>,
+}
+/// Build the text of an EIP-4361 Sign-In with Ethereum message
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SiweBuildParam {
+    #[prost(message, optional, tag="1")]
+    pub message: ::core::option::Option<super::ethereum::SiweMessage>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SiweBuildResp {
+    #[prost(string, tag="1")]
+    pub text: ::prost::alloc::string::String,
+}
+/// Parse a Sign-In with Ethereum message and validate it against the relying party
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SiweValidateParam {
+    #[prost(string, tag="1")]
+    pub text: ::prost::alloc::string::String,
+    /// Domain requesting the sign-in
+    #[prost(string, tag="2")]
+    pub domain: ::prost::alloc::string::String,
+    /// Expected chain id, not checked if 0
+    #[prost(uint64, tag="3")]
+    pub chain_id: u64,
+    /// Expected nonce, not checked if empty
+    #[prost(string, tag="4")]
+    pub nonce: ::prost::alloc::string::String,
+    /// Current time in Unix seconds
+    #[prost(int64, tag="5")]
+    pub now: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SiweValidateResp {
+    #[prost(message, optional, tag="1")]
+    pub message: ::core::option::Option<super::ethereum::SiweMessage>,
+    #[prost(enumeration="super::ethereum::SiweStatus", tag="2")]
+    pub status: i32,
+}
+/// Validate a Sign-In with Ethereum message and sign it with EIP-191 personal_sign
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SiweSignParam {
+    #[prost(bytes="vec", tag="1")]
+    pub stored_key_data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="2")]
+    pub derivation_path: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub password: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub text: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub domain: ::prost::alloc::string::String,
+    #[prost(uint64, tag="6")]
+    pub chain_id: u64,
+    #[prost(string, tag="7")]
+    pub nonce: ::prost::alloc::string::String,
+    #[prost(int64, tag="8")]
+    pub now: i64,
+    /// Id of an EVM network in the coin registry, e.g. "polygon", Ethereum if empty
+    #[prost(string, tag="9")]
+    pub network: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SiweSignResp {
+    #[prost(message, optional, tag="1")]
+    pub message: ::core::option::Option<super::ethereum::SiweMessage>,
+    #[prost(enumeration="super::ethereum::SiweStatus", tag="2")]
+    pub status: i32,
+    /// 65-byte r || s || v signature, empty unless the status is valid
+    #[prost(bytes="vec", tag="3")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
+/// Sign an ERC-4337 UserOperation of a smart account owned by the stored key
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignUserOperationParam {
+    #[prost(bytes="vec", tag="1")]
+    pub stored_key_data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="2")]
+    pub derivation_path: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub password: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="4")]
+    pub user_operation: ::core::option::Option<super::ethereum::UserOperation>,
+    /// Id of an EVM network in the coin registry, e.g. "polygon", Ethereum if empty
+    #[prost(string, tag="5")]
+    pub network: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignUserOperationResp {
+    #[prost(message, optional, tag="1")]
+    pub output: ::core::option::Option<super::ethereum::UserOperationSignOutput>,
+}
+/// Counterfactual address of a smart account deployed by a factory with CREATE2
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ComputeAccountAddressParam {
+    #[prost(string, tag="1")]
+    pub factory: ::prost::alloc::string::String,
+    /// 32-byte CREATE2 salt
+    #[prost(bytes="vec", tag="2")]
+    pub salt: ::prost::alloc::vec::Vec<u8>,
+    /// keccak256 of the account's init code
+    #[prost(bytes="vec", tag="3")]
+    pub init_code_hash: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ComputeAccountAddressResp {
+    #[prost(string, tag="1")]
+    pub address: ::prost::alloc::string::String,
+}
+/// Sign a Safe multisig transaction as one of its owners
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignSafeTransactionParam {
+    #[prost(bytes="vec", tag="1")]
+    pub stored_key_data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="2")]
+    pub derivation_path: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub password: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="4")]
+    pub transaction: ::core::option::Option<super::ethereum::SafeTransaction>,
+    #[prost(enumeration="super::ethereum::SafeSignatureType", tag="5")]
+    pub signature_type: i32,
+    /// Id of an EVM network in the coin registry, e.g. "polygon", Ethereum if empty
+    #[prost(string, tag="6")]
+    pub network: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignSafeTransactionResp {
+    #[prost(bytes="vec", tag="1")]
+    pub safe_tx_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
+/// Combine owner signatures of a Safe transaction into the `signatures` of `execTransaction`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CombineSafeSignaturesParam {
+    #[prost(bytes="vec", tag="1")]
+    pub safe_tx_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", repeated, tag="2")]
+    pub signatures: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CombineSafeSignaturesResp {
+    #[prost(bytes="vec", tag="1")]
+    pub signatures: ::prost::alloc::vec::Vec<u8>,
+    /// Owners in the order of their signatures
+    #[prost(string, repeated, tag="2")]
+    pub owners: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// Sign an EIP-2612 or Permit2 gasless approval
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignPermitParam {
+    #[prost(bytes="vec", tag="1")]
+    pub stored_key_data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="2")]
+    pub derivation_path: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub password: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="4")]
+    pub input: ::core::option::Option<super::ethereum::PermitInput>,
+    /// Id of an EVM network in the coin registry, e.g. "polygon", Ethereum if empty
+    #[prost(string, tag="5")]
+    pub network: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignPermitResp {
+    #[prost(message, optional, tag="1")]
+    pub output: ::core::option::Option<super::ethereum::PermitSignOutput>,
+}
+/// Sign an EIP-7702 authorization to include in the authorization list of a set code transaction
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignAuthorizationParam {
+    #[prost(bytes="vec", tag="1")]
+    pub stored_key_data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="2")]
+    pub derivation_path: ::prost::alloc::string::String,
+    #[prost(string, tag="3")]
+    pub password: ::prost::alloc::string::String,
+    #[prost(message, optional, tag="4")]
+    pub authorization: ::core::option::Option<super::ethereum::Authorization>,
+    /// Id of an EVM network in the coin registry, e.g. "polygon", Ethereum if empty
+    #[prost(string, tag="5")]
+    pub network: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignAuthorizationResp {
+    #[prost(message, optional, tag="1")]
+    pub authorization: ::core::option::Option<super::ethereum::Authorization>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PasswordValidationParam {
     #[prost(bytes="vec", tag="1")]
     pub stored_key_data: ::prost::alloc::vec::Vec<u8>,
@@ -341,6 +718,8 @@ pub struct AddressValidationParam {
     pub address: ::prost::alloc::string::String,
     #[prost(enumeration="Coin", tag="2")]
     pub coin: i32,
+    #[prost(enumeration="ChecksumValidation", tag="3")]
+    pub checksum_validation: i32,
 }
 /// Get the version code of MaskWalletCore library
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -368,6 +747,27 @@ pub mod validate_param {
 pub struct ValidateResp {
     #[prost(bool, tag="1")]
     pub valid: bool,
+    /// Only reported for address validation
+    #[prost(enumeration="AddressChecksum", tag="2")]
+    pub address_checksum: i32,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ChecksumValidation {
+    /// Unchecksummed addresses are accepted, mixed case ones must carry a valid checksum
+    ChecksumIfMixedCase = 0,
+    /// Only correctly checksummed addresses are accepted
+    ChecksumRequired = 1,
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum AddressChecksum {
+    /// Invalid address or a chain without checksummed addresses
+    NoChecksum = 0,
+    Lowercase = 1,
+    Uppercase = 2,
+    Checksummed = 3,
+    BadChecksum = 4,
 }
 /// Generate a persona
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -437,7 +837,7 @@ pub struct AesJwkResp {
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct MwRequest {
-    #[prost(oneof="mw_request::Request", tags="1, 2, 3, 4, 5, 10, 13, 14, 15, 16, 17, 18, 20, 21, 22, 23, 24, 25, 26")]
+    #[prost(oneof="mw_request::Request", tags="1, 2, 3, 4, 5, 10, 13, 14, 15, 16, 17, 18, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42, 43")]
     pub request: ::core::option::Option<mw_request::Request>,
 }
 /// Nested message and enum types in `MWRequest`.
@@ -482,11 +882,45 @@ pub mod mw_request {
         ParamGenerateMnemonic(super::GenerateMnemonicParam),
         #[prost(message, tag="26")]
         ParamGeneratePersona(super::PersonaGenerationParam),
+        #[prost(message, tag="27")]
+        ParamSignTypedData(super::SignTypedDataParam),
+        #[prost(message, tag="28")]
+        ParamSignMessage(super::SignMessageParam),
+        #[prost(message, tag="29")]
+        ParamVerifyMessage(super::VerifyMessageParam),
+        #[prost(message, tag="30")]
+        ParamRecoverSigner(super::RecoverSignerParam),
+        #[prost(message, tag="31")]
+        ParamDecodeTransaction(super::DecodeTransactionParam),
+        #[prost(message, tag="32")]
+        ParamAbiEncode(super::AbiEncodeParam),
+        #[prost(message, tag="33")]
+        ParamAbiDecode(super::AbiDecodeParam),
+        #[prost(message, tag="34")]
+        ParamPreviewTransaction(super::PreviewTransactionParam),
+        #[prost(message, tag="35")]
+        ParamSiweBuild(super::SiweBuildParam),
+        #[prost(message, tag="36")]
+        ParamSiweValidate(super::SiweValidateParam),
+        #[prost(message, tag="37")]
+        ParamSiweSign(super::SiweSignParam),
+        #[prost(message, tag="38")]
+        ParamSignUserOperation(super::SignUserOperationParam),
+        #[prost(message, tag="39")]
+        ParamComputeAccountAddress(super::ComputeAccountAddressParam),
+        #[prost(message, tag="40")]
+        ParamSignSafeTransaction(super::SignSafeTransactionParam),
+        #[prost(message, tag="41")]
+        ParamCombineSafeSignatures(super::CombineSafeSignaturesParam),
+        #[prost(message, tag="42")]
+        ParamSignPermit(super::SignPermitParam),
+        #[prost(message, tag="43")]
+        ParamSignAuthorization(super::SignAuthorizationParam),
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct MwResponse {
-    #[prost(oneof="mw_response::Response", tags="1, 2, 3, 4, 5, 6, 11, 14, 15, 16, 17, 19, 20, 21, 22, 23, 24, 25")]
+    #[prost(oneof="mw_response::Response", tags="1, 2, 3, 4, 5, 6, 11, 14, 15, 16, 17, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33, 34, 35, 36, 37, 38, 39, 40, 41, 42")]
     pub response: ::core::option::Option<mw_response::Response>,
 }
 /// Nested message and enum types in `MWResponse`.
@@ -529,6 +963,40 @@ pub mod mw_response {
         RespGenerateMnemonic(super::GenerateMnemonicResp),
         #[prost(message, tag="25")]
         RespGeneratePersona(super::PersonaGenerationResp),
+        #[prost(message, tag="26")]
+        RespSignTypedData(super::SignTypedDataResp),
+        #[prost(message, tag="27")]
+        RespSignMessage(super::SignMessageResp),
+        #[prost(message, tag="28")]
+        RespVerifyMessage(super::VerifyMessageResp),
+        #[prost(message, tag="29")]
+        RespRecoverSigner(super::RecoverSignerResp),
+        #[prost(message, tag="30")]
+        RespDecodeTransaction(super::DecodeTransactionResp),
+        #[prost(message, tag="31")]
+        RespAbiEncode(super::AbiEncodeResp),
+        #[prost(message, tag="32")]
+        RespAbiDecode(super::AbiDecodeResp),
+        #[prost(message, tag="33")]
+        RespPreviewTransaction(super::PreviewTransactionResp),
+        #[prost(message, tag="34")]
+        RespSiweBuild(super::SiweBuildResp),
+        #[prost(message, tag="35")]
+        RespSiweValidate(super::SiweValidateResp),
+        #[prost(message, tag="36")]
+        RespSiweSign(super::SiweSignResp),
+        #[prost(message, tag="37")]
+        RespSignUserOperation(super::SignUserOperationResp),
+        #[prost(message, tag="38")]
+        RespComputeAccountAddress(super::ComputeAccountAddressResp),
+        #[prost(message, tag="39")]
+        RespSignSafeTransaction(super::SignSafeTransactionResp),
+        #[prost(message, tag="40")]
+        RespCombineSafeSignatures(super::CombineSafeSignaturesResp),
+        #[prost(message, tag="41")]
+        RespSignPermit(super::SignPermitResp),
+        #[prost(message, tag="42")]
+        RespSignAuthorization(super::SignAuthorizationResp),
     }
 }
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -0,0 +1,45 @@
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Tag {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub value: ::prost::alloc::string::String,
+}
+/// Input data necessary to create a signed format 2 transaction.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignInput {
+    /// Address of the recipient of quantity, empty for a data transaction
+    #[prost(string, tag="1")]
+    pub target: ::prost::alloc::string::String,
+    /// Transferred amount in winston, 0 if empty
+    #[prost(string, tag="2")]
+    pub quantity: ::prost::alloc::string::String,
+    /// Fee in winston
+    #[prost(string, tag="3")]
+    pub reward: ::prost::alloc::string::String,
+    /// base64url anchor of /tx_anchor or the id of the last transaction of the wallet
+    #[prost(string, tag="4")]
+    pub last_tx: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag="5")]
+    pub tags: ::prost::alloc::vec::Vec<Tag>,
+    #[prost(bytes="vec", tag="6")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    /// Size in bytes and base64url merkle root of data uploaded separately in chunks, used only if
+    /// data is empty
+    #[prost(string, tag="7")]
+    pub data_size: ::prost::alloc::string::String,
+    #[prost(string, tag="8")]
+    pub data_root: ::prost::alloc::string::String,
+}
+/// Transaction signing output.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignOutput {
+    /// base64url SHA-256 of the signature
+    #[prost(string, tag="1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(bytes="vec", tag="2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+    /// JSON of the signed transaction, to be posted to /tx
+    #[prost(string, tag="3")]
+    pub encoded: ::prost::alloc::string::String,
+}
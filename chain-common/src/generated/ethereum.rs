@@ -1,3 +1,46 @@
+/// Addresses and storage keys the transaction plans to access (EIP-2930)
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AccessListItem {
+    /// hex encoded accessed address
+    #[prost(string, tag="1")]
+    pub address: ::prost::alloc::string::String,
+    /// hex encoded 32-byte storage keys
+    #[prost(string, repeated, tag="2")]
+    pub storage_keys: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// EIP-7702 authorization to set the code of the signing account to a delegation to `address`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Authorization {
+    /// 0 to authorize on every chain
+    #[prost(uint64, tag="1")]
+    pub chain_id: u64,
+    /// Hex encoded address of the implementation to delegate to
+    #[prost(string, tag="2")]
+    pub address: ::prost::alloc::string::String,
+    /// Hex encoded nonce of the authorizing account, at the time the transaction is executed
+    #[prost(string, tag="3")]
+    pub nonce: ::prost::alloc::string::String,
+    /// Signature of keccak256(0x05 || rlp([chain_id, address, nonce])), empty until signed
+    #[prost(uint32, tag="4")]
+    pub y_parity: u32,
+    #[prost(bytes="vec", tag="5")]
+    pub r: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="6")]
+    pub s: ::prost::alloc::vec::Vec<u8>,
+}
+/// Blobs of an EIP-4844 transaction with their KZG commitments and proofs, all in the same order
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BlobSidecar {
+    /// 131072-byte blobs
+    #[prost(bytes="vec", repeated, tag="1")]
+    pub blobs: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    /// 48-byte KZG commitments
+    #[prost(bytes="vec", repeated, tag="2")]
+    pub commitments: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    /// 48-byte KZG proofs
+    #[prost(bytes="vec", repeated, tag="3")]
+    pub proofs: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+}
 /// Input data necessary to create a signed transaction.
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct SignInput {
@@ -7,7 +50,8 @@ pub struct SignInput {
     #[prost(string, tag="2")]
     pub nonce: ::prost::alloc::string::String,
     /// hex encoded gas_price number
-    /// If > 0, legacy fee scheme is used; if 0, EIP1559 fee scheme is used
+    /// used only for Legacy and AccessList transactions, disregarded for EIP1559.
+    /// With an unspecified type, legacy fee scheme is used if > 0 and EIP1559 if 0
     #[prost(string, tag="3")]
     pub gas_price: ::prost::alloc::string::String,
     /// hex encoded gas_limit number
@@ -24,12 +68,42 @@ pub struct SignInput {
     /// hex encoded amount number
     #[prost(string, tag="7")]
     pub amount: ::prost::alloc::string::String,
-    /// Recipient's address.
+    /// Recipient's address, empty to deploy a contract with `payload` as the init code
     #[prost(string, tag="8")]
     pub to_address: ::prost::alloc::string::String,
     /// payload data
     #[prost(bytes="vec", tag="9")]
     pub payload: ::prost::alloc::vec::Vec<u8>,
+    /// used only for AccessList and EIP1559 transactions, disregarded for legacy
+    #[prost(message, repeated, tag="10")]
+    pub access_list: ::prost::alloc::vec::Vec<AccessListItem>,
+    /// Unspecified infers the type from the fees, as before types were introduced
+    #[prost(enumeration="TransactionType", tag="11")]
+    pub transaction_type: i32,
+    /// Signed authorizations, used only and required for SetCode transactions
+    #[prost(message, repeated, tag="12")]
+    pub authorization_list: ::prost::alloc::vec::Vec<Authorization>,
+    /// hex encoded maximum fee per blob gas, used only for Blob transactions
+    #[prost(string, tag="13")]
+    pub max_fee_per_blob_gas: ::prost::alloc::string::String,
+    /// 32-byte versioned hashes of the blobs, used only for Blob transactions.
+    /// Computed from the commitments of the sidecar when empty
+    #[prost(bytes="vec", repeated, tag="14")]
+    pub blob_versioned_hashes: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    /// Blobs of a Blob transaction, to encode it in the network wrapper form with its sidecar
+    #[prost(message, optional, tag="15")]
+    pub blob_sidecar: ::core::option::Option<BlobSidecar>,
+    /// hex encoded gas per pubdata byte limit of ZkSyncEip712 transactions, 50000 when empty
+    #[prost(string, tag="16")]
+    pub gas_per_pubdata: ::prost::alloc::string::String,
+    /// Bytecodes of the contracts a ZkSyncEip712 transaction deploys
+    #[prost(bytes="vec", repeated, tag="17")]
+    pub factory_deps: ::prost::alloc::vec::Vec<::prost::alloc::vec::Vec<u8>>,
+    /// Paymaster of a ZkSyncEip712 transaction and its input, empty when the account pays the fee
+    #[prost(string, tag="18")]
+    pub paymaster: ::prost::alloc::string::String,
+    #[prost(bytes="vec", tag="19")]
+    pub paymaster_input: ::prost::alloc::vec::Vec<u8>,
 }
 /// Transaction signing output.
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -46,4 +120,356 @@ pub struct SignOutput {
     /// The payload part, supplied in the input or assembled from input parameters
     #[prost(bytes="vec", tag="5")]
     pub data: ::prost::alloc::vec::Vec<u8>,
+    /// EIP-55 address of the deployed contract, only set when `to_address` is empty
+    #[prost(string, tag="6")]
+    pub contract_address: ::prost::alloc::string::String,
+    /// Transaction hash, for Blob transactions with a sidecar it's the hash without the sidecar
+    #[prost(bytes="vec", tag="7")]
+    pub hash: ::prost::alloc::vec::Vec<u8>,
+}
+/// A raw transaction decoded back into its fields
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DecodedTransaction {
+    /// The transaction fields, numbers are 0x prefixed hex and the recipient is EIP-55 checksummed
+    #[prost(message, optional, tag="1")]
+    pub sign_input: ::core::option::Option<SignInput>,
+    /// Whether the raw transaction carries a signature
+    #[prost(bool, tag="2")]
+    pub signed: bool,
+    /// Signature, v is the y parity for typed transactions, 27/28 or the EIP-155 value for legacy
+    #[prost(uint64, tag="3")]
+    pub v: u64,
+    #[prost(bytes="vec", tag="4")]
+    pub r: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="5")]
+    pub s: ::prost::alloc::vec::Vec<u8>,
+    /// Transaction hash, keccak256 of the raw signed transaction, empty if unsigned
+    #[prost(bytes="vec", tag="6")]
+    pub hash: ::prost::alloc::vec::Vec<u8>,
+    /// The digest that is signed
+    #[prost(bytes="vec", tag="7")]
+    pub sign_hash: ::prost::alloc::vec::Vec<u8>,
+    /// EIP-55 address of the recovered sender, empty if unsigned
+    #[prost(string, tag="8")]
+    pub from: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransactionWarning {
+    #[prost(enumeration="RiskType", tag="1")]
+    pub risk: i32,
+    /// Human readable description of the risk
+    #[prost(string, tag="2")]
+    pub message: ::prost::alloc::string::String,
+}
+/// Summary of a transaction to review before signing it
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TransactionPreview {
+    /// EIP-55 checksummed recipient, empty for a contract deployment
+    #[prost(string, tag="1")]
+    pub to: ::prost::alloc::string::String,
+    /// Native value in wei, as a decimal string
+    #[prost(string, tag="2")]
+    pub value: ::prost::alloc::string::String,
+    /// Maximum network fee in wei as a decimal string, gas limit times the gas price or the EIP-1559 max fee per gas,
+    /// plus the maximum blob fee of blob transactions
+    #[prost(string, tag="3")]
+    pub max_fee: ::prost::alloc::string::String,
+    /// Canonical signature of the recognised function, empty for plain transfers or unknown calldata
+    #[prost(string, tag="4")]
+    pub method: ::prost::alloc::string::String,
+    /// JSON array of the decoded `{"name", "type", "value"}` arguments of `method`
+    #[prost(string, tag="5")]
+    pub args: ::prost::alloc::string::String,
+    #[prost(bool, tag="6")]
+    pub is_deployment: bool,
+    #[prost(message, repeated, tag="7")]
+    pub warnings: ::prost::alloc::vec::Vec<TransactionWarning>,
+}
+/// EIP-4361 Sign-In with Ethereum message fields, optional fields are empty when absent
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SiweMessage {
+    /// Optional URI scheme of the domain, e.g. `https`
+    #[prost(string, tag="1")]
+    pub scheme: ::prost::alloc::string::String,
+    /// RFC 3986 authority requesting the sign-in, e.g. `example.com`
+    #[prost(string, tag="2")]
+    pub domain: ::prost::alloc::string::String,
+    /// EIP-55 checksummed address of the signer
+    #[prost(string, tag="3")]
+    pub address: ::prost::alloc::string::String,
+    /// Optional human readable assertion, on a single line
+    #[prost(string, tag="4")]
+    pub statement: ::prost::alloc::string::String,
+    /// RFC 3986 URI of the resource the sign-in is for
+    #[prost(string, tag="5")]
+    pub uri: ::prost::alloc::string::String,
+    /// Always `1`
+    #[prost(string, tag="6")]
+    pub version: ::prost::alloc::string::String,
+    #[prost(uint64, tag="7")]
+    pub chain_id: u64,
+    /// At least 8 alphanumeric characters chosen by the relying party
+    #[prost(string, tag="8")]
+    pub nonce: ::prost::alloc::string::String,
+    /// RFC 3339 date-times, `expiration_time` and `not_before` are optional
+    #[prost(string, tag="9")]
+    pub issued_at: ::prost::alloc::string::String,
+    #[prost(string, tag="10")]
+    pub expiration_time: ::prost::alloc::string::String,
+    #[prost(string, tag="11")]
+    pub not_before: ::prost::alloc::string::String,
+    /// Optional system-specific identifier
+    #[prost(string, tag="12")]
+    pub request_id: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag="13")]
+    pub resources: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// ERC-4337 UserOperation of a smart contract account, numbers are hex encoded
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UserOperation {
+    #[prost(enumeration="EntryPointVersion", tag="1")]
+    pub version: i32,
+    /// Address of the EntryPoint contract and the chain the operation is for
+    #[prost(string, tag="2")]
+    pub entry_point: ::prost::alloc::string::String,
+    #[prost(uint64, tag="3")]
+    pub chain_id: u64,
+    /// Address of the smart account
+    #[prost(string, tag="4")]
+    pub sender: ::prost::alloc::string::String,
+    #[prost(string, tag="5")]
+    pub nonce: ::prost::alloc::string::String,
+    /// Factory address followed by the factory calldata, empty when the account is deployed
+    #[prost(bytes="vec", tag="6")]
+    pub init_code: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="7")]
+    pub call_data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(string, tag="8")]
+    pub call_gas_limit: ::prost::alloc::string::String,
+    #[prost(string, tag="9")]
+    pub verification_gas_limit: ::prost::alloc::string::String,
+    #[prost(string, tag="10")]
+    pub pre_verification_gas: ::prost::alloc::string::String,
+    #[prost(string, tag="11")]
+    pub max_fee_per_gas: ::prost::alloc::string::String,
+    #[prost(string, tag="12")]
+    pub max_priority_fee_per_gas: ::prost::alloc::string::String,
+    /// v0.6 only: paymaster address followed by the paymaster data, empty without a paymaster
+    #[prost(bytes="vec", tag="13")]
+    pub paymaster_and_data: ::prost::alloc::vec::Vec<u8>,
+    /// v0.7 only: packed into paymasterAndData, empty `paymaster` without a paymaster
+    #[prost(string, tag="14")]
+    pub paymaster: ::prost::alloc::string::String,
+    #[prost(string, tag="15")]
+    pub paymaster_verification_gas_limit: ::prost::alloc::string::String,
+    #[prost(string, tag="16")]
+    pub paymaster_post_op_gas_limit: ::prost::alloc::string::String,
+    #[prost(bytes="vec", tag="17")]
+    pub paymaster_data: ::prost::alloc::vec::Vec<u8>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UserOperationSignOutput {
+    /// userOpHash, as computed by the EntryPoint
+    #[prost(bytes="vec", tag="1")]
+    pub user_op_hash: ::prost::alloc::vec::Vec<u8>,
+    /// 65-byte r || s || v personal_sign signature of the userOpHash, v is 27 or 28
+    #[prost(bytes="vec", tag="2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+}
+/// Safe (Gnosis Safe) multisig transaction, numbers are hex encoded and may be empty for zero
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SafeTransaction {
+    /// Address of the Safe, the EIP-712 verifying contract
+    #[prost(string, tag="1")]
+    pub safe_address: ::prost::alloc::string::String,
+    /// 0 for Safe versions before 1.3.0, whose EIP-712 domain has no chain id
+    #[prost(uint64, tag="2")]
+    pub chain_id: u64,
+    #[prost(string, tag="3")]
+    pub to: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub value: ::prost::alloc::string::String,
+    #[prost(bytes="vec", tag="5")]
+    pub data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(enumeration="SafeOperation", tag="6")]
+    pub operation: i32,
+    #[prost(string, tag="7")]
+    pub safe_tx_gas: ::prost::alloc::string::String,
+    #[prost(string, tag="8")]
+    pub base_gas: ::prost::alloc::string::String,
+    #[prost(string, tag="9")]
+    pub gas_price: ::prost::alloc::string::String,
+    /// Empty for the zero address, paying the refund in ether and to the executor
+    #[prost(string, tag="10")]
+    pub gas_token: ::prost::alloc::string::String,
+    #[prost(string, tag="11")]
+    pub refund_receiver: ::prost::alloc::string::String,
+    #[prost(string, tag="12")]
+    pub nonce: ::prost::alloc::string::String,
+}
+/// Token and amount of a permit, numbers are hex encoded and may be empty for zero
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PermitToken {
+    #[prost(string, tag="1")]
+    pub token: ::prost::alloc::string::String,
+    #[prost(string, tag="2")]
+    pub amount: ::prost::alloc::string::String,
+    /// Permit2 allowance expiry and per-token nonce, only used by PermitSingle and PermitBatch
+    #[prost(string, tag="3")]
+    pub expiration: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub nonce: ::prost::alloc::string::String,
+}
+/// A gasless approval, numbers are hex encoded and may be empty for zero
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PermitInput {
+    #[prost(enumeration="PermitKind", tag="1")]
+    pub kind: i32,
+    #[prost(uint64, tag="2")]
+    pub chain_id: u64,
+    /// EIP-2612 domain name and version of the token, usually its name and "1"
+    #[prost(string, tag="3")]
+    pub token_name: ::prost::alloc::string::String,
+    #[prost(string, tag="4")]
+    pub token_version: ::prost::alloc::string::String,
+    /// The approving account, only part of the EIP-2612 permit
+    #[prost(string, tag="5")]
+    pub owner: ::prost::alloc::string::String,
+    #[prost(string, tag="6")]
+    pub spender: ::prost::alloc::string::String,
+    /// Exactly one token except for PermitBatch
+    #[prost(message, repeated, tag="7")]
+    pub tokens: ::prost::alloc::vec::Vec<PermitToken>,
+    /// Nonce of EIP-2612 and PermitTransferFrom
+    #[prost(string, tag="8")]
+    pub nonce: ::prost::alloc::string::String,
+    /// deadline, or sigDeadline of PermitSingle and PermitBatch
+    #[prost(string, tag="9")]
+    pub deadline: ::prost::alloc::string::String,
+}
+/// A decoded field of the permit message, e.g. `details\[0\].amount`
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PermitField {
+    #[prost(string, tag="1")]
+    pub name: ::prost::alloc::string::String,
+    /// Addresses as given and numbers in decimal
+    #[prost(string, tag="2")]
+    pub value: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PermitSignOutput {
+    /// EIP-712 digest of the permit
+    #[prost(bytes="vec", tag="1")]
+    pub digest: ::prost::alloc::vec::Vec<u8>,
+    /// 65-byte r || s || v signature of the digest, v is 27 or 28
+    #[prost(bytes="vec", tag="2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+    /// The typed data that was signed, for eth_signTypedData_v4 compatible wallets and relayers
+    #[prost(string, tag="3")]
+    pub typed_data: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag="4")]
+    pub fields: ::prost::alloc::vec::Vec<PermitField>,
+    /// Whether any amount is 2^128 or more, which is effectively unlimited
+    #[prost(bool, tag="5")]
+    pub unlimited: bool,
+}
+/// EIP-2718 transaction type
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TransactionType {
+    /// Inferred from the fees: EIP-1559 if gas_price is 0, legacy otherwise
+    Unspecified = 0,
+    /// Legacy transaction, replay protected with EIP-155.
+    /// Numbered outside of the EIP-2718 type range of 0x00 to 0x7f
+    Legacy = 128,
+    /// EIP-2930 transaction with an access list, type 0x01
+    AccessList = 1,
+    /// EIP-1559 transaction, type 0x02
+    Eip1559 = 2,
+    /// EIP-4844 transaction carrying blobs, type 0x03
+    Blob = 3,
+    /// EIP-7702 set code transaction with an authorization list, type 0x04
+    SetCode = 4,
+    /// zkSync Era transaction signed as EIP-712 typed data, type 0x71
+    ZkSyncEip712 = 113,
+}
+/// Version of the eth_signTypedData encoding
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TypedDataVersion {
+    /// eth_signTypedData_v4, supports arrays and encodes missing structs as zero
+    V4 = 0,
+    /// eth_signTypedData_v3, omits missing fields and rejects arrays
+    V3 = 1,
+}
+/// Risk found when previewing a transaction
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum RiskType {
+    /// ERC-20 approve or increaseAllowance of an effectively unlimited amount
+    UnlimitedApproval = 0,
+    /// setApprovalForAll letting an operator move every token of an ERC-721 or ERC-1155 collection
+    ApprovalForAll = 1,
+    /// Allowance granted to or through the Uniswap Permit2 contract
+    Permit2Approval = 2,
+    /// Native value sent to a contract
+    ValueToContract = 3,
+}
+/// Outcome of validating a Sign-In with Ethereum message
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SiweStatus {
+    Valid = 0,
+    /// The address is not EIP-55 checksummed
+    InvalidAddress = 1,
+    /// The domain is not the one requesting the sign-in
+    DomainMismatch = 2,
+    ChainIdMismatch = 3,
+    NonceMismatch = 4,
+    /// Issued after the current time
+    IssuedInFuture = 5,
+    /// The expiration time has passed
+    Expired = 6,
+    /// The not-before time has not been reached
+    NotYetValid = 7,
+    /// The signing account is not the address of the message
+    AddressMismatch = 8,
+}
+/// ERC-4337 EntryPoint version, which decides how a UserOperation is packed and hashed
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum EntryPointVersion {
+    /// EntryPoint v0.6, 0x5FF137D4b0FDCD49DcA30c7CF57E578a026d2789
+    V06 = 0,
+    /// EntryPoint v0.7 with the PackedUserOperation layout, 0x0000000071727De22E5E9d8BAf0edAc6f37da032
+    V07 = 1,
+}
+/// Call type of a Safe transaction
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SafeOperation {
+    Call = 0,
+    DelegateCall = 1,
+}
+/// How a Safe owner signs the safeTxHash
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum SafeSignatureType {
+    /// EIP-712 signature of the safeTxHash, v is 27 or 28
+    Eip712 = 0,
+    /// eth_sign (personal_sign) signature of the safeTxHash, v is adjusted by 4 to 31 or 32
+    EthSign = 1,
+}
+/// Kind of gasless approval signed as EIP-712 typed data
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum PermitKind {
+    /// EIP-2612 `Permit` of the token itself
+    Eip2612 = 0,
+    /// Permit2 `PermitSingle`, an allowance of one token
+    Permit2Single = 1,
+    /// Permit2 `PermitBatch`, allowances of several tokens
+    Permit2Batch = 2,
+    /// Permit2 `PermitTransferFrom`, a one-time signature transfer
+    Permit2TransferFrom = 3,
 }
@@ -0,0 +1,86 @@
+/// Indices of a call in the runtime, the pallet index followed by the call index
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CallIndices {
+    #[prost(uint32, tag="1")]
+    pub module_index: u32,
+    #[prost(uint32, tag="2")]
+    pub method_index: u32,
+}
+/// Transfer from the signer with balances.transferKeepAlive, which keeps the sender alive
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Transfer {
+    /// SS58 address of the recipient
+    #[prost(string, tag="1")]
+    pub to_address: ::prost::alloc::string::String,
+    /// amount in planck, big endian
+    #[prost(bytes="vec", tag="2")]
+    pub value: ::prost::alloc::vec::Vec<u8>,
+    /// If empty, the indices of balances.transferKeepAlive on Polkadot or Kusama, chosen by the
+    /// genesis hash
+    #[prost(message, optional, tag="3")]
+    pub call_indices: ::core::option::Option<CallIndices>,
+}
+/// Mortality of the extrinsic, it is immortal if period is 0
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Era {
+    /// block the era starts from, whose hash is SignInput.block_hash
+    #[prost(uint64, tag="1")]
+    pub block_number: u64,
+    /// number of blocks the extrinsic is valid for, rounded up to a power of two
+    #[prost(uint64, tag="2")]
+    pub period: u64,
+}
+/// Input data necessary to create a signed extrinsic.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignInput {
+    /// hash of the era's first block, the genesis hash for an immortal extrinsic
+    #[prost(bytes="vec", tag="1")]
+    pub block_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(bytes="vec", tag="2")]
+    pub genesis_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(uint64, tag="3")]
+    pub nonce: u64,
+    #[prost(uint32, tag="4")]
+    pub spec_version: u32,
+    #[prost(uint32, tag="5")]
+    pub transaction_version: u32,
+    /// tip in planck for the block author, big endian
+    #[prost(bytes="vec", tag="6")]
+    pub tip: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag="7")]
+    pub era: ::core::option::Option<Era>,
+    /// Whether the runtime has the CheckMetadataHash signed extension
+    #[prost(bool, tag="8")]
+    pub check_metadata_hash: bool,
+    /// 32 bytes metadata hash checked by the runtime, disabled if empty
+    /// disregarded if check_metadata_hash is false
+    #[prost(bytes="vec", tag="9")]
+    pub metadata_hash: ::prost::alloc::vec::Vec<u8>,
+    #[prost(oneof="sign_input::Call", tags="10, 11")]
+    pub call: ::core::option::Option<sign_input::Call>,
+}
+/// Nested message and enum types in `SignInput`.
+pub mod sign_input {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Call {
+        #[prost(message, tag="10")]
+        Transfer(super::Transfer),
+        /// A SCALE encoded call
+        #[prost(bytes, tag="11")]
+        RawCall(::prost::alloc::vec::Vec<u8>),
+    }
+}
+/// Transaction signing output.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignOutput {
+    /// SCALE encoded signed extrinsic, prefixed with its length
+    #[prost(bytes="vec", tag="1")]
+    pub encoded: ::prost::alloc::vec::Vec<u8>,
+    /// The signature of the signing payload
+    #[prost(bytes="vec", tag="2")]
+    pub signature: ::prost::alloc::vec::Vec<u8>,
+    /// The signing payload, call followed by the extra and additional signed extension data
+    /// Payloads longer than 256 bytes are signed by their blake2b-256 hash
+    #[prost(bytes="vec", tag="3")]
+    pub payload: ::prost::alloc::vec::Vec<u8>,
+}
@@ -0,0 +1,136 @@
+/// Transfer lamports from the signer to the recipient with the System Program
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Transfer {
+    /// Recipient's address, base58 encoded
+    #[prost(string, tag="1")]
+    pub recipient: ::prost::alloc::string::String,
+    /// amount in lamports
+    #[prost(uint64, tag="2")]
+    pub value: u64,
+    /// optional memo attached with the Memo Program
+    #[prost(string, tag="3")]
+    pub memo: ::prost::alloc::string::String,
+}
+/// Transfer SPL tokens from the signer's associated token account with TransferChecked
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TokenTransfer {
+    /// base58 encoded address of the token mint
+    #[prost(string, tag="1")]
+    pub mint: ::prost::alloc::string::String,
+    /// Recipient's wallet address, base58 encoded
+    /// the tokens are sent to its associated token account
+    #[prost(string, tag="2")]
+    pub recipient: ::prost::alloc::string::String,
+    /// amount in the smallest unit of the token
+    #[prost(uint64, tag="3")]
+    pub amount: u64,
+    /// decimals of the mint, checked by the token program
+    #[prost(uint32, tag="4")]
+    pub decimals: u32,
+    #[prost(enumeration="TokenProgram", tag="5")]
+    pub token_program: i32,
+    /// If true, the recipient's associated token account is created first if it does not exist,
+    /// with the rent paid by the fee payer
+    #[prost(bool, tag="6")]
+    pub create_recipient_account: bool,
+    /// optional memo attached with the Memo Program
+    #[prost(string, tag="7")]
+    pub memo: ::prost::alloc::string::String,
+}
+/// Create the associated token account of a wallet, it succeeds if the account already exists
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CreateTokenAccount {
+    /// Wallet address owning the new account, base58 encoded
+    #[prost(string, tag="1")]
+    pub owner: ::prost::alloc::string::String,
+    /// base58 encoded address of the token mint
+    #[prost(string, tag="2")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(enumeration="TokenProgram", tag="3")]
+    pub token_program: i32,
+}
+/// Close the signer's associated token account, its balance must be zero
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CloseTokenAccount {
+    /// base58 encoded address of the token mint
+    #[prost(string, tag="1")]
+    pub mint: ::prost::alloc::string::String,
+    #[prost(enumeration="TokenProgram", tag="2")]
+    pub token_program: i32,
+    /// base58 encoded address receiving the rent of the closed account
+    /// If empty, the rent goes back to the signer
+    #[prost(string, tag="3")]
+    pub destination: ::prost::alloc::string::String,
+}
+/// Input data necessary to create a signed transaction.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignInput {
+    /// base58 encoded recent blockhash
+    /// disregarded for raw_message and raw_transaction, which carry their own blockhash
+    #[prost(string, tag="1")]
+    pub recent_blockhash: ::prost::alloc::string::String,
+    /// base58 encoded address of the fee payer
+    /// If empty, the signer pays the fee; otherwise the transaction is only partially signed
+    /// disregarded for raw_message and raw_transaction, which carry their own fee payer
+    #[prost(string, tag="2")]
+    pub fee_payer: ::prost::alloc::string::String,
+    /// encoding of SignOutput.encoded
+    #[prost(enumeration="Encoding", tag="3")]
+    pub encoding: i32,
+    #[prost(oneof="sign_input::TransactionType", tags="4, 5, 6, 7, 8, 9")]
+    pub transaction_type: ::core::option::Option<sign_input::TransactionType>,
+}
+/// Nested message and enum types in `SignInput`.
+pub mod sign_input {
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum TransactionType {
+        #[prost(message, tag="4")]
+        TransferTransaction(super::Transfer),
+        /// A serialized legacy or v0 message, the signer must be one of its required signers
+        /// The signatures of the other required signers are left empty
+        #[prost(bytes, tag="5")]
+        RawMessage(::prost::alloc::vec::Vec<u8>),
+        #[prost(message, tag="6")]
+        TokenTransferTransaction(super::TokenTransfer),
+        #[prost(message, tag="7")]
+        CreateTokenAccountTransaction(super::CreateTokenAccount),
+        #[prost(message, tag="8")]
+        CloseTokenAccountTransaction(super::CloseTokenAccount),
+        /// A serialized wire transaction carrying a legacy or v0 message, possibly partially signed
+        /// The signer's signature is added to its slot, the other signatures are kept
+        #[prost(bytes, tag="9")]
+        RawTransaction(::prost::alloc::vec::Vec<u8>),
+    }
+}
+/// Transaction signing output.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignOutput {
+    /// Signed wire transaction, encoded as SignInput.encoding
+    #[prost(string, tag="1")]
+    pub encoded: ::prost::alloc::string::String,
+    /// Signed wire transaction bytes
+    #[prost(bytes="vec", tag="2")]
+    pub raw: ::prost::alloc::vec::Vec<u8>,
+    /// base58 encoded signature of the signer
+    #[prost(string, tag="3")]
+    pub signature: ::prost::alloc::string::String,
+    /// The serialized message that has been signed
+    #[prost(bytes="vec", tag="4")]
+    pub message: ::prost::alloc::vec::Vec<u8>,
+}
+/// Encoding of the signed wire transaction
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum Encoding {
+    Base58 = 0,
+    Base64 = 1,
+}
+/// Token program owning the mint
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum TokenProgram {
+    /// TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA
+    Token = 0,
+    /// TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb
+    Token2022 = 1,
+}
@@ -1,6 +1,9 @@
 mod generated;
 pub use generated::api;
+pub use generated::arweave;
 pub use generated::ethereum;
+pub use generated::polkadot;
+pub use generated::solana;
 
 pub mod coin;
 pub mod convert;
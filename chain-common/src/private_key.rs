@@ -1,6 +1,8 @@
+use super::coin::Coin;
 use super::public_key::PublicKey;
 use crypto::curve::Curve;
 use crypto::public_key::PublicKeyType;
+use crypto::rsa::RsaKey;
 use crypto::Error as CryptoError;
 use std::str::FromStr;
 use std::string::ToString;
@@ -9,6 +11,10 @@ use std::string::ToString;
 const VALID_SIZE: u8 = 32;
 // The number of bytes in an extended private key.
 const VALID_EXTENDED_SIZE: u8 = 3 * VALID_SIZE;
+// The number of bytes in an sr25519 secret key, the key and its nonce.
+const VALID_SR25519_SIZE: u8 = 2 * VALID_SIZE;
+// The minimum number of bytes in a PKCS#1 DER RSA private key, at least its modulus.
+const RSA_MIN_SIZE: usize = crypto::rsa::MIN_MODULUS_BITS / 8;
 
 pub enum PrivateKeyType {
     PrivateKeyTypeDefault32 = 0,  // 32-byte private key
@@ -31,22 +37,38 @@ impl PrivateKey {
         }
     }
 
+    fn is_rsa(coin: &Coin) -> bool {
+        PublicKeyType::from_str(&coin.public_key_type) == Ok(PublicKeyType::Rsa)
+    }
+
     fn is_valid_data(data: &[u8]) -> bool {
-        // Check length.  Extended key needs 3*32 bytes.
-        if data.len() as u8 != VALID_SIZE && data.len() as u8 != VALID_EXTENDED_SIZE {
+        // Check length.  Extended key needs 3*32 bytes, sr25519 key 2*32 bytes.
+        if data.len() as u8 != VALID_SIZE
+            && data.len() as u8 != VALID_EXTENDED_SIZE
+            && data.len() as u8 != VALID_SR25519_SIZE
+        {
             return false;
         }
         // Check whether data is not all zero
         return data.iter().any(|&x| x != 0);
     }
 
-    pub fn is_valid(data: &[u8], curve: &str) -> Result<(), CryptoError> {
-        if !Self::is_valid_data(data) {
+    pub fn is_valid(data: &[u8], coin: &Coin) -> Result<(), CryptoError> {
+        // RSA keys are PKCS#1 DER, fully validated once when imported
+        if Self::is_rsa(coin) {
+            return RsaKey::from_der(data).map(|_| {});
+        }
+        let curve = Curve::from_str(&coin.curve).map_err(|_| CryptoError::NotSupportedCurve)?;
+        // Only sr25519 keys carry a nonce, only extended ed25519 keys an extension and chain code
+        let size = match curve {
+            Curve::Sr25519 => VALID_SR25519_SIZE,
+            Curve::Ed25519Extended => VALID_EXTENDED_SIZE,
+            _ => VALID_SIZE,
+        };
+        if data.len() != size as usize || !Self::is_valid_data(data) {
             return Err(CryptoError::InvalidPrivateKey);
         }
-        Curve::from_str(curve)
-            .map_err(|_| CryptoError::NotSupportedCurve)
-            .map(|_| {})
+        Ok(())
     }
 
     fn new_extended(data: &[u8], ext: &[u8], chain_code: &[u8]) -> Result<PrivateKey, CryptoError> {
@@ -78,6 +100,21 @@ impl PrivateKey {
         }
     }
 
+    /// The private key of `coin`, whose RSA keys are kept as DER and only parsed when used
+    pub fn new_for_coin(data: &[u8], coin: &Coin) -> Result<PrivateKey, CryptoError> {
+        if !Self::is_rsa(coin) {
+            return Self::new(data);
+        }
+        if data.len() < RSA_MIN_SIZE {
+            return Err(CryptoError::InvalidPrivateKey);
+        }
+        Ok(PrivateKey {
+            data: data.to_vec(),
+            extends_data: vec![],
+            chain_code_bytes: vec![],
+        })
+    }
+
     pub fn get_public_key(&self, public_key_type_str: &str) -> Result<PublicKey, CryptoError> {
         let public_key_type = PublicKeyType::from_str(public_key_type_str)
             .map_err(|_| CryptoError::NotSupportedPublicKeyType)?;
@@ -107,8 +144,10 @@ impl ToString for PrivateKey {
 
 #[cfg(test)]
 mod tests {
+    use crate::coin::Coin;
     use crate::private_key::PrivateKey;
     use hex;
+    use std::collections::HashMap;
 
     #[test]
     fn test_get_public_key_secp256k1extended() {
@@ -128,4 +167,49 @@ mod tests {
         let pub_key_hex2 = hex::encode(&pub_key2.data);
         assert_eq!(pub_key_hex2, "0499c6f51ad6f98c9c583f8e92bb7758ab2ca9a04110c0a1126ec43e5453d196c166b489a4b7c491e7688e6ebea3a71fc3a1a48d60f98d5ce84c93b65e423fde91");
     }
+
+    fn coin(curve: &str, public_key_type: &str) -> Coin {
+        Coin {
+            id: "test".to_owned(),
+            name: "Test".to_owned(),
+            coin_id: 0,
+            symbol: "TEST".to_owned(),
+            decimals: 18,
+            blockchain: "Test".to_owned(),
+            derivation_path: "".to_owned(),
+            curve: curve.to_owned(),
+            public_key_type: public_key_type.to_owned(),
+            chain_id: None,
+            all_info: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_is_valid_size_of_curve() {
+        let key = [1u8; 96];
+        let secp256k1 = coin("secp256k1", "secp256k1");
+        let ed25519 = coin("ed25519", "ed25519");
+        let sr25519 = coin("sr25519", "sr25519");
+        assert!(PrivateKey::is_valid(&key[..32], &secp256k1).is_ok());
+        assert!(PrivateKey::is_valid(&key[..64], &secp256k1).is_err());
+        assert!(PrivateKey::is_valid(&key[..64], &ed25519).is_err());
+        assert!(PrivateKey::is_valid(&key[..64], &sr25519).is_ok());
+        assert!(PrivateKey::is_valid(&key[..32], &sr25519).is_err());
+        assert!(PrivateKey::is_valid(&key, &ed25519).is_err());
+        let ed25519_extended = coin("ed25519-cardano-seed", "ed25519");
+        assert!(PrivateKey::is_valid(&key, &ed25519_extended).is_ok());
+        assert!(PrivateKey::is_valid(&[0u8; 32], &secp256k1).is_err());
+    }
+
+    #[test]
+    fn test_rsa_key_only_for_rsa_coin() {
+        let der = [1u8; 300];
+        let rsa = coin("", "rsa");
+        assert!(PrivateKey::new(&der).is_err());
+        assert!(PrivateKey::is_valid(&der, &coin("secp256k1", "secp256k1")).is_err());
+        // Kept as is until used, only an import validates the DER
+        assert_eq!(PrivateKey::new_for_coin(&der, &rsa).unwrap().data, der);
+        assert!(PrivateKey::is_valid(&der, &rsa).is_err());
+        assert!(PrivateKey::new_for_coin(&der[..32], &rsa).is_err());
+    }
 }
@@ -8,6 +8,10 @@ const SECP256K1EXTENDED_SIZE: usize = 65;
 
 const ED25519_SIZE: usize = 32;
 
+const SR25519_SIZE: usize = 32;
+
+const RSA_MIN_SIZE: usize = crypto::rsa::MIN_MODULUS_BITS / 8;
+
 pub struct PublicKey {
     pub r#type: PublicKeyType,
     pub data: Vec<u8>,
@@ -27,6 +31,8 @@ impl PublicKey {
             PublicKeyType::Ed25519 => {
                 size == ED25519_SIZE || (size == ED25519_SIZE + 1 && data[0] == 0x01)
             }
+            PublicKeyType::Sr25519 => size == SR25519_SIZE,
+            PublicKeyType::Rsa => size >= RSA_MIN_SIZE,
         }
     }
 
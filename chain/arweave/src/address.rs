@@ -0,0 +1,86 @@
+use base64::{decode_config, encode_config, URL_SAFE_NO_PAD};
+use chain_common::public_key::PublicKey;
+use crypto::public_key::PublicKeyType;
+use crypto::Error;
+use sha2::{Digest, Sha256};
+use std::str::FromStr;
+
+const ADDRESS_SIZE: usize = 32;
+
+/// The base64url SHA-256 of the owner, the modulus of the wallet's RSA key
+pub struct ArweaveAddress {
+    pub data: Vec<u8>,
+}
+
+impl ArweaveAddress {
+    pub fn new(public_key: &PublicKey) -> Result<Self, Error> {
+        if public_key.r#type != PublicKeyType::Rsa {
+            return Err(Error::NotSupportedPublicKeyType);
+        }
+        Ok(Self::from_owner(&public_key.data))
+    }
+
+    pub fn from_owner(owner: &[u8]) -> Self {
+        ArweaveAddress {
+            data: Sha256::digest(owner).to_vec(),
+        }
+    }
+
+    pub fn is_valid(address: &str) -> bool {
+        Self::from_str(address).is_ok()
+    }
+}
+
+impl FromStr for ArweaveAddress {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let data = decode_config(s, URL_SAFE_NO_PAD).map_err(|_| Error::InvalidPublicKey)?;
+        // Reject the non canonical encodings of the same bytes
+        if data.len() != ADDRESS_SIZE || encode_config(&data, URL_SAFE_NO_PAD) != s {
+            return Err(Error::InvalidPublicKey);
+        }
+        Ok(ArweaveAddress { data })
+    }
+}
+
+impl std::fmt::Display for ArweaveAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", encode_config(&self.data, URL_SAFE_NO_PAD))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_of_owner() {
+        let owner = decode_config("rSZAlWKVI-SWqLCNNwEKCZIfLVpgbj0Nrt_Bt_NiQRbmrUzY51Aiwun66UIiMf1BJcAiwz5gPSPxVMTjr0rvow8rymerVVuVhBQsQc2_AmVMOPHdDsF3bf4xVE3gLED85FL05hA8_qyVEvY32zdLcGw7zqqCe5OaY-wpl4fNINZzR7vLdcqhtYyO8hJz_FWclhkbjjXYoId6O1_VIp4Mc90MtQ9BGlCQ8zX2H7EZHM8Sg2hQSDi_Lc6pltRo0prVferrvOL-Gju1_g6hzUtzf51cB9xE0utfZl0Uz2M_DB67EiMLTim2rJxgqsdMLP5lu20DSGp5UBXgOitUFBjrOw", URL_SAFE_NO_PAD).unwrap();
+        let public_key = PublicKey::new(PublicKeyType::Rsa, &owner).unwrap();
+        let address = ArweaveAddress::new(&public_key).unwrap();
+        assert_eq!(
+            address.to_string(),
+            "pJDD7ACZwCuTUcdJ5KbPdJjIExqbfydss0ve6g6UD3k"
+        );
+    }
+
+    #[test]
+    fn test_is_valid() {
+        assert!(ArweaveAddress::is_valid(
+            "pJDD7ACZwCuTUcdJ5KbPdJjIExqbfydss0ve6g6UD3k"
+        ));
+        // Padded, truncated or standard base64
+        assert!(!ArweaveAddress::is_valid(
+            "pJDD7ACZwCuTUcdJ5KbPdJjIExqbfydss0ve6g6UD3k="
+        ));
+        assert!(!ArweaveAddress::is_valid(
+            "pJDD7ACZwCuTUcdJ5KbPdJjIExqbfydss0ve6g6UD3"
+        ));
+        assert!(!ArweaveAddress::is_valid(
+            "NKBABbyvIG7smQvZY32f22cl4KDA1K6/AD8X9MlW61w"
+        ));
+        assert!(ArweaveAddress::is_valid(
+            "NKBABbyvIG7smQvZY32f22cl4KDA1K6_AD8X9MlW61w"
+        ));
+    }
+}
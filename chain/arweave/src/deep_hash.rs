@@ -0,0 +1,49 @@
+use sha2::{Digest, Sha384};
+
+/// A value of the Arweave deep hash, the signature data of transactions
+pub enum DeepHashItem {
+    Blob(Vec<u8>),
+    List(Vec<DeepHashItem>),
+}
+
+pub fn deep_hash(item: &DeepHashItem) -> Vec<u8> {
+    match item {
+        DeepHashItem::Blob(data) => {
+            let tag = Sha384::digest(format!("blob{}", data.len()));
+            Sha384::digest([tag.to_vec(), Sha384::digest(data).to_vec()].concat()).to_vec()
+        }
+        DeepHashItem::List(items) => {
+            let tag = Sha384::digest(format!("list{}", items.len())).to_vec();
+            items.iter().fold(tag, |acc, item| {
+                Sha384::digest([acc, deep_hash(item)].concat()).to_vec()
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_deep_hash() {
+        let blob = DeepHashItem::Blob(b"Mask".to_vec());
+        let blob_hash = Sha384::digest(
+            [
+                Sha384::digest(b"blob4").to_vec(),
+                Sha384::digest(b"Mask").to_vec(),
+            ]
+            .concat(),
+        );
+        assert_eq!(deep_hash(&blob), blob_hash.to_vec());
+
+        let list = DeepHashItem::List(vec![blob]);
+        let list_hash =
+            Sha384::digest([Sha384::digest(b"list1").to_vec(), blob_hash.to_vec()].concat());
+        assert_eq!(deep_hash(&list), list_hash.to_vec());
+        assert_eq!(
+            deep_hash(&DeepHashItem::List(vec![])),
+            Sha384::digest(b"list0").to_vec()
+        );
+    }
+}
@@ -0,0 +1,98 @@
+use base64::{decode_config, URL_SAFE_NO_PAD};
+use crypto::rsa::RsaKey;
+use crypto::Error;
+use serde::{Deserialize, Serialize};
+use std::str::FromStr;
+
+/// An Arweave wallet file, the JWK of an RSA private key
+#[derive(Serialize, Deserialize)]
+pub struct RsaJwk {
+    pub kty: String,
+    pub n: String,
+    pub e: String,
+    pub d: String,
+    pub p: String,
+    pub q: String,
+    pub dp: String,
+    pub dq: String,
+    pub qi: String,
+}
+
+impl FromStr for RsaJwk {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let jwk: RsaJwk = serde_json::from_str(s).map_err(|_| Error::InvalidKeyStoreJson)?;
+        if jwk.kty != "RSA" {
+            return Err(Error::InvalidKeyStoreJson);
+        }
+        Ok(jwk)
+    }
+}
+
+impl RsaJwk {
+    /// The PKCS#1 DER private key
+    pub fn to_private_key(&self) -> Result<Vec<u8>, Error> {
+        let components = [
+            &self.n, &self.e, &self.d, &self.p, &self.q, &self.dp, &self.dq, &self.qi,
+        ]
+        .map(|component| decode_config(component, URL_SAFE_NO_PAD));
+        let components = components
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|_| Error::InvalidPrivateKey)?;
+        let components: Vec<&[u8]> = components.iter().map(Vec::as_slice).collect();
+        let components: [&[u8]; 8] = components
+            .try_into()
+            .map_err(|_| Error::InvalidPrivateKey)?;
+        RsaKey::from_components(components)?.to_der()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use chain_common::private_key::PrivateKey;
+
+    pub const JWK: &str = r#"
+    {
+        "kty": "RSA",
+        "n": "rSZAlWKVI-SWqLCNNwEKCZIfLVpgbj0Nrt_Bt_NiQRbmrUzY51Aiwun66UIiMf1BJcAiwz5gPSPxVMTjr0rvow8rymerVVuVhBQsQc2_AmVMOPHdDsF3bf4xVE3gLED85FL05hA8_qyVEvY32zdLcGw7zqqCe5OaY-wpl4fNINZzR7vLdcqhtYyO8hJz_FWclhkbjjXYoId6O1_VIp4Mc90MtQ9BGlCQ8zX2H7EZHM8Sg2hQSDi_Lc6pltRo0prVferrvOL-Gju1_g6hzUtzf51cB9xE0utfZl0Uz2M_DB67EiMLTim2rJxgqsdMLP5lu20DSGp5UBXgOitUFBjrOw",
+        "e": "AQAB",
+        "d": "ESjsV6G4hCEgaUFuDVh2BTLNEjKVwfcdBwq36SIex7KcU-p8yJfhig2kRz-9odSmgzZCe7UP1p9cSHlm0zWLAE79mQajLUdxTvCs3iyk1Hjzj-UwckjsLCN4AhHhuKEMLxilmrNbSCYUdBegu4IEYPTpDHdLjTG4qFR8Xr8frjRIozcYUDaiWAF7WKKpv6L91QAAjMDhKAUAr6ZC63v-gPvyvRwjEJUZcocNQFeiZRuojOQ_u07AXKy5IHvnDbQQkwzfguICav9LYh3Kr9MPOg92FCkRdCN5QjmdXucno3nLXq08NjUMzu6DpiSYTdJ4xVXX7cmqHRFv99zw49DXmQ",
+        "p": "67IF5tIoKI3D8AauMDCloQ0ftGq04f3BXPDGVwgZLvApkRFET7ttlclyNAZI0gv0yAEOM7BruGBuRSiEdt1YuVRenxhZKjZBrtjAHyqx3cf3Kqe5ZS855NoGYb9N_flgQxPmUjJvtfGJvvG25_EnHu218tDBeB8Tld1gBJGNVPc",
+        "q": "vBDacwh_4mhQE3b_vdoViU-XtgSBt0K2os2yGr-hrZY1U6GhPCbfHgVJQ-qgVgOlhyWn7n62xSja8O_eNj2AS7tJbfU9WdJ83tjz-2DTj-stQyqk9UUjxC_qZzVFHzr4N7prbyYA6OKsDRpKgs2lRmrCZ8k5x5PM69VoT1yjft0",
+        "dp": "38QoLjgfwEC3YU42WbIVzY00KImWKWhNB_vfkgkyP4nrLLsD696575dNQW8l-6U2RBwca5525plGl4ocGO2Uqn8bRlhLbvQL8AAAhpZ2P2T1mB2cuh_YCxmjLXH-jb0QUx61cBxKSqaCbDH3wMUGqyprr5fZ1j1KzuFZo3y8fYk",
+        "dq": "XXF2SR_ZAvLXjsRaz8czWjV86QiWNytjucLAkv_SYoC24GX3yf56b90qiR9kKTjInzz7ksnNIGtrVbmpTIQEYhydoI_wuteWCdf8alOy6thsdeoTana_DbTNIBjczmarfG0ZAnH8k7pEyIHesd1iGHnlPFJEbkCm2isAGCasadU",
+        "qi": "ejXqJgjClfZn6KaPUeWqfbI4X14mphKa8U3U7KtWKfVPGMlSF9zM1NZT9cGDZLLIS8EI4JXLDGOxpjREpbgPtJczrrFar0rg_qnoFHx9FxbaxTtVXln2ga3ynTOV_n4rkOM7Hogv_lnwQh4MmKcXq5KPXbesWpH9QnHsrH4BsGI"
+    }
+    "#;
+
+    pub fn private_key() -> PrivateKey {
+        let der = RsaJwk::from_str(JWK).unwrap().to_private_key().unwrap();
+        PrivateKey {
+            data: der,
+            extends_data: vec![],
+            chain_code_bytes: vec![],
+        }
+    }
+
+    #[test]
+    fn test_to_private_key() {
+        let jwk = RsaJwk::from_str(JWK).unwrap();
+        let der = jwk.to_private_key().unwrap();
+        assert_eq!(hex::encode(&der[..16]), "308204a30201000282010100ad264095");
+        let public_key = private_key().get_public_key("rsa").unwrap();
+        assert_eq!(
+            base64::encode_config(&public_key.data, URL_SAFE_NO_PAD),
+            jwk.n
+        );
+    }
+
+    #[test]
+    fn test_invalid_jwk() {
+        assert!(RsaJwk::from_str(&JWK.replace("\"RSA\"", "\"EC\"")).is_err());
+        // The primes don't match the modulus
+        let jwk = RsaJwk::from_str(&JWK.replace("\"p\": \"67IF", "\"p\": \"77IF")).unwrap();
+        assert_eq!(jwk.to_private_key().unwrap_err(), Error::InvalidPrivateKey);
+    }
+}
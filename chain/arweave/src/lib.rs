@@ -0,0 +1,7 @@
+pub mod address;
+mod deep_hash;
+pub mod entry;
+pub mod jwk;
+mod merkle;
+mod signer;
+mod transaction;
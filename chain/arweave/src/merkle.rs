@@ -0,0 +1,112 @@
+use sha2::{Digest, Sha256};
+
+const MAX_CHUNK_SIZE: usize = 256 * 1024;
+const MIN_CHUNK_SIZE: usize = 32 * 1024;
+
+// Byte offsets are hashed as 32 bytes big endian notes
+const NOTE_SIZE: usize = 32;
+
+struct Node {
+    id: Vec<u8>,
+    max_byte_range: usize,
+}
+
+/// Root of the merkle tree of the chunks of `data`, the `data_root` of a format 2 transaction
+pub fn data_root(data: &[u8]) -> Vec<u8> {
+    let mut nodes: Vec<Node> = chunk_ranges(data.len())
+        .into_iter()
+        .map(|(start, end)| Node {
+            id: hash(&[&hash(&[&hash(&[&data[start..end]])]), &hash(&[&note(end)])]),
+            max_byte_range: end,
+        })
+        .collect();
+    while nodes.len() > 1 {
+        let mut layer = Vec::with_capacity(nodes.len() / 2 + 1);
+        let mut nodes_iter = nodes.into_iter();
+        while let Some(left) = nodes_iter.next() {
+            match nodes_iter.next() {
+                Some(right) => layer.push(Node {
+                    id: hash(&[
+                        &hash(&[&left.id]),
+                        &hash(&[&right.id]),
+                        &hash(&[&note(left.max_byte_range)]),
+                    ]),
+                    max_byte_range: right.max_byte_range,
+                }),
+                None => layer.push(left),
+            }
+        }
+        nodes = layer;
+    }
+    nodes.remove(0).id
+}
+
+/// Chunks of at most 256 KiB, the last two balanced so that the last one isn't under 32 KiB
+fn chunk_ranges(size: usize) -> Vec<(usize, usize)> {
+    let mut ranges = vec![];
+    let mut cursor = 0;
+    while size - cursor >= MAX_CHUNK_SIZE {
+        let rest = size - cursor;
+        let next_chunk_size = rest - MAX_CHUNK_SIZE;
+        let chunk_size = if next_chunk_size > 0 && next_chunk_size < MIN_CHUNK_SIZE {
+            rest / 2 + rest % 2
+        } else {
+            MAX_CHUNK_SIZE
+        };
+        ranges.push((cursor, cursor + chunk_size));
+        cursor += chunk_size;
+    }
+    ranges.push((cursor, size));
+    ranges
+}
+
+fn hash(data: &[&[u8]]) -> Vec<u8> {
+    Sha256::digest(data.concat()).to_vec()
+}
+
+fn note(offset: usize) -> [u8; NOTE_SIZE] {
+    let mut note = [0u8; NOTE_SIZE];
+    note[NOTE_SIZE - 8..].copy_from_slice(&(offset as u64).to_be_bytes());
+    note
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base64::{encode_config, URL_SAFE_NO_PAD};
+
+    fn root_of_size(size: usize) -> String {
+        let data: Vec<u8> = (0..size).map(|i| (i % 251) as u8).collect();
+        encode_config(data_root(&data), URL_SAFE_NO_PAD)
+    }
+
+    #[test]
+    fn test_chunk_ranges() {
+        assert_eq!(chunk_ranges(10), vec![(0, 10)]);
+        assert_eq!(
+            chunk_ranges(MAX_CHUNK_SIZE),
+            vec![(0, MAX_CHUNK_SIZE), (MAX_CHUNK_SIZE, MAX_CHUNK_SIZE)]
+        );
+        // A last chunk under 32 KiB is merged into the previous one, and split in halves
+        assert_eq!(
+            chunk_ranges(270 * 1024),
+            vec![(0, 135 * 1024), (135 * 1024, 270 * 1024)]
+        );
+    }
+
+    #[test]
+    fn test_data_root() {
+        assert_eq!(
+            encode_config(data_root(b"Hello Mask"), URL_SAFE_NO_PAD),
+            "INXltbZ_io27Hvi445UBK4kFE9mIWUIL3pW8aA5Wu7w"
+        );
+        assert_eq!(
+            root_of_size(270 * 1024),
+            "ddrV_K2dxOv4QnwjiJWsMdhNa53ptrXf5Hzh9ovx_w8"
+        );
+        assert_eq!(
+            root_of_size(600 * 1024),
+            "N_gS_f6fPYsmpog4LZJLEwgKVekMLOLMrdCXsi2Z_oc"
+        );
+    }
+}
@@ -0,0 +1,71 @@
+use super::transaction::Transaction;
+use chain_common::arweave::{SignInput, SignOutput};
+use chain_common::private_key::PrivateKey;
+use chain_common::Error;
+use crypto::rsa::RsaKey;
+use sha2::{Digest, Sha256};
+
+pub struct Signer;
+
+impl Signer {
+    pub fn sign(private_key: &PrivateKey, sign_input: &SignInput) -> Result<SignOutput, Error> {
+        let key = RsaKey::from_der(&private_key.data).map_err(|_| Error::InvalidPrivateKey)?;
+        let transaction = Transaction::new(&key.modulus(), sign_input)?;
+        let signature = key
+            .sign_pss(&transaction.signature_data())
+            .map_err(|_| Error::InvalidPrivateKey)?;
+        Ok(SignOutput {
+            id: Transaction::id(&signature),
+            encoded: transaction.to_json(&signature),
+            signature,
+        })
+    }
+
+    /// Sign `message` the way ArConnect `signMessage` does, the RSA-PSS signature of its SHA-256
+    pub fn sign_message(private_key: &PrivateKey, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let key = RsaKey::from_der(&private_key.data).map_err(|_| Error::InvalidPrivateKey)?;
+        key.sign_pss(&Sha256::digest(message))
+            .map_err(|_| Error::InvalidPrivateKey)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jwk::tests::private_key;
+    use crate::transaction::tests::{owner, sign_input};
+    use base64::{decode_config, URL_SAFE_NO_PAD};
+    use crypto::rsa::verify_pss;
+
+    #[test]
+    fn test_sign() {
+        let output = Signer::sign(&private_key(), &sign_input()).unwrap();
+        let signature_data = Transaction::new(&owner(), &sign_input())
+            .unwrap()
+            .signature_data();
+        assert_eq!(output.signature.len(), 256);
+        assert!(verify_pss(&owner(), &signature_data, &output.signature));
+        assert_eq!(output.id, Transaction::id(&output.signature));
+
+        let json: serde_json::Value = serde_json::from_str(&output.encoded).unwrap();
+        assert_eq!(json["format"], 2);
+        assert_eq!(json["id"], output.id);
+        assert_eq!(json["data"], "SGVsbG8gTWFzaw");
+        assert_eq!(
+            json["data_root"],
+            "INXltbZ_io27Hvi445UBK4kFE9mIWUIL3pW8aA5Wu7w"
+        );
+        assert_eq!(json["tags"][1]["value"], "TWFzaw");
+        let signature =
+            decode_config(json["signature"].as_str().unwrap(), URL_SAFE_NO_PAD).unwrap();
+        assert_eq!(signature, output.signature);
+    }
+
+    #[test]
+    fn test_sign_message() {
+        let signature = Signer::sign_message(&private_key(), b"Hello Mask").unwrap();
+        let digest = Sha256::digest(b"Hello Mask");
+        assert!(verify_pss(&owner(), &digest, &signature));
+        assert!(!verify_pss(&owner(), b"Hello Mask", &signature));
+    }
+}
@@ -0,0 +1,225 @@
+use super::address::ArweaveAddress;
+use super::deep_hash::{deep_hash, DeepHashItem};
+use super::merkle::data_root;
+use base64::{decode_config, encode_config, URL_SAFE_NO_PAD};
+use chain_common::arweave::SignInput;
+use chain_common::Error;
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::str::FromStr;
+
+const FORMAT: u8 = 2;
+
+const DATA_ROOT_SIZE: usize = 32;
+
+/// A format 2 transaction, whose data is committed to by its merkle root
+pub struct Transaction {
+    owner: Vec<u8>,
+    target: Vec<u8>,
+    quantity: String,
+    reward: String,
+    last_tx: Vec<u8>,
+    tags: Vec<(Vec<u8>, Vec<u8>)>,
+    data: Vec<u8>,
+    data_size: String,
+    data_root: Vec<u8>,
+}
+
+impl Transaction {
+    pub fn new(owner: &[u8], sign_input: &SignInput) -> Result<Self, Error> {
+        let target = match sign_input.target.as_str() {
+            "" => vec![],
+            target => {
+                ArweaveAddress::from_str(target)
+                    .map_err(|_| Error::InvalidSignInput)?
+                    .data
+            }
+        };
+        let quantity = match sign_input.quantity.as_str() {
+            "" => "0".to_owned(),
+            quantity => winston(quantity)?,
+        };
+        let reward = winston(&sign_input.reward)?;
+        let last_tx = decode(&sign_input.last_tx)?;
+        if last_tx.is_empty() {
+            return Err(Error::InvalidSignInput);
+        }
+        let tags = sign_input
+            .tags
+            .iter()
+            .map(|tag| (tag.name.as_bytes().to_vec(), tag.value.as_bytes().to_vec()))
+            .collect();
+
+        let (data_size, data_root) = match (sign_input.data.len(), sign_input.data_size.as_str()) {
+            (0, "" | "0") => ("0".to_owned(), vec![]),
+            (0, data_size) => {
+                let data_root = decode(&sign_input.data_root)?;
+                if data_root.len() != DATA_ROOT_SIZE {
+                    return Err(Error::InvalidSignInput);
+                }
+                (winston(data_size)?, data_root)
+            }
+            (size, _) => (size.to_string(), data_root(&sign_input.data)),
+        };
+
+        Ok(Transaction {
+            owner: owner.to_vec(),
+            target,
+            quantity,
+            reward,
+            last_tx,
+            tags,
+            data: sign_input.data.to_vec(),
+            data_size,
+            data_root,
+        })
+    }
+
+    /// The deep hash of the fields, signed by the owner
+    pub fn signature_data(&self) -> Vec<u8> {
+        let blob = |data: &[u8]| DeepHashItem::Blob(data.to_vec());
+        let tags = self
+            .tags
+            .iter()
+            .map(|(name, value)| DeepHashItem::List(vec![blob(name), blob(value)]))
+            .collect();
+        deep_hash(&DeepHashItem::List(vec![
+            blob(FORMAT.to_string().as_bytes()),
+            blob(&self.owner),
+            blob(&self.target),
+            blob(self.quantity.as_bytes()),
+            blob(self.reward.as_bytes()),
+            blob(&self.last_tx),
+            DeepHashItem::List(tags),
+            blob(self.data_size.as_bytes()),
+            blob(&self.data_root),
+        ]))
+    }
+
+    /// The id of the transaction signed by `signature`
+    pub fn id(signature: &[u8]) -> String {
+        encode(&Sha256::digest(signature))
+    }
+
+    /// The JSON of the signed transaction accepted by `POST /tx`
+    pub fn to_json(&self, signature: &[u8]) -> String {
+        let tags: Vec<_> = self
+            .tags
+            .iter()
+            .map(|(name, value)| json!({ "name": encode(name), "value": encode(value) }))
+            .collect();
+        json!({
+            "format": FORMAT,
+            "id": Self::id(signature),
+            "last_tx": encode(&self.last_tx),
+            "owner": encode(&self.owner),
+            "tags": tags,
+            "target": encode(&self.target),
+            "quantity": self.quantity,
+            "data": encode(&self.data),
+            "data_size": self.data_size,
+            "data_root": encode(&self.data_root),
+            "reward": self.reward,
+            "signature": encode(signature),
+        })
+        .to_string()
+    }
+}
+
+/// An amount in winston, a decimal integer string
+fn winston(amount: &str) -> Result<String, Error> {
+    if amount.is_empty() || !amount.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(Error::InvalidSignInput);
+    }
+    Ok(amount.to_owned())
+}
+
+fn decode(data: &str) -> Result<Vec<u8>, Error> {
+    decode_config(data, URL_SAFE_NO_PAD).map_err(|_| Error::InvalidSignInput)
+}
+
+fn encode(data: &[u8]) -> String {
+    encode_config(data, URL_SAFE_NO_PAD)
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use chain_common::arweave::Tag;
+
+    pub fn sign_input() -> SignInput {
+        SignInput {
+            target: "NKBABbyvIG7smQvZY32f22cl4KDA1K6_AD8X9MlW61w".to_owned(),
+            quantity: "1000000000000".to_owned(),
+            reward: "1234567".to_owned(),
+            last_tx: "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8gISIjJCUmJygpKissLS4v".to_owned(),
+            tags: vec![
+                Tag {
+                    name: "Content-Type".to_owned(),
+                    value: "text/plain".to_owned(),
+                },
+                Tag {
+                    name: "App-Name".to_owned(),
+                    value: "Mask".to_owned(),
+                },
+            ],
+            data: b"Hello Mask".to_vec(),
+            data_size: "".to_owned(),
+            data_root: "".to_owned(),
+        }
+    }
+
+    pub fn owner() -> Vec<u8> {
+        decode("rSZAlWKVI-SWqLCNNwEKCZIfLVpgbj0Nrt_Bt_NiQRbmrUzY51Aiwun66UIiMf1BJcAiwz5gPSPxVMTjr0rvow8rymerVVuVhBQsQc2_AmVMOPHdDsF3bf4xVE3gLED85FL05hA8_qyVEvY32zdLcGw7zqqCe5OaY-wpl4fNINZzR7vLdcqhtYyO8hJz_FWclhkbjjXYoId6O1_VIp4Mc90MtQ9BGlCQ8zX2H7EZHM8Sg2hQSDi_Lc6pltRo0prVferrvOL-Gju1_g6hzUtzf51cB9xE0utfZl0Uz2M_DB67EiMLTim2rJxgqsdMLP5lu20DSGp5UBXgOitUFBjrOw").unwrap()
+    }
+
+    #[test]
+    fn test_signature_data() {
+        let transaction = Transaction::new(&owner(), &sign_input()).unwrap();
+        assert_eq!(transaction.data_size, "10");
+        assert_eq!(
+            hex::encode(transaction.signature_data()),
+            "4b130236d7c8a57f47d50fc015aeedf35be3795d815c057af65ac39e533dfa36d637e4eb3c25e3d2b82f019610cb358b"
+        );
+
+        // Data uploaded in chunks is committed to by its root
+        let input = SignInput {
+            data: vec![],
+            data_size: "10".to_owned(),
+            data_root: "INXltbZ_io27Hvi445UBK4kFE9mIWUIL3pW8aA5Wu7w".to_owned(),
+            ..sign_input()
+        };
+        let chunked = Transaction::new(&owner(), &input).unwrap();
+        assert_eq!(chunked.signature_data(), transaction.signature_data());
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        let inputs = [
+            SignInput {
+                reward: "".to_owned(),
+                ..sign_input()
+            },
+            SignInput {
+                quantity: "-1".to_owned(),
+                ..sign_input()
+            },
+            SignInput {
+                target: "0x8F140c590b1E2C8549ca23F22492f281379eb323".to_owned(),
+                ..sign_input()
+            },
+            SignInput {
+                last_tx: "".to_owned(),
+                ..sign_input()
+            },
+            SignInput {
+                data: vec![],
+                data_size: "10".to_owned(),
+                ..sign_input()
+            },
+        ];
+        for input in inputs {
+            assert!(Transaction::new(&owner(), &input).is_err());
+        }
+    }
+}
@@ -0,0 +1,822 @@
+use super::address::EthereumAddress;
+use super::address_checksum::{checksum, ChecksumType};
+use super::eip712::{decode_hex, encode_atomic, encode_uint, keccak256};
+use chain_common::Error;
+use ethereum_types::U256;
+use serde_json::{json, Map, Value};
+
+/// A Solidity type of a contract function parameter
+#[derive(Clone, Debug, PartialEq)]
+pub enum ParamType {
+    Address,
+    Bool,
+    Uint(usize),
+    Int(usize),
+    FixedBytes(usize),
+    Bytes,
+    String,
+    Array(Box<ParamType>),
+    FixedArray(Box<ParamType>, usize),
+    Tuple(Vec<Param>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Param {
+    pub name: String,
+    pub kind: ParamType,
+}
+
+/// A contract function, parsed from a signature like `transfer(address to, uint256 amount)` or
+/// from a JSON ABI function fragment
+#[derive(Clone, Debug, PartialEq)]
+pub struct Function {
+    pub name: String,
+    pub inputs: Vec<Param>,
+}
+
+impl Function {
+    /// The canonical signature, e.g. `transfer(address,uint256)`
+    pub fn signature(&self) -> String {
+        format!("{}{}", self.name, tuple_type_name(&self.inputs))
+    }
+
+    /// The first 4 bytes of keccak256 of the signature
+    pub fn selector(&self) -> Result<Vec<u8>, Error> {
+        Ok(keccak256(self.signature().as_bytes())?[..4].to_vec())
+    }
+
+    /// Encode the calldata, `args` is either a JSON array of the arguments in order or a JSON
+    /// object keyed by the parameter names
+    pub fn encode_input(&self, args: &Value) -> Result<Vec<u8>, Error> {
+        let values = tuple_values(&self.inputs, args)?;
+        let kinds = self
+            .inputs
+            .iter()
+            .map(|param| &param.kind)
+            .collect::<Vec<_>>();
+        let mut encoded = self.selector()?;
+        encoded.extend_from_slice(&encode_sequence(&kinds, &values)?);
+        Ok(encoded)
+    }
+
+    /// Decode the calldata into a JSON array of `{"name", "type", "value"}` objects
+    ///
+    /// Integers are decimal strings, addresses are EIP-55 checksummed, bytes are 0x prefixed hex
+    /// and tuples are objects keyed by the component names
+    pub fn decode_input(&self, data: &[u8]) -> Result<Value, Error> {
+        if data.len() < 4 || data[..4] != self.selector()? {
+            return Err(Error::InvalidSignInput);
+        }
+        let kinds = self
+            .inputs
+            .iter()
+            .map(|param| &param.kind)
+            .collect::<Vec<_>>();
+        let values = decode_sequence(&kinds, &data[4..])?;
+        let args = self
+            .inputs
+            .iter()
+            .zip(values)
+            .map(|(param, value)| {
+                json!({
+                    "name": param.name,
+                    "type": type_name(&param.kind),
+                    "value": value,
+                })
+            })
+            .collect();
+        Ok(Value::Array(args))
+    }
+}
+
+/// Parse the functions of `abi`, which is a function signature, a JSON ABI function fragment or a
+/// full JSON ABI whose non-function entries are skipped
+pub fn parse_functions(abi: &str) -> Result<Vec<Function>, Error> {
+    let abi = abi.trim();
+    if !abi.starts_with('{') && !abi.starts_with('[') {
+        return Ok(vec![parse_signature(abi)?]);
+    }
+    let value: Value = serde_json::from_str(abi).map_err(|_| Error::InvalidSignInput)?;
+    let fragments = match value {
+        Value::Array(fragments) => fragments,
+        fragment => vec![fragment],
+    };
+    fragments
+        .iter()
+        .filter(|fragment| {
+            let r#type = fragment.get("type").and_then(Value::as_str);
+            r#type.is_none() || r#type == Some("function")
+        })
+        .map(parse_fragment)
+        .collect()
+}
+
+/// Find the function to encode in `abi` by name, or by signature for overloaded functions
+///
+/// An empty `name` picks the only function of the ABI
+pub fn find_function(abi: &str, name: &str) -> Result<Function, Error> {
+    let mut functions = parse_functions(abi)?
+        .into_iter()
+        .filter(|function| match (name.is_empty(), name.contains('(')) {
+            (true, _) => true,
+            (false, true) => function.signature() == name.replace(' ', ""),
+            (false, false) => function.name == name,
+        })
+        .collect::<Vec<_>>();
+    match functions.len() {
+        1 => Ok(functions.remove(0)),
+        _ => Err(Error::InvalidSignInput),
+    }
+}
+
+/// Decode `data` with the function of `abi` whose selector it starts with
+pub fn decode_call(abi: &str, data: &[u8]) -> Result<(Function, Value), Error> {
+    if data.len() < 4 {
+        return Err(Error::InvalidSignInput);
+    }
+    for function in parse_functions(abi)? {
+        if function.selector()? == data[..4] {
+            let args = function.decode_input(data)?;
+            return Ok((function, args));
+        }
+    }
+    Err(Error::InvalidSignInput)
+}
+
+fn parse_signature(signature: &str) -> Result<Function, Error> {
+    let signature = signature.strip_prefix("function ").unwrap_or(signature);
+    let start = signature.find('(').ok_or(Error::InvalidSignInput)?;
+    let end = matching_paren(signature, start)?;
+    let name = signature[..start].trim();
+    if !is_identifier(name) {
+        return Err(Error::InvalidSignInput);
+    }
+    // Modifiers and return values after the parameter list are ignored
+    let inputs = split_params(&signature[start + 1..end])?
+        .into_iter()
+        .map(parse_param)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Function {
+        name: name.to_owned(),
+        inputs,
+    })
+}
+
+fn parse_fragment(fragment: &Value) -> Result<Function, Error> {
+    let name = fragment
+        .get("name")
+        .and_then(Value::as_str)
+        .ok_or(Error::InvalidSignInput)?;
+    if !is_identifier(name) {
+        return Err(Error::InvalidSignInput);
+    }
+    Ok(Function {
+        name: name.to_owned(),
+        inputs: parse_json_params(fragment.get("inputs").unwrap_or(&Value::Null))?,
+    })
+}
+
+fn parse_json_params(params: &Value) -> Result<Vec<Param>, Error> {
+    let params = match params {
+        Value::Null => return Ok(vec![]),
+        params => params.as_array().ok_or(Error::InvalidSignInput)?,
+    };
+    params
+        .iter()
+        .map(|param| {
+            let name = param.get("name").and_then(Value::as_str).unwrap_or("");
+            let r#type = param
+                .get("type")
+                .and_then(Value::as_str)
+                .ok_or(Error::InvalidSignInput)?;
+            // Tuple components are listed separately, `tuple[2][]` keeps its array suffixes
+            let kind = match r#type.strip_prefix("tuple") {
+                Some(suffix) if !suffix.starts_with('(') => {
+                    let components =
+                        parse_json_params(param.get("components").ok_or(Error::InvalidSignInput)?)?;
+                    parse_array_suffix(ParamType::Tuple(components), suffix)?
+                }
+                _ => parse_type(r#type)?,
+            };
+            Ok(Param {
+                name: name.to_owned(),
+                kind,
+            })
+        })
+        .collect()
+}
+
+// `type [indexed|memory|calldata|storage] [name]`
+fn parse_param(param: &str) -> Result<Param, Error> {
+    let mut depth = 0;
+    let mut type_end = param.len();
+    for (index, char) in param.char_indices() {
+        match char {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            char if char.is_whitespace() && depth == 0 => {
+                type_end = index;
+                break;
+            }
+            _ => {}
+        }
+    }
+    let words = param[type_end..]
+        .split_whitespace()
+        .filter(|word| !matches!(*word, "indexed" | "memory" | "calldata" | "storage"))
+        .collect::<Vec<_>>();
+    let name = match words.as_slice() {
+        [] => "",
+        [name] if is_identifier(name) => name,
+        _ => return Err(Error::InvalidSignInput),
+    };
+    Ok(Param {
+        name: name.to_owned(),
+        kind: parse_type(&param[..type_end])?,
+    })
+}
+
+fn parse_type(r#type: &str) -> Result<ParamType, Error> {
+    let r#type = r#type.trim();
+    if r#type.ends_with(']') {
+        let start = r#type.rfind('[').ok_or(Error::InvalidSignInput)?;
+        return parse_array_suffix(parse_type(&r#type[..start])?, &r#type[start..]);
+    }
+    let tuple = r#type.strip_prefix("tuple").unwrap_or(r#type);
+    if tuple.starts_with('(') {
+        if matching_paren(tuple, 0)? != tuple.len() - 1 {
+            return Err(Error::InvalidSignInput);
+        }
+        let components = split_params(&tuple[1..tuple.len() - 1])?
+            .into_iter()
+            .map(parse_param)
+            .collect::<Result<Vec<_>, _>>()?;
+        return Ok(ParamType::Tuple(components));
+    }
+    let kind = match r#type {
+        "address" => ParamType::Address,
+        "bool" => ParamType::Bool,
+        "string" => ParamType::String,
+        "bytes" => ParamType::Bytes,
+        "uint" => ParamType::Uint(256),
+        "int" => ParamType::Int(256),
+        _ => {
+            if let Some(size) = r#type.strip_prefix("bytes") {
+                match size.parse() {
+                    Ok(size) if (1..=32).contains(&size) => ParamType::FixedBytes(size),
+                    _ => return Err(Error::InvalidSignInput),
+                }
+            } else if let Some(bits) = r#type.strip_prefix("uint") {
+                ParamType::Uint(parse_bits(bits)?)
+            } else if let Some(bits) = r#type.strip_prefix("int") {
+                ParamType::Int(parse_bits(bits)?)
+            } else {
+                return Err(Error::InvalidSignInput);
+            }
+        }
+    };
+    Ok(kind)
+}
+
+fn parse_bits(bits: &str) -> Result<usize, Error> {
+    match bits.parse() {
+        Ok(bits) if (8..=256).step_by(8).any(|size| size == bits) => Ok(bits),
+        _ => Err(Error::InvalidSignInput),
+    }
+}
+
+// Apply suffixes such as `[2][]` to `kind`, from left to right
+fn parse_array_suffix(mut kind: ParamType, suffix: &str) -> Result<ParamType, Error> {
+    let mut rest = suffix;
+    while !rest.is_empty() {
+        let end = rest.find(']').ok_or(Error::InvalidSignInput)?;
+        let len = rest[..end]
+            .strip_prefix('[')
+            .ok_or(Error::InvalidSignInput)?;
+        kind = match len.is_empty() {
+            true => ParamType::Array(Box::new(kind)),
+            false => {
+                let len = len.parse().map_err(|_| Error::InvalidSignInput)?;
+                ParamType::FixedArray(Box::new(kind), len)
+            }
+        };
+        rest = &rest[end + 1..];
+    }
+    Ok(kind)
+}
+
+// Split a parameter list at the commas outside of nested tuples
+fn split_params(params: &str) -> Result<Vec<&str>, Error> {
+    if params.trim().is_empty() {
+        return Ok(vec![]);
+    }
+    let mut depth = 0;
+    let mut start = 0;
+    let mut split = vec![];
+    for (index, char) in params.char_indices() {
+        match char {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                split.push(params[start..index].trim());
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    split.push(params[start..].trim());
+    match split.iter().any(|param| param.is_empty()) {
+        true => Err(Error::InvalidSignInput),
+        false => Ok(split),
+    }
+}
+
+fn matching_paren(value: &str, start: usize) -> Result<usize, Error> {
+    let mut depth = 0;
+    for (index, char) in value[start..].char_indices() {
+        match char {
+            '(' => depth += 1,
+            ')' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Ok(start + index);
+                }
+            }
+            _ => {}
+        }
+    }
+    Err(Error::InvalidSignInput)
+}
+
+fn is_identifier(name: &str) -> bool {
+    !name.is_empty()
+        && !name.starts_with(|char: char| char.is_ascii_digit())
+        && name
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || char == '_' || char == '$')
+}
+
+fn type_name(kind: &ParamType) -> String {
+    match kind {
+        ParamType::Address => "address".to_owned(),
+        ParamType::Bool => "bool".to_owned(),
+        ParamType::Uint(bits) => format!("uint{}", bits),
+        ParamType::Int(bits) => format!("int{}", bits),
+        ParamType::FixedBytes(size) => format!("bytes{}", size),
+        ParamType::Bytes => "bytes".to_owned(),
+        ParamType::String => "string".to_owned(),
+        ParamType::Array(kind) => format!("{}[]", type_name(kind)),
+        ParamType::FixedArray(kind, len) => format!("{}[{}]", type_name(kind), len),
+        ParamType::Tuple(components) => tuple_type_name(components),
+    }
+}
+
+fn tuple_type_name(components: &[Param]) -> String {
+    let types = components
+        .iter()
+        .map(|param| type_name(&param.kind))
+        .collect::<Vec<_>>();
+    format!("({})", types.join(","))
+}
+
+fn is_dynamic(kind: &ParamType) -> bool {
+    match kind {
+        ParamType::Bytes | ParamType::String | ParamType::Array(_) => true,
+        ParamType::FixedArray(kind, _) => is_dynamic(kind),
+        ParamType::Tuple(components) => components.iter().any(|param| is_dynamic(&param.kind)),
+        _ => false,
+    }
+}
+
+// Size in the head of the enclosing tuple, dynamic values only take their offset.
+// Fixed arrays come from the input, so their sizes may not fit in usize
+fn head_size(kind: &ParamType) -> Result<usize, Error> {
+    if is_dynamic(kind) {
+        return Ok(32);
+    }
+    match kind {
+        ParamType::FixedArray(kind, len) => head_size(kind)?
+            .checked_mul(*len)
+            .ok_or(Error::InvalidSignInput),
+        ParamType::Tuple(components) => heads_size(components.iter().map(|param| &param.kind)),
+        _ => Ok(32),
+    }
+}
+
+fn heads_size<'a>(mut kinds: impl Iterator<Item = &'a ParamType>) -> Result<usize, Error> {
+    kinds.try_fold(0usize, |size, kind| {
+        size.checked_add(head_size(kind)?)
+            .ok_or(Error::InvalidSignInput)
+    })
+}
+
+// The values of a tuple, from a JSON array in order or a JSON object keyed by the names
+fn tuple_values<'a>(components: &[Param], value: &'a Value) -> Result<Vec<&'a Value>, Error> {
+    match value {
+        Value::Array(values) if values.len() == components.len() => Ok(values.iter().collect()),
+        Value::Object(values) => components
+            .iter()
+            .map(|param| values.get(&param.name).ok_or(Error::InvalidSignInput))
+            .collect(),
+        _ => Err(Error::InvalidSignInput),
+    }
+}
+
+fn encode_sequence(kinds: &[&ParamType], values: &[&Value]) -> Result<Vec<u8>, Error> {
+    if kinds.len() != values.len() {
+        return Err(Error::InvalidSignInput);
+    }
+    let heads_size = heads_size(kinds.iter().copied())?;
+    let mut heads = vec![];
+    let mut tails = vec![];
+    for (kind, value) in kinds.iter().zip(values) {
+        let encoded = encode_value(kind, value)?;
+        match is_dynamic(kind) {
+            true => {
+                heads.extend_from_slice(&encode_uint(U256::from(heads_size + tails.len())));
+                tails.extend_from_slice(&encoded);
+            }
+            false => heads.extend_from_slice(&encoded),
+        }
+    }
+    heads.extend_from_slice(&tails);
+    Ok(heads)
+}
+
+fn encode_value(kind: &ParamType, value: &Value) -> Result<Vec<u8>, Error> {
+    match kind {
+        ParamType::Address => {
+            let data = decode_hex(value.as_str().ok_or(Error::InvalidSignInput)?)?;
+            if data.len() != 20 {
+                return Err(Error::InvalidSignInput);
+            }
+            Ok(encode_uint(U256::from_big_endian(&data)))
+        }
+        ParamType::Bool => match value {
+            Value::Bool(value) => Ok(encode_uint(U256::from(*value as u8))),
+            _ => Err(Error::InvalidSignInput),
+        },
+        ParamType::Uint(_) | ParamType::Int(_) => encode_atomic(&type_name(kind), value),
+        ParamType::FixedBytes(size) => {
+            let mut data = decode_hex(value.as_str().ok_or(Error::InvalidSignInput)?)?;
+            if data.len() != *size {
+                return Err(Error::InvalidSignInput);
+            }
+            data.resize(32, 0);
+            Ok(data)
+        }
+        ParamType::Bytes => {
+            let data = decode_hex(value.as_str().ok_or(Error::InvalidSignInput)?)?;
+            Ok(encode_bytes(&data))
+        }
+        ParamType::String => {
+            let data = value.as_str().ok_or(Error::InvalidSignInput)?;
+            Ok(encode_bytes(data.as_bytes()))
+        }
+        ParamType::Array(kind) => {
+            let values = value.as_array().ok_or(Error::InvalidSignInput)?;
+            let mut encoded = encode_uint(U256::from(values.len()));
+            let kinds = vec![kind.as_ref(); values.len()];
+            encoded.extend_from_slice(&encode_sequence(
+                &kinds,
+                &values.iter().collect::<Vec<_>>(),
+            )?);
+            Ok(encoded)
+        }
+        ParamType::FixedArray(kind, len) => {
+            let values = value.as_array().ok_or(Error::InvalidSignInput)?;
+            if values.len() != *len {
+                return Err(Error::InvalidSignInput);
+            }
+            let kinds = vec![kind.as_ref(); values.len()];
+            encode_sequence(&kinds, &values.iter().collect::<Vec<_>>())
+        }
+        ParamType::Tuple(components) => {
+            let kinds = components
+                .iter()
+                .map(|param| &param.kind)
+                .collect::<Vec<_>>();
+            encode_sequence(&kinds, &tuple_values(components, value)?)
+        }
+    }
+}
+
+// Length followed by the data right padded to 32 bytes
+fn encode_bytes(data: &[u8]) -> Vec<u8> {
+    let mut encoded = encode_uint(U256::from(data.len()));
+    encoded.extend_from_slice(data);
+    let padding = (32 - data.len() % 32) % 32;
+    encoded.resize(encoded.len() + padding, 0);
+    encoded
+}
+
+fn decode_sequence(kinds: &[&ParamType], data: &[u8]) -> Result<Vec<Value>, Error> {
+    let mut offset = 0;
+    let mut values = vec![];
+    for kind in kinds {
+        let value = match is_dynamic(kind) {
+            true => {
+                let start = read_usize(data, offset)?;
+                decode_value(kind, data.get(start..).ok_or(Error::InvalidSignInput)?)?
+            }
+            false => decode_value(kind, data.get(offset..).ok_or(Error::InvalidSignInput)?)?,
+        };
+        offset = offset
+            .checked_add(head_size(kind)?)
+            .ok_or(Error::InvalidSignInput)?;
+        values.push(value);
+    }
+    Ok(values)
+}
+
+fn decode_value(kind: &ParamType, data: &[u8]) -> Result<Value, Error> {
+    match kind {
+        ParamType::Address => {
+            let word = read_word(data, 0)?;
+            if word[..12].iter().any(|byte| *byte != 0) {
+                return Err(Error::InvalidSignInput);
+            }
+            let address = EthereumAddress {
+                coin_id: "ethereum".to_owned(),
+                data: word[12..].to_vec(),
+            };
+            Ok(Value::String(checksum(&address, ChecksumType::Eip55)))
+        }
+        ParamType::Bool => match U256::from_big_endian(read_word(data, 0)?) {
+            value if value <= U256::one() => Ok(Value::Bool(!value.is_zero())),
+            _ => Err(Error::InvalidSignInput),
+        },
+        ParamType::Uint(bits) => {
+            let value = U256::from_big_endian(read_word(data, 0)?);
+            match value.bits() <= *bits {
+                true => Ok(Value::String(value.to_string())),
+                false => Err(Error::InvalidSignInput),
+            }
+        }
+        ParamType::Int(bits) => {
+            let value = U256::from_big_endian(read_word(data, 0)?);
+            let negative = value.bit(bits - 1);
+            // The bits above the sign bit must all repeat it
+            let high = value >> (bits - 1);
+            let expected = match negative {
+                true => U256::MAX >> (bits - 1),
+                false => U256::zero(),
+            };
+            if high != expected {
+                return Err(Error::InvalidSignInput);
+            }
+            match negative {
+                true => {
+                    let magnitude = (!value).overflowing_add(U256::one()).0;
+                    Ok(Value::String(format!("-{}", magnitude)))
+                }
+                false => Ok(Value::String(value.to_string())),
+            }
+        }
+        ParamType::FixedBytes(size) => {
+            let word = read_word(data, 0)?;
+            if word[*size..].iter().any(|byte| *byte != 0) {
+                return Err(Error::InvalidSignInput);
+            }
+            Ok(Value::String(format!("0x{}", hex::encode(&word[..*size]))))
+        }
+        ParamType::Bytes => Ok(Value::String(format!(
+            "0x{}",
+            hex::encode(decode_bytes(data)?)
+        ))),
+        ParamType::String => {
+            let value = String::from_utf8(decode_bytes(data)?.to_vec())
+                .map_err(|_| Error::InvalidSignInput)?;
+            Ok(Value::String(value))
+        }
+        ParamType::Array(kind) => {
+            let len = read_usize(data, 0)?;
+            let items = &data[32..];
+            // Every item takes at least a word, which bounds the length before allocating
+            if len > items.len() / 32 {
+                return Err(Error::InvalidSignInput);
+            }
+            Ok(Value::Array(decode_sequence(
+                &vec![kind.as_ref(); len],
+                items,
+            )?))
+        }
+        ParamType::FixedArray(kind, len) => {
+            if *len > data.len() / 32 {
+                return Err(Error::InvalidSignInput);
+            }
+            Ok(Value::Array(decode_sequence(
+                &vec![kind.as_ref(); *len],
+                data,
+            )?))
+        }
+        ParamType::Tuple(components) => {
+            let kinds = components
+                .iter()
+                .map(|param| &param.kind)
+                .collect::<Vec<_>>();
+            let values = decode_sequence(&kinds, data)?;
+            // Unnamed components keep their positions
+            if components.iter().any(|param| param.name.is_empty()) {
+                return Ok(Value::Array(values));
+            }
+            let mut object = Map::new();
+            for (param, value) in components.iter().zip(values) {
+                object.insert(param.name.to_owned(), value);
+            }
+            Ok(Value::Object(object))
+        }
+    }
+}
+
+fn decode_bytes(data: &[u8]) -> Result<&[u8], Error> {
+    let len = read_usize(data, 0)?;
+    data.get(32..32 + len).ok_or(Error::InvalidSignInput)
+}
+
+fn read_word(data: &[u8], offset: usize) -> Result<&[u8], Error> {
+    data.get(offset..offset + 32).ok_or(Error::InvalidSignInput)
+}
+
+// Offsets and lengths, bounded by u32 so they never overflow when added
+fn read_usize(data: &[u8], offset: usize) -> Result<usize, Error> {
+    let value = U256::from_big_endian(read_word(data, offset)?);
+    match value <= U256::from(u32::MAX) {
+        true => Ok(value.as_usize()),
+        false => Err(Error::InvalidSignInput),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_transfer() {
+        let function = find_function("transfer(address to, uint256 amount)", "").unwrap();
+        assert_eq!(function.signature(), "transfer(address,uint256)");
+        assert_eq!(hex::encode(function.selector().unwrap()), "a9059cbb");
+
+        let args = json!(["0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed", "1000"]);
+        let encoded = function.encode_input(&args).unwrap();
+        assert_eq!(
+            hex::encode(&encoded),
+            "a9059cbb\
+             0000000000000000000000005aaeb6053f3e94c9b9a09f33669435e7ef1beaed\
+             00000000000000000000000000000000000000000000000000000000000003e8"
+        );
+        let named = json!({"amount": "0x3e8", "to": "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"});
+        assert_eq!(function.encode_input(&named).unwrap(), encoded);
+
+        assert_eq!(
+            function.decode_input(&encoded).unwrap(),
+            json!([
+                {"name": "to", "type": "address", "value": "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed"},
+                {"name": "amount", "type": "uint256", "value": "1000"},
+            ])
+        );
+        // Wrong selector and truncated arguments
+        assert!(function.decode_input(&encoded[1..]).is_err());
+        assert!(function
+            .decode_input(&encoded[..encoded.len() - 1])
+            .is_err());
+        // Out of range for uint8
+        let function = find_function("f(uint8)", "").unwrap();
+        assert!(function.encode_input(&json!([256])).is_err());
+    }
+
+    #[test]
+    fn test_encode_dynamic() {
+        // Examples from the Solidity ABI specification
+        let function = find_function("f(uint256,uint32[],bytes10,bytes)", "").unwrap();
+        let args = json!([
+            "0x123",
+            ["0x456", "0x789"],
+            "0x31323334353637383930",
+            "0x48656c6c6f2c20776f726c6421"
+        ]);
+        let encoded = function.encode_input(&args).unwrap();
+        assert_eq!(
+            hex::encode(&encoded),
+            "8be65246\
+             0000000000000000000000000000000000000000000000000000000000000123\
+             0000000000000000000000000000000000000000000000000000000000000080\
+             3132333435363738393000000000000000000000000000000000000000000000\
+             00000000000000000000000000000000000000000000000000000000000000e0\
+             0000000000000000000000000000000000000000000000000000000000000002\
+             0000000000000000000000000000000000000000000000000000000000000456\
+             0000000000000000000000000000000000000000000000000000000000000789\
+             000000000000000000000000000000000000000000000000000000000000000d\
+             48656c6c6f2c20776f726c642100000000000000000000000000000000000000"
+        );
+
+        let function = find_function("g(uint256[][],string[])", "").unwrap();
+        let args = json!([[[1, 2], [3]], ["one", "two", "three"]]);
+        let encoded = function.encode_input(&args).unwrap();
+        assert_eq!(
+            hex::encode(&encoded),
+            "2289b18c\
+             0000000000000000000000000000000000000000000000000000000000000040\
+             0000000000000000000000000000000000000000000000000000000000000140\
+             0000000000000000000000000000000000000000000000000000000000000002\
+             0000000000000000000000000000000000000000000000000000000000000040\
+             00000000000000000000000000000000000000000000000000000000000000a0\
+             0000000000000000000000000000000000000000000000000000000000000002\
+             0000000000000000000000000000000000000000000000000000000000000001\
+             0000000000000000000000000000000000000000000000000000000000000002\
+             0000000000000000000000000000000000000000000000000000000000000001\
+             0000000000000000000000000000000000000000000000000000000000000003\
+             0000000000000000000000000000000000000000000000000000000000000003\
+             0000000000000000000000000000000000000000000000000000000000000060\
+             00000000000000000000000000000000000000000000000000000000000000a0\
+             00000000000000000000000000000000000000000000000000000000000000e0\
+             0000000000000000000000000000000000000000000000000000000000000003\
+             6f6e650000000000000000000000000000000000000000000000000000000000\
+             0000000000000000000000000000000000000000000000000000000000000003\
+             74776f0000000000000000000000000000000000000000000000000000000000\
+             0000000000000000000000000000000000000000000000000000000000000005\
+             7468726565000000000000000000000000000000000000000000000000000000"
+        );
+        let decoded = function.decode_input(&encoded).unwrap();
+        assert_eq!(decoded[0]["value"], json!([["1", "2"], ["3"]]));
+        assert_eq!(decoded[1]["value"], json!(["one", "two", "three"]));
+    }
+
+    #[test]
+    fn test_json_abi_tuples() {
+        let abi = r#"[
+            {"type": "event", "name": "Transfer", "inputs": []},
+            {
+                "type": "function",
+                "name": "execute",
+                "inputs": [
+                    {
+                        "name": "calls",
+                        "type": "tuple[]",
+                        "components": [
+                            {"name": "target", "type": "address"},
+                            {"name": "value", "type": "uint256"},
+                            {"name": "data", "type": "bytes"}
+                        ]
+                    },
+                    {"name": "deadline", "type": "int64"}
+                ],
+                "outputs": []
+            }
+        ]"#;
+        let function = find_function(abi, "execute").unwrap();
+        assert_eq!(
+            function.signature(),
+            "execute((address,uint256,bytes)[],int64)"
+        );
+        assert_eq!(
+            function,
+            find_function("function execute((address target, uint256 value, bytes data)[] calldata calls, int64 deadline) external", "").unwrap()
+        );
+
+        let calls = json!([
+            {"target": "0xde0B295669a9FD93d5F28D9Ec85E40f4cb697BAe", "value": "1", "data": "0xa9059cbb"},
+            {"target": "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed", "value": "0", "data": "0x"},
+        ]);
+        let encoded = function.encode_input(&json!([calls, "-1"])).unwrap();
+        let (decoded_function, args) = decode_call(abi, &encoded).unwrap();
+        assert_eq!(decoded_function.name, "execute");
+        assert_eq!(args[0]["name"], "calls");
+        assert_eq!(args[0]["type"], "(address,uint256,bytes)[]");
+        assert_eq!(args[0]["value"], calls);
+        assert_eq!(args[1]["value"], "-1");
+
+        assert!(find_function(abi, "transfer").is_err());
+        assert!(decode_call(abi, &[0xa9, 0x05, 0x9c, 0xbb]).is_err());
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        let function = find_function("f(bool,bytes)", "").unwrap();
+        let mut encoded = function.encode_input(&json!([true, "0x01"])).unwrap();
+        assert!(function.decode_input(&encoded).is_ok());
+        // Bool out of range
+        encoded[35] = 2;
+        assert!(function.decode_input(&encoded).is_err());
+        encoded[35] = 1;
+        // Offset past the end
+        encoded[67] = 0xff;
+        assert!(function.decode_input(&encoded).is_err());
+
+        assert!(parse_functions("f(uint7)").is_err());
+        assert!(parse_functions("f(bytes33)").is_err());
+        assert!(parse_functions("f(uint256,,bool)").is_err());
+        assert!(parse_functions("1f(uint256)").is_err());
+
+        // Head sizes overflowing usize
+        let function = find_function("f(uint256[576460752303423488])", "").unwrap();
+        assert!(function.encode_input(&json!([[]])).is_err());
+        assert!(function.decode_input(&[0u8; 64]).is_err());
+        let function = find_function(
+            "f((uint256[288230376151711744],uint256[288230376151711744]))",
+            "",
+        )
+        .unwrap();
+        assert!(function.encode_input(&json!([[[], []]])).is_err());
+    }
+}
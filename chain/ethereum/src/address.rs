@@ -12,22 +12,55 @@ pub struct EthereumAddress {
     pub data: Vec<u8>,
 }
 
+/// How the letters of a hex address are cased
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChecksumStatus {
+    Lowercase,
+    Uppercase,
+    Checksummed,
+    BadChecksum,
+}
+
 impl EthereumAddress {
+    /// Whether `address` is a hex address, a mixed case one must carry a valid EIP-55 checksum
     pub fn is_valid(address: &str) -> bool {
-        if address.is_empty() {
-            return false;
+        matches!(
+            Self::checksum_status(address, ChecksumType::Eip55),
+            Some(status) if status != ChecksumStatus::BadChecksum
+        )
+    }
+
+    /// Same as `is_valid`, also accepting the EIP-1191 checksum of `chain_id`
+    pub fn is_valid_on_chain(address: &str, chain_id: u64) -> bool {
+        Self::is_valid(address)
+            || Self::checksum_status(address, ChecksumType::Eip1191(chain_id))
+                == Some(ChecksumStatus::Checksummed)
+    }
+
+    /// Checksum status of `address` under `checksum_type`, `None` if it isn't a hex address
+    pub fn checksum_status(address: &str, checksum_type: ChecksumType) -> Option<ChecksumStatus> {
+        if address.len() != 42 {
+            return None;
         }
-        if !address.starts_with("0x") {
-            return false;
+        let hex_address = address.strip_prefix("0x")?;
+        let data = hex::decode(hex_address).ok()?;
+        if data.len() != ADDRESS_SIZE {
+            return None;
         }
-        if address.len() != 42 {
-            return false;
+        if hex_address == hex_address.to_lowercase() {
+            return Some(ChecksumStatus::Lowercase);
+        }
+        if hex_address == hex_address.to_uppercase() {
+            return Some(ChecksumStatus::Uppercase);
         }
-        let data = match hex::decode(&address[2..]) {
-            Ok(data) => data,
-            Err(_) => return false,
+        let address_data = EthereumAddress {
+            coin_id: "".to_owned(),
+            data,
         };
-        data.len() == ADDRESS_SIZE
+        match checksum(&address_data, checksum_type) == address {
+            true => Some(ChecksumStatus::Checksummed),
+            false => Some(ChecksumStatus::BadChecksum),
+        }
     }
 
     pub fn new(public_key: &PublicKey, coin_id: &str) -> Result<Self, Error> {
@@ -56,7 +89,8 @@ impl ToString for EthereumAddress {
 
 #[cfg(test)]
 mod tests {
-    use crate::address::EthereumAddress;
+    use crate::address::{ChecksumStatus, EthereumAddress};
+    use crate::address_checksum::ChecksumType;
     use chain_common::public_key::PublicKey;
     use crypto::public_key::PublicKeyType;
 
@@ -72,6 +106,44 @@ mod tests {
         assert!(!EthereumAddress::is_valid(test2));
         assert!(!EthereumAddress::is_valid(test3));
         assert!(EthereumAddress::is_valid(test4));
+
+        // Mixed case must carry a valid checksum
+        assert!(EthereumAddress::is_valid(
+            "0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed"
+        ));
+        assert!(EthereumAddress::is_valid(
+            "0x5AAEB6053F3E94C9B9A09F33669435E7EF1BEAED"
+        ));
+        assert!(!EthereumAddress::is_valid(
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAeD"
+        ));
+    }
+
+    #[test]
+    fn test_checksum_status() {
+        let rsk = "0x5aaEB6053f3e94c9b9a09f33669435E7ef1bEAeD";
+        assert_eq!(
+            EthereumAddress::checksum_status(rsk, ChecksumType::Eip1191(30)),
+            Some(ChecksumStatus::Checksummed)
+        );
+        assert_eq!(
+            EthereumAddress::checksum_status(rsk, ChecksumType::Eip1191(31)),
+            Some(ChecksumStatus::BadChecksum)
+        );
+        assert_eq!(
+            EthereumAddress::checksum_status(rsk, ChecksumType::Eip55),
+            Some(ChecksumStatus::BadChecksum)
+        );
+        assert_eq!(
+            EthereumAddress::checksum_status(&rsk.to_lowercase(), ChecksumType::Eip1191(30)),
+            Some(ChecksumStatus::Lowercase)
+        );
+        assert_eq!(
+            EthereumAddress::checksum_status(&rsk[1..], ChecksumType::Eip55),
+            None
+        );
+        assert!(EthereumAddress::is_valid_on_chain(rsk, 30));
+        assert!(!EthereumAddress::is_valid_on_chain(rsk, 1));
     }
 
     #[test]
@@ -2,15 +2,21 @@ use super::address::EthereumAddress;
 
 use crypto::hash::{Hasher, Keccak256};
 
+#[derive(Clone, Copy)]
 pub enum ChecksumType {
     Eip55,
     WanChain,
+    /// Chain id aware checksum of networks like RSK, hashing `chain_id || "0x" || address`
+    Eip1191(u64),
 }
 
 pub fn checksum(address: &EthereumAddress, r#type: ChecksumType) -> String {
     let address_string = hex::encode(&address.data);
-    let hash =
-        Hasher::hash(Keccak256, address_string.as_bytes()).expect("Fail to do keccak256 hash");
+    let hashed = match r#type {
+        ChecksumType::Eip1191(chain_id) => format!("{}0x{}", chain_id, address_string),
+        _ => address_string.to_owned(),
+    };
+    let hash = Hasher::hash(Keccak256, hashed.as_bytes()).expect("Fail to do keccak256 hash");
     let hash_hex = hex::encode(hash);
 
     let mut prefix = "0x".to_owned();
@@ -23,12 +29,16 @@ pub fn checksum(address: &EthereumAddress, r#type: ChecksumType) -> String {
             prefix.push(a);
         } else if ('8'..='9').contains(&h) || ('a'..='f').contains(&h) {
             match r#type {
-                ChecksumType::Eip55 => prefix.push(a.to_uppercase().next().unwrap()),
+                ChecksumType::Eip55 | ChecksumType::Eip1191(_) => {
+                    prefix.push(a.to_uppercase().next().unwrap())
+                }
                 ChecksumType::WanChain => prefix.push(a.to_lowercase().next().unwrap()),
             };
         } else {
             match r#type {
-                ChecksumType::Eip55 => prefix.push(a.to_lowercase().next().unwrap()),
+                ChecksumType::Eip55 | ChecksumType::Eip1191(_) => {
+                    prefix.push(a.to_lowercase().next().unwrap())
+                }
                 ChecksumType::WanChain => prefix.push(a.to_uppercase().next().unwrap()),
             };
         }
@@ -0,0 +1,110 @@
+use super::address::EthereumAddress;
+use super::address_checksum::{checksum, ChecksumType};
+use chain_common::Error;
+use crypto::hash::{Hasher, Keccak256};
+use ethereum_types::{H160, U256};
+use rlp::RlpStream;
+use std::str::FromStr;
+
+/// Address of the contract created by `sender` with `nonce`, keccak256(rlp([sender, nonce]))[12..]
+pub fn create_address(sender: &str, nonce: U256) -> Result<String, Error> {
+    let mut stream = RlpStream::new_list(2);
+    stream.append(&parse_address(sender)?);
+    stream.append(&nonce);
+    address_from_hash(&stream.out())
+}
+
+/// Address of the contract created by `deployer` with CREATE2,
+/// keccak256(0xff ‖ deployer ‖ salt ‖ init_code_hash)[12..]
+pub fn create2_address(
+    deployer: &str,
+    salt: &[u8],
+    init_code_hash: &[u8],
+) -> Result<String, Error> {
+    if salt.len() != 32 || init_code_hash.len() != 32 {
+        return Err(Error::InvalidSignInput);
+    }
+    let mut data = vec![0xff];
+    data.extend_from_slice(parse_address(deployer)?.as_bytes());
+    data.extend_from_slice(salt);
+    data.extend_from_slice(init_code_hash);
+    address_from_hash(&data)
+}
+
+fn parse_address(address: &str) -> Result<H160, Error> {
+    if !EthereumAddress::is_valid(address) {
+        return Err(Error::InvalidSignInput);
+    }
+    H160::from_str(&address[2..]).map_err(|_| Error::InvalidSignInput)
+}
+
+// EIP-55 address of the last 20 bytes of keccak256(data)
+fn address_from_hash(data: &[u8]) -> Result<String, Error> {
+    let hash = Hasher::hash(Keccak256, data).map_err(|_| Error::InvalidSignInput)?;
+    let address = EthereumAddress {
+        coin_id: "ethereum".to_owned(),
+        data: hash[12..].to_vec(),
+    };
+    Ok(checksum(&address, ChecksumType::Eip55))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_create_address() {
+        let sender = "0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0";
+        assert_eq!(
+            create_address(sender, U256::zero()).unwrap(),
+            "0xcd234A471b72ba2F1Ccf0A70FCABA648a5eeCD8d"
+        );
+        assert_eq!(
+            create_address(sender, U256::one()).unwrap().to_lowercase(),
+            "0x343c43a37d37dff08ae8c4a11544c718abb4fcf8"
+        );
+        assert!(create_address("0x6ac7ea33", U256::zero()).is_err());
+    }
+
+    #[test]
+    fn test_create2_address() {
+        // EIP-1014 examples
+        let salt = [0u8; 32];
+        let init_code_hash = Hasher::hash(Keccak256, &[0x00]).unwrap();
+        assert_eq!(
+            create2_address(
+                "0x0000000000000000000000000000000000000000",
+                &salt,
+                &init_code_hash
+            )
+            .unwrap(),
+            "0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38"
+        );
+        assert_eq!(
+            create2_address(
+                "0xdeadbeef00000000000000000000000000000000",
+                &salt,
+                &init_code_hash
+            )
+            .unwrap(),
+            "0xB928f69Bb1D91Cd65274e3c79d8986362984fDA3"
+        );
+        let salt = hex::decode("000000000000000000000000feed000000000000000000000000000000000000")
+            .unwrap();
+        assert_eq!(
+            create2_address(
+                "0xdeadbeef00000000000000000000000000000000",
+                &salt,
+                &init_code_hash
+            )
+            .unwrap(),
+            "0xD04116cDd17beBE565EB2422F2497E06cC1C9833"
+        );
+        assert!(create2_address(
+            "0x0000000000000000000000000000000000000000",
+            &[0u8; 31],
+            &init_code_hash
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,553 @@
+use super::address::EthereumAddress;
+use super::address_checksum::{checksum, ChecksumType};
+use super::eip4844::BlobSidecar;
+use super::recovery::recover_from_hash;
+use super::transaction::{
+    Eip1559Transaction, Eip2930Transaction, Eip4844Transaction, Eip7702Transaction,
+    LegacyTransaction, Transaction, TransactionBase,
+};
+use chain_common::ethereum::BlobSidecar as BlobSidecarInput;
+use chain_common::ethereum::{DecodedTransaction, SignInput, TransactionType};
+use chain_common::Error;
+use crypto::hash::{Hasher, Keccak256};
+use ethereum_types::{H160, U256};
+use rlp::{Decodable, Rlp};
+
+/// Decode a raw legacy, EIP-2930, EIP-1559, EIP-4844 or EIP-7702 transaction, either signed or
+/// unsigned. EIP-4844 transactions may be in the network wrapper form with their blobs.
+///
+/// An unsigned legacy transaction is either the 6 fields before EIP-155, or the 9 fields of the
+/// EIP-155 signing payload ending with `chain_id, 0, 0`
+pub fn decode_transaction(raw: &[u8]) -> Result<DecodedTransaction, Error> {
+    match raw.first() {
+        Some(0x01) => decode_eip2930(&raw[1..], raw),
+        Some(0x02) => decode_eip1559(&raw[1..], raw),
+        Some(0x03) => decode_eip4844(&raw[1..]),
+        Some(0x04) => decode_eip7702(&raw[1..], raw),
+        Some(0xc0..=0xff) => decode_legacy(raw),
+        _ => Err(Error::InvalidSignInput),
+    }
+}
+
+fn decode_legacy(raw: &[u8]) -> Result<DecodedTransaction, Error> {
+    let rlp = list(raw)?;
+    let transaction = LegacyTransaction {
+        base: base(&rlp, 0, 2)?,
+        gas_price: value(&rlp, 1)?,
+    };
+    let (chain_id, signature) = match rlp.item_count().map_err(|_| Error::InvalidSignInput)? {
+        6 => (0, None),
+        9 => {
+            let v: u64 = value(&rlp, 6)?;
+            match decode_signature(&rlp, 6)? {
+                // EIP-155 signing payload
+                None => (v, None),
+                Some(signature) => {
+                    let chain_id = match v {
+                        27 | 28 => 0,
+                        v if v >= 35 => (v - 35) / 2,
+                        _ => return Err(Error::InvalidSignInput),
+                    };
+                    (chain_id, Some(signature))
+                }
+            }
+        }
+        _ => return Err(Error::InvalidSignInput),
+    };
+    let mut sign_input = sign_input(&transaction.base, TransactionType::Legacy, chain_id);
+    sign_input.gas_price = format!("{:#x}", transaction.gas_price);
+    let sign_hash = match chain_id {
+        // Signed before EIP-155, without replay protection
+        0 => transaction.pre_eip155_hash(),
+        _ => transaction.hash(chain_id),
+    }
+    .map_err(|_| Error::InvalidSignInput)?;
+    decoded_with_sign_hash(sign_hash, sign_input, signature, raw)
+}
+
+fn decode_eip2930(payload: &[u8], raw: &[u8]) -> Result<DecodedTransaction, Error> {
+    let rlp = list(payload)?;
+    let transaction = Eip2930Transaction {
+        base: base(&rlp, 1, 3)?,
+        gas_price: value(&rlp, 2)?,
+        access_list: value(&rlp, 7)?,
+    };
+    let signature = match rlp.item_count().map_err(|_| Error::InvalidSignInput)? {
+        8 => None,
+        11 => Some(decode_signature(&rlp, 8)?.ok_or(Error::InvalidSignInput)?),
+        _ => return Err(Error::InvalidSignInput),
+    };
+    let mut sign_input = sign_input(
+        &transaction.base,
+        TransactionType::AccessList,
+        value(&rlp, 0)?,
+    );
+    sign_input.gas_price = format!("{:#x}", transaction.gas_price);
+    sign_input.access_list = (&transaction.access_list).into();
+    decoded(&transaction, sign_input, signature, raw)
+}
+
+fn decode_eip1559(payload: &[u8], raw: &[u8]) -> Result<DecodedTransaction, Error> {
+    let rlp = list(payload)?;
+    let transaction = Eip1559Transaction {
+        base: base(&rlp, 1, 4)?,
+        max_inclusion_fee_per_gas: value(&rlp, 2)?,
+        max_fee_per_gas: value(&rlp, 3)?,
+        access_list: value(&rlp, 8)?,
+    };
+    let signature = match rlp.item_count().map_err(|_| Error::InvalidSignInput)? {
+        9 => None,
+        12 => Some(decode_signature(&rlp, 9)?.ok_or(Error::InvalidSignInput)?),
+        _ => return Err(Error::InvalidSignInput),
+    };
+    let mut sign_input = sign_input(&transaction.base, TransactionType::Eip1559, value(&rlp, 0)?);
+    sign_input.max_inclusion_fee_per_gas = format!("{:#x}", transaction.max_inclusion_fee_per_gas);
+    sign_input.max_fee_per_gas = format!("{:#x}", transaction.max_fee_per_gas);
+    sign_input.access_list = (&transaction.access_list).into();
+    decoded(&transaction, sign_input, signature, raw)
+}
+
+fn decode_eip4844(payload: &[u8]) -> Result<DecodedTransaction, Error> {
+    // The network wrapper form is `rlp([tx_payload_body, blobs, commitments, proofs])`
+    let outer = list(payload)?;
+    let (body, sidecar) = match outer.at(0).map_err(|_| Error::InvalidSignInput)?.is_list() {
+        true => {
+            if outer.item_count().map_err(|_| Error::InvalidSignInput)? != 4 {
+                return Err(Error::InvalidSignInput);
+            }
+            let sidecar = BlobSidecarInput {
+                blobs: outer.list_at(1).map_err(|_| Error::InvalidSignInput)?,
+                commitments: outer.list_at(2).map_err(|_| Error::InvalidSignInput)?,
+                proofs: outer.list_at(3).map_err(|_| Error::InvalidSignInput)?,
+            };
+            let body = outer.at(0).map_err(|_| Error::InvalidSignInput)?.as_raw();
+            (body, Some(BlobSidecar::try_from(&sidecar)?))
+        }
+        false => (payload, None),
+    };
+    let rlp = list(body)?;
+    let transaction = Eip4844Transaction {
+        base: base(&rlp, 1, 4)?,
+        max_inclusion_fee_per_gas: value(&rlp, 2)?,
+        max_fee_per_gas: value(&rlp, 3)?,
+        access_list: value(&rlp, 8)?,
+        max_fee_per_blob_gas: value(&rlp, 9)?,
+        blob_versioned_hashes: rlp.list_at(10).map_err(|_| Error::InvalidSignInput)?,
+        sidecar,
+    };
+    if let Some(ref sidecar) = transaction.sidecar {
+        if sidecar.versioned_hashes()? != transaction.blob_versioned_hashes {
+            return Err(Error::InvalidSignInput);
+        }
+    }
+    let signature = match rlp.item_count().map_err(|_| Error::InvalidSignInput)? {
+        11 => None,
+        14 => Some(decode_signature(&rlp, 11)?.ok_or(Error::InvalidSignInput)?),
+        _ => return Err(Error::InvalidSignInput),
+    };
+    let mut sign_input = sign_input(&transaction.base, TransactionType::Blob, value(&rlp, 0)?);
+    sign_input.max_inclusion_fee_per_gas = format!("{:#x}", transaction.max_inclusion_fee_per_gas);
+    sign_input.max_fee_per_gas = format!("{:#x}", transaction.max_fee_per_gas);
+    sign_input.access_list = (&transaction.access_list).into();
+    sign_input.max_fee_per_blob_gas = format!("{:#x}", transaction.max_fee_per_blob_gas);
+    sign_input.blob_versioned_hashes = transaction
+        .blob_versioned_hashes
+        .iter()
+        .map(|hash| hash.as_bytes().to_vec())
+        .collect();
+    sign_input.blob_sidecar = transaction.sidecar.as_ref().map(BlobSidecarInput::from);
+    // The transaction hash doesn't cover the sidecar
+    let raw = [&[0x03], body].concat();
+    decoded(&transaction, sign_input, signature, &raw)
+}
+
+fn decode_eip7702(payload: &[u8], raw: &[u8]) -> Result<DecodedTransaction, Error> {
+    let rlp = list(payload)?;
+    let transaction = Eip7702Transaction {
+        base: base(&rlp, 1, 4)?,
+        max_inclusion_fee_per_gas: value(&rlp, 2)?,
+        max_fee_per_gas: value(&rlp, 3)?,
+        access_list: value(&rlp, 8)?,
+        authorization_list: value(&rlp, 9)?,
+    };
+    let signature = match rlp.item_count().map_err(|_| Error::InvalidSignInput)? {
+        10 => None,
+        13 => Some(decode_signature(&rlp, 10)?.ok_or(Error::InvalidSignInput)?),
+        _ => return Err(Error::InvalidSignInput),
+    };
+    let mut sign_input = sign_input(&transaction.base, TransactionType::SetCode, value(&rlp, 0)?);
+    sign_input.max_inclusion_fee_per_gas = format!("{:#x}", transaction.max_inclusion_fee_per_gas);
+    sign_input.max_fee_per_gas = format!("{:#x}", transaction.max_fee_per_gas);
+    sign_input.access_list = (&transaction.access_list).into();
+    sign_input.authorization_list = (&transaction.authorization_list).into();
+    decoded(&transaction, sign_input, signature, raw)
+}
+
+struct Signature {
+    v: u64,
+    r: Vec<u8>,
+    s: Vec<u8>,
+}
+
+fn decoded(
+    transaction: &dyn Transaction,
+    sign_input: SignInput,
+    signature: Option<Signature>,
+    raw: &[u8],
+) -> Result<DecodedTransaction, Error> {
+    let sign_hash = transaction
+        .hash(sign_input.chain_id)
+        .map_err(|_| Error::InvalidSignInput)?;
+    decoded_with_sign_hash(sign_hash, sign_input, signature, raw)
+}
+
+fn decoded_with_sign_hash(
+    sign_hash: Vec<u8>,
+    sign_input: SignInput,
+    signature: Option<Signature>,
+    raw: &[u8],
+) -> Result<DecodedTransaction, Error> {
+    let mut decoded = DecodedTransaction {
+        sign_input: None,
+        signed: false,
+        v: 0,
+        r: vec![],
+        s: vec![],
+        hash: vec![],
+        sign_hash: sign_hash.to_vec(),
+        from: "".to_owned(),
+    };
+    if let Some(signature) = signature {
+        let sender = recover_from_hash(&sign_hash, &signature.r, &signature.s, signature.v)?;
+        decoded.signed = true;
+        decoded.hash = Hasher::hash(Keccak256, raw).map_err(|_| Error::InvalidSignInput)?;
+        decoded.from = sender.address;
+        decoded.v = signature.v;
+        decoded.r = signature.r;
+        decoded.s = signature.s;
+    }
+    decoded.sign_input = Some(sign_input);
+    Ok(decoded)
+}
+
+// The transaction must be a single RLP list without trailing bytes
+fn list(data: &[u8]) -> Result<Rlp<'_>, Error> {
+    let rlp = Rlp::new(data);
+    let payload_info = rlp.payload_info().map_err(|_| Error::InvalidSignInput)?;
+    if !rlp.is_list() || payload_info.total() != data.len() {
+        return Err(Error::InvalidSignInput);
+    }
+    Ok(rlp)
+}
+
+fn value<T: Decodable>(rlp: &Rlp, index: usize) -> Result<T, Error> {
+    rlp.val_at(index).map_err(|_| Error::InvalidSignInput)
+}
+
+// Nonce at `nonce_index`, then gas limit at `gas_limit_index`, followed by to, value and data
+fn base(rlp: &Rlp, nonce_index: usize, gas_limit_index: usize) -> Result<TransactionBase, Error> {
+    let receiver = rlp
+        .at(gas_limit_index + 1)
+        .and_then(|item| item.data())
+        .map_err(|_| Error::InvalidSignInput)?;
+    let receiver = match receiver.len() {
+        0 => None,
+        20 => Some(H160::from_slice(receiver)),
+        _ => return Err(Error::InvalidSignInput),
+    };
+    Ok(TransactionBase {
+        nonce: value(rlp, nonce_index)?,
+        gas_limit: value(rlp, gas_limit_index)?,
+        receiver,
+        amount: value(rlp, gas_limit_index + 2)?,
+        payload: value(rlp, gas_limit_index + 3)?,
+    })
+}
+
+// The `v, r, s` starting at `index`, none if both r and s are empty
+fn decode_signature(rlp: &Rlp, index: usize) -> Result<Option<Signature>, Error> {
+    let r: U256 = value(rlp, index + 1)?;
+    let s: U256 = value(rlp, index + 2)?;
+    if r.is_zero() && s.is_zero() {
+        return Ok(None);
+    }
+    let bytes = |value: U256| {
+        let mut data = [0u8; 32];
+        value.to_big_endian(&mut data);
+        data[(value.leading_zeros() / 8) as usize..].to_vec()
+    };
+    Ok(Some(Signature {
+        v: value(rlp, index)?,
+        r: bytes(r),
+        s: bytes(s),
+    }))
+}
+
+fn sign_input(base: &TransactionBase, r#type: TransactionType, chain_id: u64) -> SignInput {
+    let to_address = match base.receiver {
+        Some(receiver) => checksum(
+            &EthereumAddress {
+                coin_id: "ethereum".to_owned(),
+                data: receiver.as_bytes().to_vec(),
+            },
+            ChecksumType::Eip55,
+        ),
+        None => "".to_owned(),
+    };
+    SignInput {
+        chain_id,
+        nonce: format!("{:#x}", base.nonce),
+        gas_price: "0x0".to_owned(),
+        gas_limit: format!("{:#x}", base.gas_limit),
+        max_inclusion_fee_per_gas: "0x0".to_owned(),
+        max_fee_per_gas: "0x0".to_owned(),
+        amount: format!("{:#x}", base.amount),
+        to_address,
+        payload: base.payload.to_vec(),
+        access_list: vec![],
+        transaction_type: r#type as i32,
+        authorization_list: vec![],
+        max_fee_per_blob_gas: "0x0".to_owned(),
+        blob_versioned_hashes: vec![],
+        blob_sidecar: None,
+        gas_per_pubdata: "".to_owned(),
+        factory_deps: vec![],
+        paymaster: "".to_owned(),
+        paymaster_input: vec![],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::eip4844::{versioned_hash, BLOB_SIZE};
+    use crate::signer::Signer;
+    use chain_common::ethereum::{AccessListItem, Authorization};
+    use chain_common::private_key::PrivateKey;
+    use std::str::FromStr;
+
+    #[test]
+    fn test_decode_signed_legacy() {
+        // EIP-155 example, signed with 0x4646..46
+        let raw = hex::decode("f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83").unwrap();
+        let decoded = decode_transaction(&raw).unwrap();
+        let sign_input = decoded.sign_input.unwrap();
+        assert_eq!(sign_input.transaction_type, TransactionType::Legacy as i32);
+        assert_eq!(sign_input.chain_id, 1);
+        assert_eq!(sign_input.nonce, "0x9");
+        assert_eq!(sign_input.gas_price, "0x4a817c800");
+        assert_eq!(sign_input.gas_limit, "0x5208");
+        assert_eq!(sign_input.amount, "0xde0b6b3a7640000");
+        assert_eq!(
+            sign_input.to_address,
+            "0x3535353535353535353535353535353535353535"
+        );
+        assert!(decoded.signed);
+        assert_eq!(decoded.v, 37);
+        assert_eq!(
+            hex::encode(&decoded.sign_hash),
+            "daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+        );
+        assert_eq!(decoded.hash, Hasher::hash(Keccak256, &raw).unwrap());
+        assert_eq!(decoded.from, "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+    }
+
+    #[test]
+    fn test_decode_unsigned_legacy() {
+        // EIP-155 signing payload of the example above
+        let raw = hex::decode("ec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080018080").unwrap();
+        let decoded = decode_transaction(&raw).unwrap();
+        assert!(!decoded.signed);
+        assert!(decoded.from.is_empty());
+        assert!(decoded.hash.is_empty());
+        assert_eq!(decoded.sign_input.unwrap().chain_id, 1);
+        assert_eq!(
+            hex::encode(&decoded.sign_hash),
+            "daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53"
+        );
+
+        // Trailing bytes
+        let mut invalid = raw.to_vec();
+        invalid.push(0);
+        assert!(decode_transaction(&invalid).is_err());
+        assert!(decode_transaction(&[0x05, 0xc0]).is_err());
+    }
+
+    #[test]
+    fn test_decode_pre_eip155_legacy() {
+        // The EIP-155 example signed without a chain id, with v of 28
+        let raw = hex::decode("f86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a7640000801ca04e097a7b18732c750d5d72526e747c32e31f3e0e0ca5e15c8063f470655fa1b4a0117f4c49b0bf9ba19353290bf2a3ba945d499bc293880eb6a951a9ff91a9ef35").unwrap();
+        let decoded = decode_transaction(&raw).unwrap();
+        assert_eq!(decoded.sign_input.unwrap().chain_id, 0);
+        assert_eq!(decoded.v, 28);
+        assert_eq!(
+            hex::encode(&decoded.sign_hash),
+            "f9e36c28c8cb35adba138005c02ab7aa7fbcd891f3139cb2eeed052a51cd2713"
+        );
+        assert_eq!(
+            hex::encode(&decoded.hash),
+            "028a3b15ff9bab39d5d3f12c5878e4760439b027932591c67868a5ad0cd0c44f"
+        );
+        assert_eq!(decoded.from, "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+    }
+
+    #[test]
+    fn test_decode_typed_round_trip() {
+        let private_key = PrivateKey::from_str(
+            "4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+        for transaction_type in [TransactionType::AccessList, TransactionType::Eip1559] {
+            let input = SignInput {
+                chain_id: 5,
+                nonce: "0x1".to_owned(),
+                gas_limit: "0x62d4".to_owned(),
+                gas_price: "0x3b9aca00".to_owned(),
+                max_inclusion_fee_per_gas: "0x3b9aca00".to_owned(),
+                max_fee_per_gas: "0x77359400".to_owned(),
+                amount: "0x2386f26fc10000".to_owned(),
+                payload: vec![0xa9, 0x05, 0x9c, 0xbb],
+                to_address: "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae".to_owned(),
+                access_list: vec![AccessListItem {
+                    address: "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae".to_owned(),
+                    storage_keys: vec![
+                        "0x0000000000000000000000000000000000000000000000000000000000000003"
+                            .to_owned(),
+                    ],
+                }],
+                transaction_type: transaction_type as i32,
+                ..Default::default()
+            };
+            let output = Signer::sign(&private_key, &input, input.chain_id).unwrap();
+            let decoded = decode_transaction(&output.encoded).unwrap();
+            assert!(decoded.signed);
+            assert_eq!(decoded.v, output.v as u64);
+            assert_eq!(decoded.r, output.r);
+            assert_eq!(decoded.s, output.s);
+            assert_eq!(decoded.from, "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+
+            let decoded_input = decoded.sign_input.unwrap();
+            assert_eq!(decoded_input.chain_id, 5);
+            assert_eq!(decoded_input.payload, input.payload);
+            assert_eq!(decoded_input.access_list, input.access_list);
+            // Signing the decoded fields gives back the same transaction
+            let resigned =
+                Signer::sign(&private_key, &decoded_input, decoded_input.chain_id).unwrap();
+            assert_eq!(resigned.encoded, output.encoded);
+        }
+    }
+
+    #[test]
+    fn test_decode_set_code_round_trip() {
+        let private_key = PrivateKey::from_str(
+            "4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+        let mut input = SignInput {
+            chain_id: 1,
+            nonce: "0x2".to_owned(),
+            gas_limit: "0x186a0".to_owned(),
+            gas_price: "".to_owned(),
+            max_inclusion_fee_per_gas: "0x3b9aca00".to_owned(),
+            max_fee_per_gas: "0x77359400".to_owned(),
+            amount: "0x0".to_owned(),
+            payload: vec![],
+            to_address: "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F".to_owned(),
+            transaction_type: TransactionType::SetCode as i32,
+            authorization_list: vec![Authorization {
+                chain_id: 1,
+                address: "0x63c0c19a282a1b52b07dd5a65b58948a07dae32b".to_owned(),
+                nonce: "0x3".to_owned(),
+                y_parity: 1,
+                r: vec![0x11; 32],
+                s: vec![0x22; 32],
+            }],
+            ..Default::default()
+        };
+        let output = Signer::sign(&private_key, &input, input.chain_id).unwrap();
+        assert_eq!(output.encoded[0], 0x04);
+        let decoded = decode_transaction(&output.encoded).unwrap();
+        assert_eq!(
+            hex::encode(&decoded.sign_hash),
+            "e76f2e6bb34933f0c0b63d8abf0b5e323557db91450b0694f1c802c96c82183a"
+        );
+        assert_eq!(decoded.from, "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+        let decoded_input = decoded.sign_input.unwrap();
+        assert_eq!(decoded_input.authorization_list, input.authorization_list);
+        assert_eq!(
+            Signer::sign(&private_key, &decoded_input, decoded_input.chain_id)
+                .unwrap()
+                .encoded,
+            output.encoded
+        );
+
+        // A set code transaction needs authorizations and can't deploy contracts
+        input.to_address = "".to_owned();
+        input.payload = vec![0x00];
+        assert!(Signer::sign(&private_key, &input, input.chain_id).is_err());
+        input.to_address = "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F".to_owned();
+        input.authorization_list = vec![];
+        assert!(Signer::sign(&private_key, &input, input.chain_id).is_err());
+    }
+
+    #[test]
+    fn test_decode_blob_round_trip() {
+        let private_key = PrivateKey::from_str(
+            "4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+        let mut input = SignInput {
+            chain_id: 1,
+            nonce: "0x0".to_owned(),
+            gas_limit: "0x5208".to_owned(),
+            gas_price: "".to_owned(),
+            max_inclusion_fee_per_gas: "0x3b9aca00".to_owned(),
+            max_fee_per_gas: "0x77359400".to_owned(),
+            amount: "0x0".to_owned(),
+            payload: vec![],
+            to_address: "0x3535353535353535353535353535353535353535".to_owned(),
+            transaction_type: TransactionType::Blob as i32,
+            max_fee_per_blob_gas: "0x2".to_owned(),
+            blob_sidecar: Some(BlobSidecarInput {
+                blobs: vec![vec![0u8; BLOB_SIZE]],
+                commitments: vec![vec![0xc0; 48]],
+                proofs: vec![vec![0xc0; 48]],
+            }),
+            ..Default::default()
+        };
+        // Versioned hashes come from the commitments and the sidecar is wrapped around
+        let output = Signer::sign(&private_key, &input, input.chain_id).unwrap();
+        assert_eq!(output.encoded[0], 0x03);
+        assert!(output.encoded.len() > BLOB_SIZE);
+        let decoded = decode_transaction(&output.encoded).unwrap();
+        assert_eq!(
+            hex::encode(&decoded.sign_hash),
+            "da5e7fa4a6d9d79152c1a4e36494b9007418a2c17b6c7c7ef576adeaa739e8bf"
+        );
+        assert_eq!(decoded.hash, output.hash);
+        assert_eq!(decoded.from, "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+        let decoded_input = decoded.sign_input.unwrap();
+        let versioned_hashes = vec![versioned_hash(&[0xc0; 48]).unwrap().as_bytes().to_vec()];
+        assert_eq!(decoded_input.blob_versioned_hashes, versioned_hashes);
+        assert_eq!(decoded_input.blob_sidecar, input.blob_sidecar);
+
+        // Without the sidecar the transaction is signed as is
+        input.blob_sidecar = None;
+        input.blob_versioned_hashes = versioned_hashes;
+        let unwrapped = Signer::sign(&private_key, &input, input.chain_id).unwrap();
+        assert_eq!(unwrapped.hash, output.hash);
+        assert_eq!(
+            unwrapped.hash,
+            Hasher::hash(Keccak256, &unwrapped.encoded).unwrap()
+        );
+        assert_eq!(
+            decode_transaction(&unwrapped.encoded).unwrap().hash,
+            output.hash
+        );
+
+        // The hashes must match the blobs
+        input.blob_versioned_hashes[0][31] ^= 1;
+        input.blob_sidecar = decoded_input.blob_sidecar;
+        assert!(Signer::sign(&private_key, &input, input.chain_id).is_err());
+    }
+}
@@ -1,7 +1,11 @@
+use chain_common::ethereum::AccessListItem as AccessListItemInput;
+use chain_common::Error;
 use ethereum_types::{Address, H256};
-use rlp_derive::{RlpEncodable, RlpEncodableWrapper};
+use rlp_derive::{RlpDecodable, RlpDecodableWrapper, RlpEncodable, RlpEncodableWrapper};
+use std::convert::TryFrom;
+use std::str::FromStr;
 
-#[derive(RlpEncodableWrapper)]
+#[derive(RlpEncodableWrapper, RlpDecodableWrapper)]
 pub struct AccessList(pub Vec<AccessListItem>);
 
 impl From<Vec<AccessListItem>> for AccessList {
@@ -10,11 +14,82 @@ impl From<Vec<AccessListItem>> for AccessList {
     }
 }
 
+impl TryFrom<&[AccessListItemInput]> for AccessList {
+    type Error = Error;
+    fn try_from(input: &[AccessListItemInput]) -> Result<Self, Self::Error> {
+        let items = input
+            .iter()
+            .map(|item| {
+                let address = Address::from_str(trim_hex_prefix(&item.address))
+                    .map_err(|_| Error::InvalidSignInput)?;
+                let storage_keys = item
+                    .storage_keys
+                    .iter()
+                    .map(|key| H256::from_str(trim_hex_prefix(key)))
+                    .collect::<Result<Vec<H256>, _>>()
+                    .map_err(|_| Error::InvalidSignInput)?;
+                Ok(AccessListItem {
+                    address,
+                    storage_keys,
+                })
+            })
+            .collect::<Result<Vec<AccessListItem>, Error>>()?;
+        Ok(AccessList(items))
+    }
+}
+
+impl From<&AccessList> for Vec<AccessListItemInput> {
+    fn from(access_list: &AccessList) -> Self {
+        access_list
+            .0
+            .iter()
+            .map(|item| AccessListItemInput {
+                address: format!("{:#x}", item.address),
+                storage_keys: item
+                    .storage_keys
+                    .iter()
+                    .map(|key| format!("{:#x}", key))
+                    .collect(),
+            })
+            .collect()
+    }
+}
+
 /// Access list item
-#[derive(RlpEncodable)]
+#[derive(RlpEncodable, RlpDecodable)]
 pub struct AccessListItem {
     /// Accessed address
     pub address: Address,
     /// Accessed storage keys
     pub storage_keys: Vec<H256>,
 }
+
+pub(super) fn trim_hex_prefix(value: &str) -> &str {
+    value.strip_prefix("0x").unwrap_or(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_access_list() {
+        let input = vec![AccessListItemInput {
+            address: "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae".to_owned(),
+            storage_keys: vec![
+                "0x0000000000000000000000000000000000000000000000000000000000000003".to_owned(),
+                "0000000000000000000000000000000000000000000000000000000000000007".to_owned(),
+            ],
+        }];
+        let access_list = AccessList::try_from(input.as_slice()).unwrap();
+        assert_eq!(access_list.0[0].storage_keys.len(), 2);
+        assert_eq!(
+            hex::encode(rlp::encode(&access_list)),
+            "f85bf85994de0b295669a9fd93d5f28d9ec85e40f4cb697baef842a00000000000000000000000000000000000000000000000000000000000000003a00000000000000000000000000000000000000000000000000000000000000007"
+        );
+
+        let mut invalid_key = input.to_vec();
+        invalid_key[0].storage_keys[1] = "0x07".to_owned();
+        assert!(AccessList::try_from(invalid_key.as_slice()).is_err());
+    }
+}
@@ -0,0 +1,130 @@
+use chain_common::ethereum::BlobSidecar as BlobSidecarInput;
+use chain_common::Error;
+use crypto::hash::{Hasher, Sha256};
+use ethereum_types::{H256, U256};
+use rlp::RlpStream;
+use std::convert::TryFrom;
+
+pub const BLOB_SIZE: usize = 131_072;
+
+/// Blob gas used by every blob of a transaction
+pub const GAS_PER_BLOB: u64 = 131_072;
+
+const KZG_SIZE: usize = 48;
+
+const VERSIONED_HASH_VERSION_KZG: u8 = 0x01;
+
+/// Versioned hash of a blob from its KZG commitment, `0x01 || sha256(commitment)[1..]`
+pub fn versioned_hash(commitment: &[u8]) -> Result<H256, Error> {
+    if commitment.len() != KZG_SIZE {
+        return Err(Error::InvalidSignInput);
+    }
+    let mut hash = Hasher::hash(Sha256, commitment).map_err(|_| Error::InvalidSignInput)?;
+    hash[0] = VERSIONED_HASH_VERSION_KZG;
+    Ok(H256::from_slice(&hash))
+}
+
+/// The blobs of a transaction, sent along with it to the network but not part of its hash
+pub struct BlobSidecar {
+    pub blobs: Vec<Vec<u8>>,
+    pub commitments: Vec<Vec<u8>>,
+    pub proofs: Vec<Vec<u8>>,
+}
+
+impl TryFrom<&BlobSidecarInput> for BlobSidecar {
+    type Error = Error;
+    fn try_from(input: &BlobSidecarInput) -> Result<Self, Self::Error> {
+        let count = input.blobs.len();
+        if count == 0 || input.commitments.len() != count || input.proofs.len() != count {
+            return Err(Error::InvalidSignInput);
+        }
+        let sized = |items: &[Vec<u8>], size: usize| items.iter().all(|item| item.len() == size);
+        if !sized(&input.blobs, BLOB_SIZE)
+            || !sized(&input.commitments, KZG_SIZE)
+            || !sized(&input.proofs, KZG_SIZE)
+        {
+            return Err(Error::InvalidSignInput);
+        }
+        Ok(BlobSidecar {
+            blobs: input.blobs.to_vec(),
+            commitments: input.commitments.to_vec(),
+            proofs: input.proofs.to_vec(),
+        })
+    }
+}
+
+impl From<&BlobSidecar> for BlobSidecarInput {
+    fn from(sidecar: &BlobSidecar) -> Self {
+        BlobSidecarInput {
+            blobs: sidecar.blobs.to_vec(),
+            commitments: sidecar.commitments.to_vec(),
+            proofs: sidecar.proofs.to_vec(),
+        }
+    }
+}
+
+impl BlobSidecar {
+    pub fn versioned_hashes(&self) -> Result<Vec<H256>, Error> {
+        self.commitments
+            .iter()
+            .map(|commitment| versioned_hash(commitment))
+            .collect()
+    }
+
+    /// Network wrapper form `0x03 || rlp([tx_payload_body, blobs, commitments, proofs])` of a
+    /// signed transaction
+    pub fn wrap(&self, encoded: &[u8]) -> Vec<u8> {
+        let mut rlp_stream = RlpStream::new_list(4);
+        rlp_stream.append_raw(&encoded[1..], 1);
+        rlp_stream.append_list::<Vec<u8>, _>(&self.blobs);
+        rlp_stream.append_list::<Vec<u8>, _>(&self.commitments);
+        rlp_stream.append_list::<Vec<u8>, _>(&self.proofs);
+
+        let mut wrapped = vec![encoded[0]];
+        wrapped.extend_from_slice(&rlp_stream.out());
+        wrapped
+    }
+}
+
+/// The blob fee a transaction pays at most, `blob count * GAS_PER_BLOB * max_fee_per_blob_gas`
+pub fn max_blob_fee(blob_count: usize, max_fee_per_blob_gas: U256) -> Option<U256> {
+    U256::from(blob_count)
+        .checked_mul(U256::from(GAS_PER_BLOB))?
+        .checked_mul(max_fee_per_blob_gas)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_versioned_hash() {
+        let commitment = [0xc0u8; 48];
+        assert_eq!(
+            hex::encode(versioned_hash(&commitment).unwrap()),
+            "01e2f35246044cb2646ad1a00d9373fe9e4c129324952976bc2cecb9c31952b7"
+        );
+        assert!(versioned_hash(&commitment[..32]).is_err());
+    }
+
+    #[test]
+    fn test_parse_sidecar() {
+        let input = BlobSidecarInput {
+            blobs: vec![vec![0u8; BLOB_SIZE]],
+            commitments: vec![vec![0xc0; 48]],
+            proofs: vec![vec![0xc0; 48]],
+        };
+        let sidecar = BlobSidecar::try_from(&input).unwrap();
+        assert_eq!(
+            sidecar.versioned_hashes().unwrap(),
+            vec![versioned_hash(&[0xc0; 48]).unwrap()]
+        );
+
+        let mut missing_proof = input.clone();
+        missing_proof.proofs.clear();
+        assert!(BlobSidecar::try_from(&missing_proof).is_err());
+        let mut short_blob = input;
+        short_blob.blobs[0].pop();
+        assert!(BlobSidecar::try_from(&short_blob).is_err());
+    }
+}
@@ -0,0 +1,414 @@
+use chain_common::ethereum::TypedDataVersion;
+use chain_common::Error;
+use crypto::hash::{Hasher, Keccak256};
+use ethereum_types::U256;
+use serde_json::{Map, Value};
+use std::collections::BTreeSet;
+
+const DOMAIN_TYPE: &str = "EIP712Domain";
+
+/// A typed structured data message in the `eth_signTypedData` JSON format
+pub struct TypedData {
+    types: Map<String, Value>,
+    primary_type: String,
+    domain: Value,
+    message: Value,
+    version: TypedDataVersion,
+}
+
+impl TypedData {
+    pub fn new(json: &str, version: TypedDataVersion) -> Result<Self, Error> {
+        let value: Value = serde_json::from_str(json).map_err(|_| Error::InvalidSignInput)?;
+        let mut types = value
+            .get("types")
+            .and_then(Value::as_object)
+            .ok_or(Error::InvalidSignInput)?
+            .clone();
+        // A typed data without domain fields still has an empty `EIP712Domain` type
+        types
+            .entry(DOMAIN_TYPE)
+            .or_insert_with(|| Value::Array(vec![]));
+        let primary_type = value
+            .get("primaryType")
+            .and_then(Value::as_str)
+            .ok_or(Error::InvalidSignInput)?
+            .to_owned();
+        if !types.contains_key(&primary_type) {
+            return Err(Error::InvalidSignInput);
+        }
+        Ok(TypedData {
+            types,
+            primary_type,
+            domain: value.get("domain").cloned().unwrap_or(Value::Null),
+            message: value.get("message").cloned().unwrap_or(Value::Null),
+            version,
+        })
+    }
+
+    pub fn domain_separator(&self) -> Result<Vec<u8>, Error> {
+        self.hash_struct(DOMAIN_TYPE, &self.domain)
+    }
+
+    /// The digest to sign: `keccak256("\x19\x01" ‖ domainSeparator ‖ hashStruct(message))`
+    pub fn hash(&self) -> Result<Vec<u8>, Error> {
+        let mut encoded = vec![0x19, 0x01];
+        encoded.extend_from_slice(&self.domain_separator()?);
+        if self.primary_type != DOMAIN_TYPE {
+            encoded.extend_from_slice(&self.hash_struct(&self.primary_type, &self.message)?);
+        }
+        keccak256(&encoded)
+    }
+
+    pub fn hash_struct(&self, type_name: &str, data: &Value) -> Result<Vec<u8>, Error> {
+        keccak256(&self.encode_data(type_name, data)?)
+    }
+
+    /// `Name(type1 name1,...)` followed by the referenced struct types sorted by name
+    pub fn encode_type(&self, type_name: &str) -> Result<String, Error> {
+        let mut dependencies = BTreeSet::new();
+        self.find_dependencies(type_name, &mut dependencies)?;
+        dependencies.remove(type_name);
+
+        let mut encoded = String::new();
+        for name in std::iter::once(type_name).chain(dependencies.iter().map(String::as_str)) {
+            let fields = self
+                .fields(name)?
+                .iter()
+                .map(|(field_name, field_type)| format!("{} {}", field_type, field_name))
+                .collect::<Vec<String>>();
+            encoded.push_str(&format!("{}({})", name, fields.join(",")));
+        }
+        Ok(encoded)
+    }
+
+    fn find_dependencies(
+        &self,
+        type_name: &str,
+        found: &mut BTreeSet<String>,
+    ) -> Result<(), Error> {
+        let base_type = type_name.split('[').next().unwrap_or(type_name);
+        if found.contains(base_type) || !self.types.contains_key(base_type) {
+            return Ok(());
+        }
+        found.insert(base_type.to_owned());
+        for (_, field_type) in self.fields(base_type)? {
+            self.find_dependencies(&field_type, found)?;
+        }
+        Ok(())
+    }
+
+    fn fields(&self, type_name: &str) -> Result<Vec<(String, String)>, Error> {
+        self.types
+            .get(type_name)
+            .and_then(Value::as_array)
+            .ok_or(Error::InvalidSignInput)?
+            .iter()
+            .map(|field| {
+                let name = field.get("name").and_then(Value::as_str);
+                let r#type = field.get("type").and_then(Value::as_str);
+                match (name, r#type) {
+                    (Some(name), Some(r#type)) => Ok((name.to_owned(), r#type.to_owned())),
+                    _ => Err(Error::InvalidSignInput),
+                }
+            })
+            .collect()
+    }
+
+    fn encode_data(&self, type_name: &str, data: &Value) -> Result<Vec<u8>, Error> {
+        let mut encoded = keccak256(self.encode_type(type_name)?.as_bytes())?;
+        for (field_name, field_type) in self.fields(type_name)? {
+            let value = data.get(&field_name).unwrap_or(&Value::Null);
+            // v3 leaves missing fields out of the encoding
+            if self.version == TypedDataVersion::V3 && value.is_null() {
+                continue;
+            }
+            encoded.extend_from_slice(&self.encode_field(&field_type, value)?);
+        }
+        Ok(encoded)
+    }
+
+    fn encode_field(&self, field_type: &str, value: &Value) -> Result<Vec<u8>, Error> {
+        if self.types.contains_key(field_type) {
+            // v4 encodes a missing struct as zero
+            return match value.is_null() {
+                true => Ok(vec![0u8; 32]),
+                false => self.hash_struct(field_type, value),
+            };
+        }
+        if value.is_null() {
+            return Err(Error::InvalidSignInput);
+        }
+        if let Some(item_type) = array_item_type(field_type) {
+            // Arrays are only supported since v4
+            if self.version == TypedDataVersion::V3 {
+                return Err(Error::InvalidSignInput);
+            }
+            let items = value.as_array().ok_or(Error::InvalidSignInput)?;
+            if let Some(len) = array_fixed_len(field_type)? {
+                if items.len() != len {
+                    return Err(Error::InvalidSignInput);
+                }
+            }
+            let mut encoded = vec![];
+            for item in items {
+                encoded.extend_from_slice(&self.encode_field(item_type, item)?);
+            }
+            return keccak256(&encoded);
+        }
+        match field_type {
+            "string" => {
+                let value = value.as_str().ok_or(Error::InvalidSignInput)?;
+                keccak256(value.as_bytes())
+            }
+            "bytes" => keccak256(&decode_bytes(value)?),
+            "bool" => match value {
+                Value::Bool(value) => Ok(encode_uint(U256::from(*value as u8))),
+                _ => Err(Error::InvalidSignInput),
+            },
+            "address" => {
+                let data = decode_hex(value.as_str().ok_or(Error::InvalidSignInput)?)?;
+                if data.len() != 20 {
+                    return Err(Error::InvalidSignInput);
+                }
+                Ok(encode_uint(U256::from_big_endian(&data)))
+            }
+            _ => encode_atomic(field_type, value),
+        }
+    }
+}
+
+// Encode `bytesN`, `uintN` and `intN` values
+pub(super) fn encode_atomic(field_type: &str, value: &Value) -> Result<Vec<u8>, Error> {
+    if let Some(size) = field_type.strip_prefix("bytes") {
+        let size: usize = size.parse().map_err(|_| Error::InvalidSignInput)?;
+        let data = decode_bytes(value)?;
+        if size == 0 || size > 32 || data.len() > size {
+            return Err(Error::InvalidSignInput);
+        }
+        let mut encoded = data;
+        encoded.resize(32, 0);
+        return Ok(encoded);
+    }
+    let (signed, bits) = match field_type.strip_prefix("uint") {
+        Some(bits) => (false, bits),
+        None => (
+            true,
+            field_type
+                .strip_prefix("int")
+                .ok_or(Error::InvalidSignInput)?,
+        ),
+    };
+    let bits: usize = bits.parse().map_err(|_| Error::InvalidSignInput)?;
+    if !(8..=256).step_by(8).any(|size| size == bits) {
+        return Err(Error::InvalidSignInput);
+    }
+    let (negative, magnitude) = parse_integer(value)?;
+    let in_range = match (signed, negative) {
+        (false, true) => false,
+        (false, false) => magnitude.bits() <= bits,
+        (true, false) => magnitude.bits() < bits,
+        // The minimum of intN is -2^(N-1)
+        (true, true) => magnitude <= U256::one() << (bits - 1),
+    };
+    if !in_range {
+        return Err(Error::InvalidSignInput);
+    }
+    match negative {
+        // Two's complement over 256 bits
+        true => Ok(encode_uint((!magnitude).overflowing_add(U256::one()).0)),
+        false => Ok(encode_uint(magnitude)),
+    }
+}
+
+// Integers are either JSON numbers, decimal strings or 0x prefixed hex strings
+fn parse_integer(value: &Value) -> Result<(bool, U256), Error> {
+    match value {
+        Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+            (Some(value), _) => Ok((false, U256::from(value))),
+            (None, Some(value)) => Ok((true, U256::from(value.unsigned_abs()))),
+            _ => Err(Error::InvalidSignInput),
+        },
+        Value::String(value) => {
+            let (negative, value) = match value.strip_prefix('-') {
+                Some(value) => (true, value),
+                None => (false, value.as_str()),
+            };
+            let magnitude = match value.strip_prefix("0x") {
+                Some(hex) => U256::from_str_radix(hex, 16).ok(),
+                None => U256::from_dec_str(value).ok(),
+            }
+            .ok_or(Error::InvalidSignInput)?;
+            Ok((negative && !magnitude.is_zero(), magnitude))
+        }
+        _ => Err(Error::InvalidSignInput),
+    }
+}
+
+// Dynamic and fixed-size bytes are hex strings, other strings are taken as UTF-8 text
+fn decode_bytes(value: &Value) -> Result<Vec<u8>, Error> {
+    let value = value.as_str().ok_or(Error::InvalidSignInput)?;
+    match value.starts_with("0x") {
+        true => decode_hex(value),
+        false => Ok(value.as_bytes().to_vec()),
+    }
+}
+
+pub(super) fn decode_hex(value: &str) -> Result<Vec<u8>, Error> {
+    hex::decode(value.strip_prefix("0x").unwrap_or(value)).map_err(|_| Error::InvalidSignInput)
+}
+
+pub(super) fn encode_uint(value: U256) -> Vec<u8> {
+    let mut encoded = [0u8; 32];
+    value.to_big_endian(&mut encoded);
+    encoded.to_vec()
+}
+
+fn array_item_type(field_type: &str) -> Option<&str> {
+    match field_type.ends_with(']') {
+        true => field_type.rfind('[').map(|index| &field_type[..index]),
+        false => None,
+    }
+}
+
+fn array_fixed_len(field_type: &str) -> Result<Option<usize>, Error> {
+    let start = field_type.rfind('[').ok_or(Error::InvalidSignInput)?;
+    let len = &field_type[start + 1..field_type.len() - 1];
+    match len.is_empty() {
+        true => Ok(None),
+        false => len.parse().map(Some).map_err(|_| Error::InvalidSignInput),
+    }
+}
+
+pub(super) fn keccak256(input: &[u8]) -> Result<Vec<u8>, Error> {
+    Hasher::hash(Keccak256, input).map_err(|_| Error::InvalidSignInput)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAIL: &str = r#"{
+        "types": {
+            "EIP712Domain": [
+                { "name": "name", "type": "string" },
+                { "name": "version", "type": "string" },
+                { "name": "chainId", "type": "uint256" },
+                { "name": "verifyingContract", "type": "address" }
+            ],
+            "Person": [
+                { "name": "name", "type": "string" },
+                { "name": "wallet", "type": "address" }
+            ],
+            "Mail": [
+                { "name": "from", "type": "Person" },
+                { "name": "to", "type": "Person" },
+                { "name": "contents", "type": "string" }
+            ]
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        },
+        "message": {
+            "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+            "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+            "contents": "Hello, Bob!"
+        }
+    }"#;
+
+    #[test]
+    fn test_hash_mail() {
+        for version in [TypedDataVersion::V3, TypedDataVersion::V4] {
+            let typed_data = TypedData::new(MAIL, version).unwrap();
+            assert_eq!(
+                typed_data.encode_type("Mail").unwrap(),
+                "Mail(Person from,Person to,string contents)Person(string name,address wallet)"
+            );
+            assert_eq!(
+                hex::encode(typed_data.domain_separator().unwrap()),
+                "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f"
+            );
+            assert_eq!(
+                hex::encode(typed_data.hash_struct("Mail", &typed_data.message).unwrap()),
+                "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e"
+            );
+            assert_eq!(
+                hex::encode(typed_data.hash().unwrap()),
+                "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2"
+            );
+        }
+    }
+
+    #[test]
+    fn test_missing_fields() {
+        let json = MAIL.replace(
+            r#""to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },"#,
+            "",
+        );
+        // v3 skips the missing struct, v4 encodes it as zero
+        let v3 = TypedData::new(&json, TypedDataVersion::V3).unwrap();
+        let v4 = TypedData::new(&json, TypedDataVersion::V4).unwrap();
+        assert_ne!(v3.hash().unwrap(), v4.hash().unwrap());
+
+        let json = MAIL.replace(r#""contents": "Hello, Bob!""#, r#""contents": null"#);
+        assert!(TypedData::new(&json, TypedDataVersion::V4)
+            .unwrap()
+            .hash()
+            .is_err());
+    }
+
+    #[test]
+    fn test_arrays_require_v4() {
+        let json = MAIL.replace(
+            r#"{ "name": "to", "type": "Person" }"#,
+            r#"{ "name": "to", "type": "Person[1]" }"#,
+        );
+        let json = json.replace(
+            r#""to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" }"#,
+            r#""to": [{ "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" }]"#,
+        );
+        assert!(TypedData::new(&json, TypedDataVersion::V3)
+            .unwrap()
+            .hash()
+            .is_err());
+        assert!(TypedData::new(&json, TypedDataVersion::V4)
+            .unwrap()
+            .hash()
+            .is_ok());
+        // The length of a fixed-size array is checked
+        let json = json.replace("Person[1]", "Person[2]");
+        assert!(TypedData::new(&json, TypedDataVersion::V4)
+            .unwrap()
+            .hash()
+            .is_err());
+    }
+
+    #[test]
+    fn test_encode_atomic() {
+        let encode =
+            |field_type: &str, value: Value| encode_atomic(field_type, &value).map(hex::encode);
+        assert_eq!(
+            encode("int8", Value::from(-1)).unwrap(),
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+        );
+        assert_eq!(
+            encode("int8", Value::from("-128")).unwrap(),
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff80"
+        );
+        assert!(encode("int8", Value::from(128)).is_err());
+        assert!(encode("uint8", Value::from(-1)).is_err());
+        assert_eq!(
+            encode("uint256", Value::from("0x10")).unwrap(),
+            encode("uint256", Value::from("16")).unwrap()
+        );
+        assert_eq!(
+            encode("bytes4", Value::from("0x01020304")).unwrap(),
+            "0102030400000000000000000000000000000000000000000000000000000000"
+        );
+        assert!(encode("bytes2", Value::from("0x010203")).is_err());
+        assert!(encode("uint7", Value::from(1)).is_err());
+    }
+}
@@ -0,0 +1,155 @@
+use super::eip2930::trim_hex_prefix;
+use chain_common::ethereum::Authorization as AuthorizationInput;
+use chain_common::Error;
+use crypto::hash::{Hasher, Keccak256};
+use ethereum_types::{Address, U256};
+use rlp::RlpStream;
+use rlp_derive::{RlpDecodable, RlpDecodableWrapper, RlpEncodable, RlpEncodableWrapper};
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// Prefix of the authorization signing payload, keeping it apart from transactions
+const AUTHORIZATION_MAGIC: u8 = 0x05;
+
+#[derive(RlpEncodableWrapper, RlpDecodableWrapper)]
+pub struct AuthorizationList(pub Vec<SignedAuthorization>);
+
+impl TryFrom<&[AuthorizationInput]> for AuthorizationList {
+    type Error = Error;
+    fn try_from(input: &[AuthorizationInput]) -> Result<Self, Self::Error> {
+        let items = input
+            .iter()
+            .map(|authorization| {
+                let (address, nonce) = parse_authorization(authorization)?;
+                if authorization.y_parity > 1 {
+                    return Err(Error::InvalidSignInput);
+                }
+                let r = signature_value(&authorization.r)?;
+                let s = signature_value(&authorization.s)?;
+                Ok(SignedAuthorization {
+                    chain_id: authorization.chain_id,
+                    address,
+                    nonce,
+                    y_parity: authorization.y_parity as u8,
+                    r,
+                    s,
+                })
+            })
+            .collect::<Result<Vec<SignedAuthorization>, Error>>()?;
+        Ok(AuthorizationList(items))
+    }
+}
+
+impl From<&AuthorizationList> for Vec<AuthorizationInput> {
+    fn from(authorization_list: &AuthorizationList) -> Self {
+        authorization_list
+            .0
+            .iter()
+            .map(|authorization| AuthorizationInput {
+                chain_id: authorization.chain_id,
+                address: format!("{:#x}", authorization.address),
+                nonce: format!("{:#x}", authorization.nonce),
+                y_parity: authorization.y_parity as u32,
+                r: signature_bytes(authorization.r),
+                s: signature_bytes(authorization.s),
+            })
+            .collect()
+    }
+}
+
+/// Authorization tuple with its signature
+#[derive(RlpEncodable, RlpDecodable)]
+pub struct SignedAuthorization {
+    pub chain_id: u64,
+    /// Address the authorizing account delegates to
+    pub address: Address,
+    pub nonce: u64,
+    pub y_parity: u8,
+    pub r: U256,
+    pub s: U256,
+}
+
+/// The digest the authorizing account signs, `keccak256(0x05 || rlp([chain_id, address, nonce]))`
+pub fn authorization_hash(authorization: &AuthorizationInput) -> Result<Vec<u8>, Error> {
+    let (address, nonce) = parse_authorization(authorization)?;
+    let mut rlp_stream = RlpStream::new_list(3);
+    rlp_stream.append(&authorization.chain_id);
+    rlp_stream.append(&address);
+    rlp_stream.append(&nonce);
+
+    let mut encoded = vec![AUTHORIZATION_MAGIC];
+    encoded.extend_from_slice(&rlp_stream.out());
+    Hasher::hash(Keccak256, &encoded).map_err(|_| Error::InvalidSignInput)
+}
+
+fn parse_authorization(authorization: &AuthorizationInput) -> Result<(Address, u64), Error> {
+    let address = Address::from_str(trim_hex_prefix(&authorization.address))
+        .map_err(|_| Error::InvalidSignInput)?;
+    let nonce = u64::from_str_radix(trim_hex_prefix(&authorization.nonce), 16)
+        .map_err(|_| Error::InvalidSignInput)?;
+    Ok((address, nonce))
+}
+
+// Authorizations in a transaction must be signed
+fn signature_value(data: &[u8]) -> Result<U256, Error> {
+    if data.len() > 32 {
+        return Err(Error::InvalidSignInput);
+    }
+    let value = U256::from_big_endian(data);
+    match value.is_zero() {
+        true => Err(Error::InvalidSignInput),
+        false => Ok(value),
+    }
+}
+
+fn signature_bytes(value: U256) -> Vec<u8> {
+    let mut data = [0u8; 32];
+    value.to_big_endian(&mut data);
+    data.to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn authorization() -> AuthorizationInput {
+        AuthorizationInput {
+            chain_id: 1,
+            address: "0x63c0c19a282a1B52b07dD5a65b58948A07DAE32B".to_owned(),
+            nonce: "0x0".to_owned(),
+            y_parity: 0,
+            r: vec![],
+            s: vec![],
+        }
+    }
+
+    #[test]
+    fn test_authorization_hash() {
+        let mut authorization = authorization();
+        assert_eq!(
+            hex::encode(authorization_hash(&authorization).unwrap()),
+            "f743228656c74db57ce53cafe32c6023a8aad310a4bcc217d125f9f7796520fc"
+        );
+        authorization.nonce = "0x".to_owned();
+        assert!(authorization_hash(&authorization).is_err());
+    }
+
+    #[test]
+    fn test_parse_authorization_list() {
+        let mut authorization = authorization();
+        authorization.nonce = "0x1".to_owned();
+        authorization.y_parity = 1;
+        authorization.r = vec![0x11; 32];
+        authorization.s = vec![0x22; 32];
+        let list = AuthorizationList::try_from(vec![authorization.clone()].as_slice()).unwrap();
+        assert_eq!(
+            hex::encode(rlp::encode(&list)),
+            "f85cf85a019463c0c19a282a1b52b07dd5a65b58948a07dae32b0101a01111111111111111111111111111111111111111111111111111111111111111a02222222222222222222222222222222222222222222222222222222222222222"
+        );
+        assert_eq!(Vec::<AuthorizationInput>::from(&list)[0].r, authorization.r);
+
+        // Unsigned authorizations can't be included
+        authorization.r = vec![];
+        assert!(AuthorizationList::try_from(vec![authorization].as_slice()).is_err());
+    }
+}
@@ -1,6 +1,8 @@
-use super::address::EthereumAddress;
+use super::address::{ChecksumStatus, EthereumAddress};
+use super::address_checksum::{checksum, ChecksumType};
 use super::signer::Signer;
 use bytes::BytesMut;
+use chain_common::api::{AddressChecksum, ChecksumValidation};
 use chain_common::coin::Coin;
 use chain_common::entry::{ChainExportType, ChainImportType, Entry};
 use chain_common::ethereum::SignInput;
@@ -28,6 +30,29 @@ impl Entry for EthereumEntry {
         EthereumAddress::is_valid(address)
     }
 
+    fn validate_address_checksum(
+        &self,
+        coin: &Coin,
+        address: &str,
+        validation: ChecksumValidation,
+    ) -> (bool, AddressChecksum) {
+        let status = match EthereumAddress::checksum_status(address, checksum_type(coin)) {
+            Some(status) => status,
+            None => return (false, AddressChecksum::NoChecksum),
+        };
+        let valid = match validation {
+            ChecksumValidation::ChecksumIfMixedCase => status != ChecksumStatus::BadChecksum,
+            ChecksumValidation::ChecksumRequired => status == ChecksumStatus::Checksummed,
+        };
+        let address_checksum = match status {
+            ChecksumStatus::Lowercase => AddressChecksum::Lowercase,
+            ChecksumStatus::Uppercase => AddressChecksum::Uppercase,
+            ChecksumStatus::Checksummed => AddressChecksum::Checksummed,
+            ChecksumStatus::BadChecksum => AddressChecksum::BadChecksum,
+        };
+        (valid, address_checksum)
+    }
+
     fn derive_address(
         &self,
         coin: &Coin,
@@ -36,12 +61,12 @@ impl Entry for EthereumEntry {
         _hrp: &[u8],
     ) -> Result<String, Error> {
         let address = EthereumAddress::new(public_key, &coin.id)?;
-        Ok(address.to_string())
+        Ok(checksum(&address, checksum_type(coin)))
     }
 
     fn sign(
         &self,
-        _coin: &Coin,
+        coin: &Coin,
         private_key: &PrivateKey,
         payload: &[u8],
     ) -> Result<Vec<u8>, Error> {
@@ -49,8 +74,10 @@ impl Entry for EthereumEntry {
             Ok(request) => request,
             Err(_) => return Err(Error::InvalidPrivateKey),
         };
-        let output =
-            Signer::sign(private_key, &sign_input).map_err(|_| Error::InvalidPrivateKey)?;
+        // Every EVM network declares its chain id in the coin registry
+        let network_chain_id = coin.chain_id.ok_or(Error::InvalidPrivateKey)?;
+        let output = Signer::sign(private_key, &sign_input, network_chain_id)
+            .map_err(|_| Error::InvalidPrivateKey)?;
 
         let mut buf = BytesMut::with_capacity(output.encoded_len());
         output
@@ -58,4 +85,149 @@ impl Entry for EthereumEntry {
             .expect("Fail to encode the SignOutput");
         Ok(buf.to_vec())
     }
+
+    fn sign_message(
+        &self,
+        _coin: &Coin,
+        private_key: &PrivateKey,
+        message: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        Signer::sign_message(private_key, message).map_err(|_| Error::InvalidPrivateKey)
+    }
+
+    fn verify_message(
+        &self,
+        coin: &Coin,
+        message: &[u8],
+        signature: &[u8],
+        address: &str,
+    ) -> Result<bool, Error> {
+        let status = EthereumAddress::checksum_status(address, checksum_type(coin));
+        if status.is_none() || status == Some(ChecksumStatus::BadChecksum) {
+            return Ok(false);
+        }
+        Ok(Signer::verify_message(
+            message,
+            signature,
+            &address.to_lowercase(),
+        ))
+    }
+}
+
+/// Networks like RSK declare `"checksum": "eip1191"` in the coin registry
+fn checksum_type(coin: &Coin) -> ChecksumType {
+    match coin
+        .all_info
+        .get("checksum")
+        .and_then(|value| value.as_str())
+    {
+        Some("eip1191") => ChecksumType::Eip1191(coin.chain_id.unwrap_or_default()),
+        _ if coin.id.to_lowercase() == "wanchain" => ChecksumType::WanChain,
+        _ => ChecksumType::Eip55,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto::public_key::PublicKeyType;
+    use std::collections::HashMap;
+
+    fn rootstock() -> Coin {
+        let mut all_info = HashMap::new();
+        all_info.insert("checksum".to_owned(), serde_json::json!("eip1191"));
+        Coin {
+            id: "rootstock".to_owned(),
+            name: "Rootstock".to_owned(),
+            coin_id: 137,
+            symbol: "RBTC".to_owned(),
+            decimals: 18,
+            blockchain: "Ethereum".to_owned(),
+            derivation_path: "m/44'/137'/0'/0/0".to_owned(),
+            curve: "secp256k1".to_owned(),
+            public_key_type: "secp256k1Extended".to_owned(),
+            chain_id: Some(30),
+            all_info,
+        }
+    }
+
+    #[test]
+    fn test_eip1191_address() {
+        let public_key = PublicKey {
+            r#type: PublicKeyType::Secp256k1Extended,
+            data: hex::decode("0499c6f51ad6f98c9c583f8e92bb7758ab2ca9a04110c0a1126ec43e5453d196c166b489a4b7c491e7688e6ebea3a71fc3a1a48d60f98d5ce84c93b65e423fde91").unwrap(),
+        };
+        let coin = rootstock();
+        let address = EthereumEntry
+            .derive_address(&coin, &public_key, &[], &[])
+            .unwrap();
+        assert_eq!(address, "0xAc1Ec44e4f0CA7D172B7803F6836DE87fb72b309");
+
+        let validate = |address: &str, validation| {
+            EthereumEntry.validate_address_checksum(&coin, address, validation)
+        };
+        assert_eq!(
+            validate(&address, ChecksumValidation::ChecksumRequired),
+            (true, AddressChecksum::Checksummed)
+        );
+        assert_eq!(
+            validate(
+                &address.to_lowercase(),
+                ChecksumValidation::ChecksumIfMixedCase
+            ),
+            (true, AddressChecksum::Lowercase)
+        );
+        assert_eq!(
+            validate(
+                &address.to_lowercase(),
+                ChecksumValidation::ChecksumRequired
+            ),
+            (false, AddressChecksum::Lowercase)
+        );
+        // The EIP-55 checksum is wrong on RSK
+        assert_eq!(
+            validate(
+                "0xAc1ec44E4f0ca7D172B7803f6836De87Fb72b309",
+                ChecksumValidation::ChecksumIfMixedCase
+            ),
+            (false, AddressChecksum::BadChecksum)
+        );
+        assert_eq!(
+            validate("0xAc1ec44E", ChecksumValidation::ChecksumIfMixedCase),
+            (false, AddressChecksum::NoChecksum)
+        );
+    }
+
+    #[test]
+    fn test_sign_for_network() {
+        use std::str::FromStr;
+
+        let input = SignInput {
+            chain_id: 3,
+            nonce: "0x9".to_owned(),
+            gas_price: "0x4a817c800".to_owned(),
+            gas_limit: "0x5208".to_owned(),
+            amount: "0xde0b6b3a7640000".to_owned(),
+            to_address: "0x3535353535353535353535353535353535353535".to_owned(),
+            ..Default::default()
+        };
+        let mut payload = BytesMut::with_capacity(input.encoded_len());
+        input.encode(&mut payload).unwrap();
+        let private_key = PrivateKey::from_str(
+            "4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+
+        // A network only signs its own chain id
+        let mut coin = rootstock();
+        coin.chain_id = Some(3);
+        let output = EthereumEntry.sign(&coin, &private_key, &payload).unwrap();
+        let output = chain_common::ethereum::SignOutput::decode(&output[..]).unwrap();
+        assert_eq!(output.v, 41);
+        coin.chain_id = Some(1);
+        assert!(EthereumEntry.sign(&coin, &private_key, &payload).is_err());
+        // A network without a chain id signs nothing
+        coin.chain_id = None;
+        assert!(EthereumEntry.sign(&coin, &private_key, &payload).is_err());
+    }
 }
@@ -1,6 +1,19 @@
+pub mod abi;
 pub mod address;
 mod address_checksum;
+pub mod contract;
+pub mod decoder;
 mod eip2930;
+pub mod eip4844;
+mod eip7702;
+pub mod eip712;
 pub mod entry;
-mod signer;
+pub mod permit;
+pub mod preview;
+pub mod recovery;
+pub mod safe;
+pub mod signer;
+pub mod siwe;
 mod transaction;
+mod zksync;
+pub mod user_operation;
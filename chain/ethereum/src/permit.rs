@@ -0,0 +1,371 @@
+use super::address::EthereumAddress;
+use super::eip712::TypedData;
+use super::preview::{is_unlimited_amount, PERMIT2_ADDRESS};
+use chain_common::ethereum::{PermitField, PermitInput, PermitKind, PermitToken, TypedDataVersion};
+use chain_common::Error;
+use ethereum_types::U256;
+use serde_json::{json, Value};
+
+/// A permit as EIP-712 typed data, ready to be signed
+pub struct Permit {
+    pub typed_data: String,
+    pub digest: Vec<u8>,
+    pub fields: Vec<PermitField>,
+    pub unlimited: bool,
+}
+
+/// Build the typed data of an EIP-2612 or Permit2 permit and its digest
+pub fn build_permit(input: &PermitInput) -> Result<Permit, Error> {
+    let kind = PermitKind::from_i32(input.kind).ok_or(Error::InvalidSignInput)?;
+    let mut builder = Builder::default();
+    let (domain_type, domain, mut types, primary_type, message) = match kind {
+        PermitKind::Eip2612 => {
+            let token = single_token(input)?;
+            let verifying_contract = builder.address("token", &token.token)?;
+            let message = json!({
+                "owner": builder.address("owner", &input.owner)?,
+                "spender": builder.address("spender", &input.spender)?,
+                "value": builder.amount("value", &token.amount)?,
+                "nonce": builder.number("nonce", &input.nonce)?,
+                "deadline": builder.number("deadline", &input.deadline)?,
+            });
+            (
+                json!([
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" }
+                ]),
+                json!({
+                    "name": input.token_name,
+                    "version": input.token_version,
+                    "chainId": input.chain_id,
+                    "verifyingContract": verifying_contract,
+                }),
+                json!({
+                    "Permit": [
+                        { "name": "owner", "type": "address" },
+                        { "name": "spender", "type": "address" },
+                        { "name": "value", "type": "uint256" },
+                        { "name": "nonce", "type": "uint256" },
+                        { "name": "deadline", "type": "uint256" }
+                    ]
+                }),
+                "Permit",
+                message,
+            )
+        }
+        PermitKind::Permit2Single => {
+            let message = json!({
+                "details": builder.details("details", single_token(input)?)?,
+                "spender": builder.address("spender", &input.spender)?,
+                "sigDeadline": builder.number("sigDeadline", &input.deadline)?,
+            });
+            (
+                permit2_domain_type(),
+                permit2_domain(input.chain_id),
+                json!({
+                    "PermitSingle": [
+                        { "name": "details", "type": "PermitDetails" },
+                        { "name": "spender", "type": "address" },
+                        { "name": "sigDeadline", "type": "uint256" }
+                    ],
+                    "PermitDetails": permit_details_type(),
+                }),
+                "PermitSingle",
+                message,
+            )
+        }
+        PermitKind::Permit2Batch => {
+            if input.tokens.is_empty() {
+                return Err(Error::InvalidSignInput);
+            }
+            let details = input
+                .tokens
+                .iter()
+                .enumerate()
+                .map(|(index, token)| builder.details(&format!("details[{}]", index), token))
+                .collect::<Result<Vec<_>, Error>>()?;
+            let message = json!({
+                "details": details,
+                "spender": builder.address("spender", &input.spender)?,
+                "sigDeadline": builder.number("sigDeadline", &input.deadline)?,
+            });
+            (
+                permit2_domain_type(),
+                permit2_domain(input.chain_id),
+                json!({
+                    "PermitBatch": [
+                        { "name": "details", "type": "PermitDetails[]" },
+                        { "name": "spender", "type": "address" },
+                        { "name": "sigDeadline", "type": "uint256" }
+                    ],
+                    "PermitDetails": permit_details_type(),
+                }),
+                "PermitBatch",
+                message,
+            )
+        }
+        PermitKind::Permit2TransferFrom => {
+            let token = single_token(input)?;
+            let message = json!({
+                "permitted": {
+                    "token": builder.address("permitted.token", &token.token)?,
+                    "amount": builder.amount("permitted.amount", &token.amount)?,
+                },
+                "spender": builder.address("spender", &input.spender)?,
+                "nonce": builder.number("nonce", &input.nonce)?,
+                "deadline": builder.number("deadline", &input.deadline)?,
+            });
+            (
+                permit2_domain_type(),
+                permit2_domain(input.chain_id),
+                json!({
+                    "PermitTransferFrom": [
+                        { "name": "permitted", "type": "TokenPermissions" },
+                        { "name": "spender", "type": "address" },
+                        { "name": "nonce", "type": "uint256" },
+                        { "name": "deadline", "type": "uint256" }
+                    ],
+                    "TokenPermissions": [
+                        { "name": "token", "type": "address" },
+                        { "name": "amount", "type": "uint256" }
+                    ],
+                }),
+                "PermitTransferFrom",
+                message,
+            )
+        }
+    };
+
+    types["EIP712Domain"] = domain_type;
+    let typed_data = json!({
+        "types": types,
+        "primaryType": primary_type,
+        "domain": domain,
+        "message": message,
+    })
+    .to_string();
+    let digest = TypedData::new(&typed_data, TypedDataVersion::V4)?.hash()?;
+    Ok(Permit {
+        typed_data,
+        digest,
+        fields: builder.fields,
+        unlimited: builder.unlimited,
+    })
+}
+
+// Collects the decoded fields in message order while the message is built
+#[derive(Default)]
+struct Builder {
+    fields: Vec<PermitField>,
+    unlimited: bool,
+}
+
+impl Builder {
+    fn address(&mut self, name: &str, address: &str) -> Result<Value, Error> {
+        if !EthereumAddress::is_valid(address) {
+            return Err(Error::InvalidSignInput);
+        }
+        Ok(self.field(name, address.to_owned()))
+    }
+
+    fn number(&mut self, name: &str, quantity: &str) -> Result<Value, Error> {
+        let value = quantity_value(quantity)?;
+        Ok(self.field(name, value.to_string()))
+    }
+
+    fn amount(&mut self, name: &str, quantity: &str) -> Result<Value, Error> {
+        let value = quantity_value(quantity)?;
+        self.unlimited |= is_unlimited_amount(value);
+        Ok(self.field(name, value.to_string()))
+    }
+
+    // Permit2 PermitDetails
+    fn details(&mut self, prefix: &str, token: &PermitToken) -> Result<Value, Error> {
+        Ok(json!({
+            "token": self.address(&format!("{}.token", prefix), &token.token)?,
+            "amount": self.amount(&format!("{}.amount", prefix), &token.amount)?,
+            "expiration": self.number(&format!("{}.expiration", prefix), &token.expiration)?,
+            "nonce": self.number(&format!("{}.nonce", prefix), &token.nonce)?,
+        }))
+    }
+
+    fn field(&mut self, name: &str, value: String) -> Value {
+        self.fields.push(PermitField {
+            name: name.to_owned(),
+            value: value.clone(),
+        });
+        Value::String(value)
+    }
+}
+
+fn single_token(input: &PermitInput) -> Result<&PermitToken, Error> {
+    match input.tokens.as_slice() {
+        [token] => Ok(token),
+        _ => Err(Error::InvalidSignInput),
+    }
+}
+
+fn permit2_domain_type() -> Value {
+    json!([
+        { "name": "name", "type": "string" },
+        { "name": "chainId", "type": "uint256" },
+        { "name": "verifyingContract", "type": "address" }
+    ])
+}
+
+fn permit2_domain(chain_id: u64) -> Value {
+    json!({
+        "name": "Permit2",
+        "chainId": chain_id,
+        "verifyingContract": PERMIT2_ADDRESS,
+    })
+}
+
+fn permit_details_type() -> Value {
+    json!([
+        { "name": "token", "type": "address" },
+        { "name": "amount", "type": "uint160" },
+        { "name": "expiration", "type": "uint48" },
+        { "name": "nonce", "type": "uint48" }
+    ])
+}
+
+fn quantity_value(quantity: &str) -> Result<U256, Error> {
+    match quantity.strip_prefix("0x").unwrap_or(quantity) {
+        "" => Ok(U256::zero()),
+        quantity => U256::from_str_radix(quantity, 16).map_err(|_| Error::InvalidSignInput),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+    const SPENDER: &str = "0x3fC91A3afd70395Cd496C647d5a6CC9D4B2b7FAD";
+
+    fn token(amount: &str) -> PermitToken {
+        PermitToken {
+            token: TOKEN.to_owned(),
+            amount: amount.to_owned(),
+            expiration: "0x6553f100".to_owned(),
+            nonce: "0x2".to_owned(),
+        }
+    }
+
+    fn input(kind: PermitKind, tokens: Vec<PermitToken>) -> PermitInput {
+        PermitInput {
+            kind: kind as i32,
+            chain_id: 1,
+            token_name: "USD Coin".to_owned(),
+            token_version: "2".to_owned(),
+            owner: "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F".to_owned(),
+            spender: SPENDER.to_owned(),
+            tokens,
+            nonce: "0x5".to_owned(),
+            deadline: "0x6553f100".to_owned(),
+        }
+    }
+
+    fn fields(permit: &Permit) -> Vec<(&str, &str)> {
+        permit
+            .fields
+            .iter()
+            .map(|field| (field.name.as_str(), field.value.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn test_eip2612_permit() {
+        let permit = build_permit(&input(PermitKind::Eip2612, vec![token("0xf4240")])).unwrap();
+        assert_eq!(
+            hex::encode(&permit.digest),
+            "869053a95e05619af099a8fc12ac8620a7e6789daecd3f444dab67104f8df8cf"
+        );
+        assert_eq!(
+            fields(&permit),
+            vec![
+                ("token", TOKEN),
+                ("owner", "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"),
+                ("spender", SPENDER),
+                ("value", "1000000"),
+                ("nonce", "5"),
+                ("deadline", "1700000000"),
+            ]
+        );
+        assert!(!permit.unlimited);
+    }
+
+    #[test]
+    fn test_permit2_single() {
+        let max_uint160 = format!("{:#x}", (U256::one() << 160) - 1);
+        let permit =
+            build_permit(&input(PermitKind::Permit2Single, vec![token(&max_uint160)])).unwrap();
+        assert_eq!(
+            hex::encode(&permit.digest),
+            "f494daf14e73dfe8cc50b710359467424a877e5f21ddedfb0bf7710a56dcb467"
+        );
+        assert_eq!(
+            fields(&permit),
+            vec![
+                ("details.token", TOKEN),
+                (
+                    "details.amount",
+                    "1461501637330902918203684832716283019655932542975"
+                ),
+                ("details.expiration", "1700000000"),
+                ("details.nonce", "2"),
+                ("spender", SPENDER),
+                ("sigDeadline", "1700000000"),
+            ]
+        );
+        assert!(permit.unlimited);
+
+        // Permit2 amounts are uint160
+        let overflow = format!("{:#x}", U256::one() << 160);
+        assert!(build_permit(&input(PermitKind::Permit2Single, vec![token(&overflow)])).is_err());
+        assert!(build_permit(&input(PermitKind::Permit2Single, vec![])).is_err());
+    }
+
+    #[test]
+    fn test_permit2_batch() {
+        let mut second = token("0x3e8");
+        second.token = "0xdAC17F958D2ee523a2206206994597C13D831ec7".to_owned();
+        let permit = build_permit(&input(
+            PermitKind::Permit2Batch,
+            vec![token("0xf4240"), second],
+        ))
+        .unwrap();
+        assert_eq!(
+            hex::encode(&permit.digest),
+            "9f66958fa1a355536f202649b5ae9540b90bf561b8436e1fc5a23bc574b12aee"
+        );
+        assert_eq!(permit.fields[4].name, "details[1].token");
+        assert_eq!(permit.fields[5].value, "1000");
+    }
+
+    #[test]
+    fn test_permit2_transfer_from() {
+        let permit = build_permit(&input(
+            PermitKind::Permit2TransferFrom,
+            vec![token("0xf4240")],
+        ))
+        .unwrap();
+        assert_eq!(
+            hex::encode(&permit.digest),
+            "56bdb1fa8d0a170f116998aab1fea5b574d381f00ce0a5f75c50255eaf42e5c2"
+        );
+        assert_eq!(
+            fields(&permit),
+            vec![
+                ("permitted.token", TOKEN),
+                ("permitted.amount", "1000000"),
+                ("spender", SPENDER),
+                ("nonce", "5"),
+                ("deadline", "1700000000"),
+            ]
+        );
+    }
+}
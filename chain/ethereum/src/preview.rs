@@ -0,0 +1,389 @@
+use super::abi::{self, Function};
+use super::address::EthereumAddress;
+use super::address_checksum::{checksum, ChecksumType};
+use super::eip4844::max_blob_fee;
+use super::transaction::{
+    transaction_type, Eip1559Transaction, Eip2930Transaction, Eip4844Transaction,
+    Eip7702Transaction, LegacyTransaction, TransactionBase,
+};
+use chain_common::ethereum::{
+    RiskType, SignInput, TransactionPreview, TransactionType, TransactionWarning,
+};
+use chain_common::Error;
+use ethereum_types::U256;
+use serde_json::Value;
+use std::convert::TryFrom;
+
+/// The Uniswap Permit2 contract, deployed at the same address on every chain
+pub const PERMIT2_ADDRESS: &str = "0x000000000022D473030F116dDEE9F6B43aC78BA3";
+
+// Token functions recognised in the calldata of any contract
+const TOKEN_FUNCTIONS: &[&str] = &[
+    "transfer(address to, uint256 amount)",
+    "transferFrom(address from, address to, uint256 amount)",
+    "approve(address spender, uint256 amount)",
+    "increaseAllowance(address spender, uint256 addedValue)",
+    "setApprovalForAll(address operator, bool approved)",
+    "safeTransferFrom(address from, address to, uint256 tokenId)",
+    "safeTransferFrom(address from, address to, uint256 tokenId, bytes data)",
+    "safeTransferFrom(address from, address to, uint256 id, uint256 amount, bytes data)",
+    "safeBatchTransferFrom(address from, address to, uint256[] ids, uint256[] amounts, bytes data)",
+];
+
+// Functions recognised in the calldata of the Permit2 contract
+const PERMIT2_FUNCTIONS: &[&str] = &[
+    "approve(address token, address spender, uint160 amount, uint48 expiration)",
+    "permit(address owner, ((address token, uint160 amount, uint48 expiration, uint48 nonce) details, address spender, uint256 sigDeadline) permitSingle, bytes signature)",
+    "permit(address owner, ((address token, uint160 amount, uint48 expiration, uint48 nonce)[] details, address spender, uint256 sigDeadline) permitBatch, bytes signature)",
+];
+
+/// Summarise `sign_input` and flag its risks before signing it
+///
+/// Whether the recipient is a contract is only known on chain, so it's given by the caller.
+/// Value sent along with calldata is always taken as sent to a contract.
+pub fn preview_transaction(
+    sign_input: &SignInput,
+    recipient_is_contract: bool,
+) -> Result<TransactionPreview, Error> {
+    let base = TransactionBase::try_from(sign_input)?;
+    let (gas_price, blob_fee) = match transaction_type(sign_input)? {
+        TransactionType::Legacy => (
+            LegacyTransaction::try_from(sign_input)?.gas_price,
+            U256::zero(),
+        ),
+        TransactionType::AccessList => (
+            Eip2930Transaction::try_from(sign_input)?.gas_price,
+            U256::zero(),
+        ),
+        TransactionType::Eip1559 | TransactionType::ZkSyncEip712 => (
+            Eip1559Transaction::try_from(sign_input)?.max_fee_per_gas,
+            U256::zero(),
+        ),
+        TransactionType::Blob => {
+            let transaction = Eip4844Transaction::try_from(sign_input)?;
+            let blob_fee = max_blob_fee(
+                transaction.blob_versioned_hashes.len(),
+                transaction.max_fee_per_blob_gas,
+            )
+            .ok_or(Error::InvalidSignInput)?;
+            (transaction.max_fee_per_gas, blob_fee)
+        }
+        TransactionType::SetCode => (
+            Eip7702Transaction::try_from(sign_input)?.max_fee_per_gas,
+            U256::zero(),
+        ),
+        TransactionType::Unspecified => return Err(Error::InvalidSignInput),
+    };
+    // Blob transactions also pay for blob gas
+    let max_fee = base
+        .gas_limit
+        .checked_mul(gas_price)
+        .and_then(|fee| fee.checked_add(blob_fee))
+        .ok_or(Error::InvalidSignInput)?;
+    let mut preview = TransactionPreview {
+        to: "".to_owned(),
+        value: base.amount.to_string(),
+        max_fee: max_fee.to_string(),
+        method: "".to_owned(),
+        args: "".to_owned(),
+        is_deployment: base.receiver.is_none(),
+        warnings: vec![],
+    };
+    let receiver = match base.receiver {
+        Some(receiver) => receiver,
+        None => return Ok(preview),
+    };
+    preview.to = checksum(
+        &EthereumAddress {
+            coin_id: "ethereum".to_owned(),
+            data: receiver.as_bytes().to_vec(),
+        },
+        ChecksumType::Eip55,
+    );
+    if !base.amount.is_zero() && (recipient_is_contract || !base.payload.is_empty()) {
+        preview.warnings.push(warning(
+            RiskType::ValueToContract,
+            format!("Sends {} wei to the contract {}", base.amount, preview.to),
+        ));
+    }
+    if let Some((function, args)) = decode_known_call(&preview.to, &base.payload)? {
+        let warnings = call_warnings(&preview.to, &function, &args)?;
+        preview.warnings.extend(warnings);
+        preview.method = function.signature();
+        preview.args = args.to_string();
+    }
+    Ok(preview)
+}
+
+// The recognised function of `payload` and its arguments, none for unknown calldata
+fn decode_known_call(to: &str, payload: &[u8]) -> Result<Option<(Function, Value)>, Error> {
+    if payload.len() < 4 {
+        return Ok(None);
+    }
+    let signatures = match is_permit2(to) {
+        true => PERMIT2_FUNCTIONS,
+        false => TOKEN_FUNCTIONS,
+    };
+    for signature in signatures {
+        let function = abi::find_function(signature, "")?;
+        if function.selector()? == payload[..4] {
+            return Ok(function
+                .decode_input(payload)
+                .ok()
+                .map(|args| (function, args)));
+        }
+    }
+    Ok(None)
+}
+
+fn call_warnings(
+    to: &str,
+    function: &Function,
+    args: &Value,
+) -> Result<Vec<TransactionWarning>, Error> {
+    let mut warnings = vec![];
+    match (is_permit2(to), function.signature().as_str()) {
+        (false, "approve(address,uint256)") | (false, "increaseAllowance(address,uint256)") => {
+            let spender = string_arg(&args[0]["value"])?;
+            if is_permit2(spender) {
+                warnings.push(warning(
+                    RiskType::Permit2Approval,
+                    format!("Allows Permit2 to spend the token {}", to),
+                ));
+            }
+            if is_unlimited(&args[1]["value"])? {
+                warnings.push(warning(
+                    RiskType::UnlimitedApproval,
+                    format!("Allows {} to spend an unlimited amount of {}", spender, to),
+                ));
+            }
+        }
+        // Revoking with `approved` false is safe
+        (false, "setApprovalForAll(address,bool)") if args[1]["value"] == Value::Bool(true) => {
+            let operator = string_arg(&args[0]["value"])?;
+            warnings.push(warning(
+                RiskType::ApprovalForAll,
+                format!(
+                    "Allows {} to transfer all of your tokens of {}",
+                    operator, to
+                ),
+            ));
+        }
+        (true, "approve(address,address,uint160,uint48)") => {
+            let token = string_arg(&args[0]["value"])?;
+            let spender = string_arg(&args[1]["value"])?;
+            warnings.push(warning(
+                RiskType::Permit2Approval,
+                format!("Allows {} to spend {} through Permit2", spender, token),
+            ));
+            if is_unlimited(&args[2]["value"])? {
+                warnings.push(warning(
+                    RiskType::UnlimitedApproval,
+                    format!(
+                        "Allows {} to spend an unlimited amount of {}",
+                        spender, token
+                    ),
+                ));
+            }
+        }
+        (true, _) if function.name == "permit" => {
+            let permit = &args[1]["value"];
+            let spender = string_arg(&permit["spender"])?;
+            // `details` is a single allowance for PermitSingle and a list for PermitBatch
+            let details = match &permit["details"] {
+                Value::Array(details) => details.iter().collect(),
+                details => vec![details],
+            };
+            for details in details {
+                let token = string_arg(&details["token"])?;
+                warnings.push(warning(
+                    RiskType::Permit2Approval,
+                    format!("Allows {} to spend {} through Permit2", spender, token),
+                ));
+                if is_unlimited(&details["amount"])? {
+                    warnings.push(warning(
+                        RiskType::UnlimitedApproval,
+                        format!(
+                            "Allows {} to spend an unlimited amount of {}",
+                            spender, token
+                        ),
+                    ));
+                }
+            }
+        }
+        _ => {}
+    }
+    Ok(warnings)
+}
+
+fn warning(risk: RiskType, message: String) -> TransactionWarning {
+    TransactionWarning {
+        risk: risk as i32,
+        message,
+    }
+}
+
+fn is_permit2(address: &str) -> bool {
+    address.eq_ignore_ascii_case(PERMIT2_ADDRESS)
+}
+
+fn is_unlimited(amount: &Value) -> Result<bool, Error> {
+    let amount = U256::from_dec_str(string_arg(amount)?).map_err(|_| Error::InvalidSignInput)?;
+    Ok(is_unlimited_amount(amount))
+}
+
+// Amounts of 2^128 and more are far beyond any token supply, including the max uint160 of Permit2
+pub(crate) fn is_unlimited_amount(amount: U256) -> bool {
+    amount >= U256::one() << 128
+}
+
+fn string_arg(value: &Value) -> Result<&str, Error> {
+    value.as_str().ok_or(Error::InvalidSignInput)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const TOKEN: &str = "0xdAC17F958D2ee523a2206206994597C13D831ec7";
+    const SPENDER: &str = "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed";
+    const MAX_UINT256: &str =
+        "115792089237316195423570985008687907853269984665640564039457584007913129639935";
+
+    fn input(to: &str, amount: &str, payload: Vec<u8>) -> SignInput {
+        SignInput {
+            chain_id: 1,
+            nonce: "0x0".to_owned(),
+            gas_limit: "0x5208".to_owned(),
+            gas_price: "0x4a817c800".to_owned(),
+            max_inclusion_fee_per_gas: "0x0".to_owned(),
+            max_fee_per_gas: "0x0".to_owned(),
+            amount: amount.to_owned(),
+            payload,
+            to_address: to.to_owned(),
+            transaction_type: TransactionType::Legacy as i32,
+            ..Default::default()
+        }
+    }
+
+    fn calldata(signature: &str, args: Value) -> Vec<u8> {
+        abi::find_function(signature, "")
+            .unwrap()
+            .encode_input(&args)
+            .unwrap()
+    }
+
+    fn risks(preview: &TransactionPreview) -> Vec<i32> {
+        preview
+            .warnings
+            .iter()
+            .map(|warning| warning.risk)
+            .collect()
+    }
+
+    #[test]
+    fn test_preview_transfer() {
+        let recipient = "0x3535353535353535353535353535353535353535";
+        let preview =
+            preview_transaction(&input(recipient, "0xde0b6b3a7640000", vec![]), false).unwrap();
+        assert_eq!(preview.to, recipient);
+        assert_eq!(preview.value, "1000000000000000000");
+        assert_eq!(preview.max_fee, "420000000000000");
+        assert!(preview.method.is_empty());
+        assert!(preview.warnings.is_empty());
+
+        let preview =
+            preview_transaction(&input(recipient, "0xde0b6b3a7640000", vec![]), true).unwrap();
+        assert_eq!(risks(&preview), vec![RiskType::ValueToContract as i32]);
+
+        let payload = calldata("deposit()", json!([]));
+        let preview = preview_transaction(&input(TOKEN, "0x1", payload), false).unwrap();
+        assert_eq!(risks(&preview), vec![RiskType::ValueToContract as i32]);
+        assert!(preview.method.is_empty());
+    }
+
+    #[test]
+    fn test_preview_approve() {
+        let payload = calldata("approve(address,uint256)", json!([SPENDER, MAX_UINT256]));
+        let preview = preview_transaction(&input(TOKEN, "0x0", payload), false).unwrap();
+        assert_eq!(preview.method, "approve(address,uint256)");
+        assert_eq!(risks(&preview), vec![RiskType::UnlimitedApproval as i32]);
+        let args: Value = serde_json::from_str(&preview.args).unwrap();
+        assert_eq!(args[0]["name"], "spender");
+        assert_eq!(args[0]["value"], SPENDER);
+
+        let payload = calldata(
+            "increaseAllowance(address,uint256)",
+            json!([SPENDER, "1000"]),
+        );
+        let preview = preview_transaction(&input(TOKEN, "0x0", payload), false).unwrap();
+        assert_eq!(preview.method, "increaseAllowance(address,uint256)");
+        assert!(preview.warnings.is_empty());
+
+        let payload = calldata(
+            "approve(address,uint256)",
+            json!([PERMIT2_ADDRESS, MAX_UINT256]),
+        );
+        let preview = preview_transaction(&input(TOKEN, "0x0", payload), false).unwrap();
+        assert_eq!(
+            risks(&preview),
+            vec![
+                RiskType::Permit2Approval as i32,
+                RiskType::UnlimitedApproval as i32
+            ]
+        );
+    }
+
+    #[test]
+    fn test_preview_approval_for_all() {
+        let payload = calldata("setApprovalForAll(address,bool)", json!([SPENDER, true]));
+        let preview = preview_transaction(&input(TOKEN, "0x0", payload), false).unwrap();
+        assert_eq!(risks(&preview), vec![RiskType::ApprovalForAll as i32]);
+
+        // Revoking is safe
+        let payload = calldata("setApprovalForAll(address,bool)", json!([SPENDER, false]));
+        let preview = preview_transaction(&input(TOKEN, "0x0", payload), false).unwrap();
+        assert!(preview.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_preview_permit2() {
+        let max_uint160 = "1461501637330902918203684832716283019655932542975";
+        let payload = calldata(
+            PERMIT2_FUNCTIONS[0],
+            json!([TOKEN, SPENDER, max_uint160, "1700000000"]),
+        );
+        let preview = preview_transaction(&input(PERMIT2_ADDRESS, "0x0", payload), false).unwrap();
+        assert_eq!(preview.method, "approve(address,address,uint160,uint48)");
+        assert_eq!(
+            risks(&preview),
+            vec![
+                RiskType::Permit2Approval as i32,
+                RiskType::UnlimitedApproval as i32
+            ]
+        );
+
+        let details = json!({"token": TOKEN, "amount": "1000", "expiration": 0, "nonce": 0});
+        let payload = calldata(
+            PERMIT2_FUNCTIONS[2],
+            json!([
+                SPENDER,
+                {"details": [details], "spender": SPENDER, "sigDeadline": 0},
+                "0x00"
+            ]),
+        );
+        let preview = preview_transaction(&input(PERMIT2_ADDRESS, "0x0", payload), false).unwrap();
+        assert_eq!(risks(&preview), vec![RiskType::Permit2Approval as i32]);
+    }
+
+    #[test]
+    fn test_preview_deployment() {
+        let preview = preview_transaction(&input("", "0x0", vec![0x60, 0x80]), false).unwrap();
+        assert!(preview.is_deployment);
+        assert!(preview.to.is_empty());
+        assert!(preview.warnings.is_empty());
+
+        assert!(preview_transaction(&input("0x1234", "0x0", vec![]), false).is_err());
+    }
+}
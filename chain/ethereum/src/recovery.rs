@@ -0,0 +1,153 @@
+use super::address::EthereumAddress;
+use super::address_checksum::{checksum, ChecksumType};
+use super::eip712::TypedData;
+use super::signer::hash_personal_message;
+use chain_common::ethereum::TypedDataVersion;
+use chain_common::public_key::PublicKey;
+use chain_common::Error;
+use crypto::public_key::PublicKeyType;
+use secp256k1::ecdsa::{RecoverableSignature, RecoveryId};
+use secp256k1::Secp256k1;
+
+/// The signer recovered from a signature
+pub struct RecoveredSigner {
+    /// 65-byte uncompressed public key
+    pub public_key: Vec<u8>,
+
+    /// EIP-55 checksummed address
+    pub address: String,
+}
+
+/// Recover the signer of a 32-byte digest, e.g. a transaction hash
+///
+/// `r` and `s` may have their leading zeros trimmed, `v` is either the recovery id, 27/28 or an
+/// EIP-155 value `chain_id * 2 + 35/36`
+pub fn recover_from_hash(
+    hash: &[u8],
+    r: &[u8],
+    s: &[u8],
+    v: u64,
+) -> Result<RecoveredSigner, Error> {
+    if r.len() > 32 || s.len() > 32 {
+        return Err(Error::InvalidSignInput);
+    }
+    let mut compact = [0u8; 64];
+    compact[32 - r.len()..32].copy_from_slice(r);
+    compact[64 - s.len()..].copy_from_slice(s);
+
+    let recovery_id = RecoveryId::from_i32(recovery_id(v)?).map_err(|_| Error::InvalidSignInput)?;
+    let signature = RecoverableSignature::from_compact(&compact, recovery_id)
+        .map_err(|_| Error::InvalidSignInput)?;
+    let message = secp256k1::Message::from_slice(hash).map_err(|_| Error::InvalidSignInput)?;
+    let public_key = Secp256k1::verification_only()
+        .recover_ecdsa(&message, &signature)
+        .map_err(|_| Error::InvalidSignInput)?
+        .serialize_uncompressed()
+        .to_vec();
+
+    let address = EthereumAddress::new(
+        &PublicKey {
+            r#type: PublicKeyType::Secp256k1Extended,
+            data: public_key.to_vec(),
+        },
+        "ethereum",
+    )
+    .map_err(|_| Error::InvalidSignInput)?;
+    Ok(RecoveredSigner {
+        public_key,
+        address: checksum(&address, ChecksumType::Eip55),
+    })
+}
+
+/// Recover the signer of an EIP-191 personal_sign message
+pub fn recover_from_message(
+    message: &[u8],
+    r: &[u8],
+    s: &[u8],
+    v: u64,
+) -> Result<RecoveredSigner, Error> {
+    recover_from_hash(&hash_personal_message(message)?, r, s, v)
+}
+
+/// Recover the signer of EIP-712 typed structured data
+pub fn recover_from_typed_data(
+    typed_data: &str,
+    version: TypedDataVersion,
+    r: &[u8],
+    s: &[u8],
+    v: u64,
+) -> Result<RecoveredSigner, Error> {
+    let hash = TypedData::new(typed_data, version)?.hash()?;
+    recover_from_hash(&hash, r, s, v)
+}
+
+/// Recover the signer from a 65-byte `r ‖ s ‖ v` signature
+pub fn recover_from_signature(hash: &[u8], signature: &[u8]) -> Result<RecoveredSigner, Error> {
+    if signature.len() != 65 {
+        return Err(Error::InvalidSignInput);
+    }
+    recover_from_hash(
+        hash,
+        &signature[..32],
+        &signature[32..64],
+        signature[64] as u64,
+    )
+}
+
+fn recovery_id(v: u64) -> Result<i32, Error> {
+    match v {
+        0 | 1 => Ok(v as i32),
+        27 | 28 => Ok((v - 27) as i32),
+        // EIP-155
+        v if v >= 35 => Ok(((v - 35) % 2) as i32),
+        _ => Err(Error::InvalidSignInput),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recover_from_message() {
+        let signature = hex::decode("b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c").unwrap();
+        let recovered =
+            recover_from_message(b"Some data", &signature[..32], &signature[32..64], 28).unwrap();
+        assert_eq!(
+            recovered.address,
+            "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23"
+        );
+        assert_eq!(recovered.public_key.len(), 65);
+        assert_eq!(recovered.public_key[0], 0x04);
+
+        // The raw recovery id, and an EIP-155 v with the same parity
+        for v in [1, 38] {
+            let recovered =
+                recover_from_message(b"Some data", &signature[..32], &signature[32..64], v)
+                    .unwrap();
+            assert_eq!(
+                recovered.address,
+                "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23"
+            );
+        }
+        assert!(
+            recover_from_message(b"Some data", &signature[..32], &signature[32..64], 29).is_err()
+        );
+    }
+
+    #[test]
+    fn test_recover_from_transaction_hash() {
+        // EIP-155 example transaction, signed with 0x4646..46 on chain 1 with v = 37
+        let hash = hex::decode("daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53")
+            .unwrap();
+        let r = hex::decode("28ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276")
+            .unwrap();
+        let s = hex::decode("67cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83")
+            .unwrap();
+        let recovered = recover_from_hash(&hash, &r, &s, 37).unwrap();
+        assert_eq!(
+            recovered.address,
+            "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+        );
+    }
+}
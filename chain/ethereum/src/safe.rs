@@ -0,0 +1,220 @@
+use super::address::EthereumAddress;
+use super::address_checksum::{checksum, ChecksumType};
+use super::eip712::TypedData;
+use super::recovery::recover_from_hash;
+use super::signer::hash_personal_message;
+use chain_common::ethereum::{SafeTransaction, TypedDataVersion};
+use chain_common::Error;
+use serde_json::json;
+
+const ZERO_ADDRESS: &str = "0x0000000000000000000000000000000000000000";
+
+/// The safeTxHash that owners sign, the EIP-712 hash of the `SafeTx` struct
+pub fn safe_transaction_hash(transaction: &SafeTransaction) -> Result<Vec<u8>, Error> {
+    let (domain_type, domain) = match transaction.chain_id {
+        0 => (
+            json!([{ "name": "verifyingContract", "type": "address" }]),
+            json!({ "verifyingContract": transaction.safe_address }),
+        ),
+        chain_id => (
+            json!([
+                { "name": "chainId", "type": "uint256" },
+                { "name": "verifyingContract", "type": "address" }
+            ]),
+            json!({ "chainId": chain_id, "verifyingContract": transaction.safe_address }),
+        ),
+    };
+    let typed_data = json!({
+        "types": {
+            "EIP712Domain": domain_type,
+            "SafeTx": [
+                { "name": "to", "type": "address" },
+                { "name": "value", "type": "uint256" },
+                { "name": "data", "type": "bytes" },
+                { "name": "operation", "type": "uint8" },
+                { "name": "safeTxGas", "type": "uint256" },
+                { "name": "baseGas", "type": "uint256" },
+                { "name": "gasPrice", "type": "uint256" },
+                { "name": "gasToken", "type": "address" },
+                { "name": "refundReceiver", "type": "address" },
+                { "name": "nonce", "type": "uint256" }
+            ]
+        },
+        "primaryType": "SafeTx",
+        "domain": domain,
+        "message": {
+            "to": transaction.to,
+            "value": quantity(&transaction.value),
+            "data": format!("0x{}", hex::encode(&transaction.data)),
+            "operation": transaction.operation,
+            "safeTxGas": quantity(&transaction.safe_tx_gas),
+            "baseGas": quantity(&transaction.base_gas),
+            "gasPrice": quantity(&transaction.gas_price),
+            "gasToken": address_or_zero(&transaction.gas_token),
+            "refundReceiver": address_or_zero(&transaction.refund_receiver),
+            "nonce": quantity(&transaction.nonce),
+        }
+    });
+    TypedData::new(&typed_data.to_string(), TypedDataVersion::V4)?.hash()
+}
+
+/// Concatenate owner signatures into the `signatures` bytes of `execTransaction`, sorted by
+/// owner address as the Safe contract requires, along with the sorted owners
+///
+/// Owners are recovered from EIP-712 signatures with v 27 or 28 and eth_sign signatures with v
+/// 31 or 32, and read from r for pre-approved hashes with v 1
+pub fn combine_signatures(
+    safe_tx_hash: &[u8],
+    signatures: &[Vec<u8>],
+) -> Result<(Vec<u8>, Vec<String>), Error> {
+    let mut owners = signatures
+        .iter()
+        .map(|signature| Ok((signature_owner(safe_tx_hash, signature)?, signature)))
+        .collect::<Result<Vec<_>, Error>>()?;
+    owners.sort_by_key(|(owner, _)| owner.to_lowercase());
+    if owners
+        .windows(2)
+        .any(|pair| pair[0].0.eq_ignore_ascii_case(&pair[1].0))
+    {
+        return Err(Error::InvalidSignInput);
+    }
+    let combined = owners
+        .iter()
+        .flat_map(|(_, signature)| signature.iter().copied())
+        .collect();
+    Ok((
+        combined,
+        owners.into_iter().map(|(owner, _)| owner).collect(),
+    ))
+}
+
+fn signature_owner(safe_tx_hash: &[u8], signature: &[u8]) -> Result<String, Error> {
+    if signature.len() != 65 {
+        return Err(Error::InvalidSignInput);
+    }
+    let (r, s, v) = (&signature[..32], &signature[32..64], signature[64]);
+    match v {
+        27 | 28 => Ok(recover_from_hash(safe_tx_hash, r, s, v as u64)?.address),
+        31 | 32 => {
+            let hash = hash_personal_message(safe_tx_hash)?;
+            Ok(recover_from_hash(&hash, r, s, (v - 4) as u64)?.address)
+        }
+        // The owner approved the hash on chain and is left padded in r
+        1 if r[..12].iter().all(|byte| *byte == 0) => Ok(checksum(
+            &EthereumAddress {
+                coin_id: "ethereum".to_owned(),
+                data: r[12..].to_vec(),
+            },
+            ChecksumType::Eip55,
+        )),
+        _ => Err(Error::InvalidSignInput),
+    }
+}
+
+fn quantity(value: &str) -> &str {
+    match value.is_empty() {
+        true => "0x0",
+        false => value,
+    }
+}
+
+fn address_or_zero(address: &str) -> &str {
+    match address.is_empty() {
+        true => ZERO_ADDRESS,
+        false => address,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::signer::Signer;
+    use chain_common::ethereum::{SafeOperation, SafeSignatureType};
+    use chain_common::private_key::PrivateKey;
+    use std::str::FromStr;
+
+    fn transaction() -> SafeTransaction {
+        SafeTransaction {
+            safe_address: "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed".to_owned(),
+            chain_id: 1,
+            to: "0x3535353535353535353535353535353535353535".to_owned(),
+            value: "0xde0b6b3a7640000".to_owned(),
+            data: vec![],
+            operation: SafeOperation::Call as i32,
+            safe_tx_gas: "".to_owned(),
+            base_gas: "".to_owned(),
+            gas_price: "".to_owned(),
+            gas_token: "".to_owned(),
+            refund_receiver: "".to_owned(),
+            nonce: "0x3".to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_safe_transaction_hash() {
+        let mut transaction = transaction();
+        assert_eq!(
+            hex::encode(safe_transaction_hash(&transaction).unwrap()),
+            "0e056a45f235e15af42dae120da1fd133472aec2da68fbd2bb2d41d515b51509"
+        );
+        transaction.chain_id = 0;
+        assert_eq!(
+            hex::encode(safe_transaction_hash(&transaction).unwrap()),
+            "a0d1d429d87cb84d1d0bd4f3c1a8611b9a1b666315d9783baeaeddd7e3d05da1"
+        );
+    }
+
+    #[test]
+    fn test_combine_signatures() {
+        let transaction = transaction();
+        let safe_tx_hash = safe_transaction_hash(&transaction).unwrap();
+        let first = PrivateKey::from_str(
+            "4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+        let second = PrivateKey::from_str(
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+        )
+        .unwrap();
+        let first_signature =
+            Signer::sign_safe_transaction(&first, &transaction, SafeSignatureType::Eip712).unwrap();
+        let second_signature =
+            Signer::sign_safe_transaction(&second, &transaction, SafeSignatureType::EthSign)
+                .unwrap();
+        assert!(first_signature[64] == 27 || first_signature[64] == 28);
+        assert!(second_signature[64] == 31 || second_signature[64] == 32);
+
+        // 0x2c75... sorts before 0x9d8A...
+        let (combined, owners) = combine_signatures(
+            &safe_tx_hash,
+            &[first_signature.to_vec(), second_signature.to_vec()],
+        )
+        .unwrap();
+        assert_eq!(
+            owners,
+            vec![
+                "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23",
+                "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+            ]
+        );
+        assert_eq!(combined[..65], second_signature[..]);
+        assert_eq!(combined[65..], first_signature[..]);
+
+        // Pre-approved hash of an owner
+        let mut approved = vec![0u8; 12];
+        approved
+            .extend_from_slice(&hex::decode("0000000000000000000000000000000000000001").unwrap());
+        approved.extend_from_slice(&[0u8; 32]);
+        approved.push(1);
+        let (_, owners) =
+            combine_signatures(&safe_tx_hash, &[first_signature.to_vec(), approved]).unwrap();
+        assert_eq!(owners[0], "0x0000000000000000000000000000000000000001");
+
+        assert!(combine_signatures(
+            &safe_tx_hash,
+            &[first_signature.to_vec(), first_signature.to_vec()]
+        )
+        .is_err());
+        assert!(combine_signatures(&safe_tx_hash, &[first_signature[..64].to_vec()]).is_err());
+    }
+}
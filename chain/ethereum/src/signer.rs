@@ -1,103 +1,260 @@
 use super::address::EthereumAddress;
-use super::transaction::{Eip1559Transaction, LegacyTransaction, Transaction};
-use chain_common::ethereum::{SignInput, SignOutput};
+use super::contract::create_address;
+use super::eip712::TypedData;
+use super::eip7702::authorization_hash;
+use super::permit::build_permit;
+use super::recovery::recover_from_signature;
+use super::safe::safe_transaction_hash;
+use super::transaction::{
+    transaction_type, Eip1559Transaction, Eip2930Transaction, Eip4844Transaction,
+    Eip7702Transaction, LegacyTransaction, Transaction, TransactionBase,
+};
+use super::user_operation::user_operation_hash;
+use super::zksync::ZkSyncTransaction;
+use chain_common::ethereum::{
+    Authorization, PermitInput, PermitSignOutput, SafeSignatureType, SafeTransaction, SignInput,
+    SignOutput, TransactionType, TypedDataVersion, UserOperation, UserOperationSignOutput,
+};
 use chain_common::private_key::PrivateKey;
+use chain_common::public_key::PublicKey;
 use chain_common::Error;
-use ethereum_types::U256;
+use crypto::hash::{Hasher, Keccak256};
+use crypto::public_key::PublicKeyType;
+use ethereum_types::H160;
 use secp256k1::Secp256k1;
 use std::convert::TryFrom;
 
 pub struct Signer;
 
 impl Signer {
-    pub fn sign(private_key: &PrivateKey, sign_input: &SignInput) -> Result<SignOutput, Error> {
-        if !EthereumAddress::is_valid(&sign_input.to_address) {
+    /// Sign a transaction for the network with `network_chain_id`, the chain id of the input must match it
+    pub fn sign(
+        private_key: &PrivateKey,
+        sign_input: &SignInput,
+        network_chain_id: u64,
+    ) -> Result<SignOutput, Error> {
+        if sign_input.chain_id != network_chain_id {
+            return Err(Error::InvalidSignInput);
+        }
+        // An empty recipient deploys the contract in the payload
+        let is_deployment = sign_input.to_address.is_empty();
+        if is_deployment && sign_input.payload.is_empty() {
+            return Err(Error::InvalidSignInput);
+        }
+        if !is_deployment
+            && !EthereumAddress::is_valid_on_chain(&sign_input.to_address, sign_input.chain_id)
+        {
             return Err(Error::InvalidSignInput);
         }
         let chain_id = sign_input.chain_id;
         let secp = Secp256k1::signing_only();
         let secrect_key = &secp256k1::SecretKey::from_slice(&private_key.data)
             .map_err(|_| Error::InvalidPrivateKey)?;
-        let gas_price =
-            U256::from_str_radix(&sign_input.gas_price, 16).map_err(|_| Error::InvalidSignInput)?;
-        match gas_price == U256::from(0) {
-            false => {
-                let transaction = LegacyTransaction::try_from(sign_input)?;
-                let hash = transaction
-                    .hash(chain_id)
-                    .map_err(|_| Error::InvalidSignInput)?;
-                let (v_id, signature) = secp
-                    .sign_ecdsa_recoverable(
-                        &secp256k1::Message::from_slice(&hash)
-                            .map_err(|_| Error::InvalidSignInput)?,
-                        secrect_key,
-                    )
-                    .serialize_compact();
-                let mut r = signature[0..32].to_vec();
-                let mut s = signature[32..64].to_vec();
-                while r[0] == 0 {
-                    r.remove(0);
-                }
-                while s[0] == 0 {
-                    s.remove(0);
-                }
-                let mut v = v_id.to_i32() as u64;
-                if transaction.uses_replay_protection() {
-                    // Embed chainID in V param, for replay protection, legacy (EIP155)
-                    if chain_id != 0 {
-                        v += 35 + chain_id * 2;
-                    } else {
-                        v += 27;
-                    }
-                }
-                let encoded = transaction.encode_transaction(v as u64, &r, &s, chain_id);
-                Ok(SignOutput {
-                    data: transaction.base.payload,
-                    encoded,
-                    r,
-                    v: v as u32,
-                    s,
-                })
+        let public_key = secp256k1::PublicKey::from_secret_key(&secp, secrect_key);
+        let sender = EthereumAddress::new(
+            &PublicKey {
+                r#type: PublicKeyType::Secp256k1Extended,
+                data: public_key.serialize_uncompressed().to_vec(),
+            },
+            "ethereum",
+        )
+        .map_err(|_| Error::InvalidPrivateKey)?;
+        let transaction: Box<dyn Transaction> = match transaction_type(sign_input)? {
+            TransactionType::Legacy => Box::new(LegacyTransaction::try_from(sign_input)?),
+            TransactionType::AccessList => Box::new(Eip2930Transaction::try_from(sign_input)?),
+            TransactionType::Eip1559 => Box::new(Eip1559Transaction::try_from(sign_input)?),
+            TransactionType::Blob => Box::new(Eip4844Transaction::try_from(sign_input)?),
+            TransactionType::SetCode => Box::new(Eip7702Transaction::try_from(sign_input)?),
+            TransactionType::ZkSyncEip712 => Box::new(ZkSyncTransaction::new(
+                sign_input,
+                H160::from_slice(&sender.data),
+            )?),
+            TransactionType::Unspecified => return Err(Error::InvalidSignInput),
+        };
+        let hash = transaction
+            .hash(chain_id)
+            .map_err(|_| Error::InvalidSignInput)?;
+        let (v_id, signature) = secp
+            .sign_ecdsa_recoverable(
+                &secp256k1::Message::from_slice(&hash).map_err(|_| Error::InvalidSignInput)?,
+                secrect_key,
+            )
+            .serialize_compact();
+        let mut r = signature[0..32].to_vec();
+        let mut s = signature[32..64].to_vec();
+        while r[0] == 0 {
+            r.remove(0);
+        }
+        while s[0] == 0 {
+            s.remove(0);
+        }
+        let mut v = v_id.to_i32() as u64;
+        if transaction.uses_replay_protection() {
+            // Embed chainID in V param, for replay protection, legacy (EIP155)
+            if chain_id != 0 {
+                v += 35 + chain_id * 2;
+            } else {
+                v += 27;
             }
+        }
+        let encoded = transaction.encode_transaction(v, &r, &s, chain_id);
+        let transaction_hash = transaction
+            .transaction_hash(&encoded, v, &r, &s)
+            .map_err(|_| Error::InvalidSignInput)?;
+        let encoded = transaction.network_encoding(encoded);
+        let contract_address = match is_deployment {
             true => {
-                let transaction = Eip1559Transaction::try_from(sign_input)?;
-                let hash = transaction
-                    .hash(chain_id)
-                    .map_err(|_| Error::InvalidSignInput)?;
-                let (v_id, signature) = secp
-                    .sign_ecdsa_recoverable(
-                        &secp256k1::Message::from_slice(&hash)
-                            .map_err(|_| Error::InvalidSignInput)?,
-                        secrect_key,
-                    )
-                    .serialize_compact();
-                let mut r = signature[0..32].to_vec();
-                let mut s = signature[32..64].to_vec();
-                while r[0] == 0 {
-                    r.remove(0);
-                }
-                while s[0] == 0 {
-                    s.remove(0);
-                }
-                let v = v_id.to_i32();
-                let encoded = transaction.encode_transaction(v as u64, &r, &s, chain_id);
-                Ok(SignOutput {
-                    data: transaction.base.payload,
-                    encoded,
-                    r,
-                    v: v as u32,
-                    s,
-                })
+                let nonce = TransactionBase::try_from(sign_input)?.nonce;
+                create_address(&sender.to_string(), nonce)?
+            }
+            false => "".to_owned(),
+        };
+        Ok(SignOutput {
+            data: sign_input.payload.to_vec(),
+            encoded,
+            r,
+            v: v as u32,
+            s,
+            contract_address,
+            hash: transaction_hash,
+        })
+    }
+
+    /// Sign the EIP-712 digest of `typed_data`, the signature is `r ‖ s ‖ v` with v of 27 or 28
+    pub fn sign_typed_data(
+        private_key: &PrivateKey,
+        typed_data: &str,
+        version: TypedDataVersion,
+    ) -> Result<Vec<u8>, Error> {
+        let hash = TypedData::new(typed_data, version)?.hash()?;
+        sign_hash(private_key, &hash)
+    }
+
+    /// Sign the userOpHash of an ERC-4337 UserOperation with personal_sign, as validated by
+    /// SimpleAccount-style ECDSA accounts
+    pub fn sign_user_operation(
+        private_key: &PrivateKey,
+        user_operation: &UserOperation,
+    ) -> Result<UserOperationSignOutput, Error> {
+        let user_op_hash = user_operation_hash(user_operation)?;
+        let signature = Self::sign_message(private_key, &user_op_hash)?;
+        Ok(UserOperationSignOutput {
+            user_op_hash,
+            signature,
+        })
+    }
+
+    /// Sign an EIP-7702 authorization delegating the account to `authorization.address`, the
+    /// signature fields of the input are ignored
+    pub fn sign_authorization(
+        private_key: &PrivateKey,
+        authorization: &Authorization,
+    ) -> Result<Authorization, Error> {
+        let signature = sign_hash(private_key, &authorization_hash(authorization)?)?;
+        Ok(Authorization {
+            y_parity: (signature[64] - 27) as u32,
+            r: signature[..32].to_vec(),
+            s: signature[32..64].to_vec(),
+            ..authorization.clone()
+        })
+    }
+
+    /// Sign an EIP-2612 or Permit2 permit, echoing the typed data and its decoded fields
+    pub fn sign_permit(
+        private_key: &PrivateKey,
+        input: &PermitInput,
+    ) -> Result<PermitSignOutput, Error> {
+        let permit = build_permit(input)?;
+        let signature = sign_hash(private_key, &permit.digest)?;
+        Ok(PermitSignOutput {
+            digest: permit.digest,
+            signature,
+            typed_data: permit.typed_data,
+            fields: permit.fields,
+            unlimited: permit.unlimited,
+        })
+    }
+
+    /// Sign the safeTxHash of a Safe transaction as an owner, eth_sign signatures have v raised
+    /// by 4 to 31 or 32 so the Safe recovers them from the prefixed hash
+    pub fn sign_safe_transaction(
+        private_key: &PrivateKey,
+        transaction: &SafeTransaction,
+        signature_type: SafeSignatureType,
+    ) -> Result<Vec<u8>, Error> {
+        let safe_tx_hash = safe_transaction_hash(transaction)?;
+        match signature_type {
+            SafeSignatureType::Eip712 => sign_hash(private_key, &safe_tx_hash),
+            SafeSignatureType::EthSign => {
+                let mut signature = Self::sign_message(private_key, &safe_tx_hash)?;
+                signature[64] += 4;
+                Ok(signature)
             }
         }
     }
 }
 
+impl Signer {
+    /// Sign `message` with EIP-191 personal_sign, the signature is `r ‖ s ‖ v` with v of 27 or 28
+    pub fn sign_message(private_key: &PrivateKey, message: &[u8]) -> Result<Vec<u8>, Error> {
+        sign_hash(private_key, &hash_personal_message(message)?)
+    }
+
+    /// Whether `signature` is a personal_sign signature of `message` made by `address`
+    pub fn verify_message(message: &[u8], signature: &[u8], address: &str) -> bool {
+        let address = match EthereumAddress::is_valid(address) {
+            true => hex::decode(&address[2..]).unwrap_or_default(),
+            false => return false,
+        };
+        let recovered = hash_personal_message(message)
+            .and_then(|hash| recover_public_key(&hash, signature))
+            .and_then(|public_key| {
+                EthereumAddress::new(&public_key, "").map_err(|_| Error::InvalidSignInput)
+            });
+        match recovered {
+            Ok(recovered) => recovered.data == address,
+            Err(_) => false,
+        }
+    }
+}
+
+// keccak256("\x19Ethereum Signed Message:\n" ‖ len(message) ‖ message)
+pub(crate) fn hash_personal_message(message: &[u8]) -> Result<Vec<u8>, Error> {
+    let mut data = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    data.extend_from_slice(message);
+    Hasher::hash(Keccak256, &data).map_err(|_| Error::InvalidSignInput)
+}
+
+// Recover the public key from a `r ‖ s ‖ v` signature, v is either 27/28 or the raw recovery id
+pub(crate) fn recover_public_key(hash: &[u8], signature: &[u8]) -> Result<PublicKey, Error> {
+    let recovered = recover_from_signature(hash, signature)?;
+    PublicKey::new(PublicKeyType::Secp256k1Extended, &recovered.public_key)
+        .map_err(|_| Error::InvalidSignInput)
+}
+
+// Sign a 32-byte digest into `r ‖ s ‖ v`, v is 27 or 28
+fn sign_hash(private_key: &PrivateKey, hash: &[u8]) -> Result<Vec<u8>, Error> {
+    let secp = Secp256k1::signing_only();
+    let secret_key = secp256k1::SecretKey::from_slice(&private_key.data)
+        .map_err(|_| Error::InvalidPrivateKey)?;
+    let message = secp256k1::Message::from_slice(hash).map_err(|_| Error::InvalidSignInput)?;
+    let (recovery_id, signature) = secp
+        .sign_ecdsa_recoverable(&message, &secret_key)
+        .serialize_compact();
+    let mut encoded = signature.to_vec();
+    encoded.push(recovery_id.to_i32() as u8 + 27);
+    Ok(encoded)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use chain_common::ethereum::SignInput;
+    use crate::recovery::recover_from_hash;
+    use chain_common::ethereum::{
+        AccessListItem, EntryPointVersion, PermitKind, PermitToken, SignInput,
+    };
     use chain_common::private_key::PrivateKey;
+    use ethereum_types::U256;
 
     use std::convert::TryFrom;
     use std::str::FromStr;
@@ -113,6 +270,7 @@ mod tests {
             amount: "0xde0b6b3a7640000".to_owned(),
             payload: "".as_bytes().to_vec(),
             to_address: "0x3535353535353535353535353535353535353535".to_owned(),
+            ..Default::default()
         };
         let transaction = LegacyTransaction::try_from(&input).unwrap();
         assert_eq!(transaction.base.amount.to_string(), "1000000000000000000");
@@ -130,8 +288,13 @@ mod tests {
             "4646464646464646464646464646464646464646464646464646464646464646",
         )
         .unwrap();
-        let output = Signer::sign(&private_key, &input).unwrap();
+        let output = Signer::sign(&private_key, &input, input.chain_id).unwrap();
         assert_eq!(output.v, 37);
+        // Chain id 1 can't be signed on another network
+        assert_eq!(
+            Signer::sign(&private_key, &input, 137).unwrap_err(),
+            Error::InvalidSignInput
+        );
 
         assert_eq!(
             output.encoded,
@@ -160,6 +323,7 @@ mod tests {
             amount: "0x1bc16d674ec80000".to_owned(),
             payload: payload_bytes.to_vec(),
             to_address: "0x5322b34c88ed0691971bf52a7047448f0f4efc84".to_owned(),
+            ..Default::default()
         };
         let transaction = LegacyTransaction::try_from(&input).unwrap();
         assert_eq!(transaction.base.amount.to_string(), "2000000000000000000");
@@ -171,7 +335,7 @@ mod tests {
             "608dcb1742bb3fb7aec002074e3420e4fab7d00cced79ccdac53ed5b27138151",
         )
         .unwrap();
-        let output = Signer::sign(&private_key, &input).unwrap();
+        let output = Signer::sign(&private_key, &input, input.chain_id).unwrap();
         assert_eq!(output.v, 37);
     }
 
@@ -187,16 +351,62 @@ mod tests {
             amount: "0xde0b6b3a7640000".to_owned(),
             payload: "".as_bytes().to_vec(),
             to_address: "0x146aed09cd9dea7a64de689c5d3ef73d2ee5ca".to_owned(), // short addr
+            ..Default::default()
         };
         let private_key = PrivateKey::from_str(
             "4646464646464646464646464646464646464646464646464646464646464646",
         )
         .unwrap();
-        let sign_error = Signer::sign(&private_key, &input);
+        let sign_error = Signer::sign(&private_key, &input, input.chain_id);
         assert!(sign_error.is_err());
         assert_eq!(sign_error.err().unwrap(), Error::InvalidSignInput);
     }
 
+    #[test]
+    fn test_sign_contract_deployment() {
+        let mut input = SignInput {
+            chain_id: 1,
+            nonce: "0x2".to_owned(),
+            gas_limit: "0x186a0".to_owned(),
+            gas_price: "0x4a817c800".to_owned(),
+            max_inclusion_fee_per_gas: "0x0".to_owned(),
+            max_fee_per_gas: "0x0".to_owned(),
+            amount: "0x0".to_owned(),
+            payload: hex::decode("6080604052348015600f57600080fd5b50").unwrap(),
+            to_address: "".to_owned(),
+            transaction_type: TransactionType::Legacy as i32,
+            ..Default::default()
+        };
+        let private_key = PrivateKey::from_str(
+            "4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+        let output = Signer::sign(&private_key, &input, input.chain_id).unwrap();
+        assert_eq!(output.v, 37);
+        assert_eq!(
+            output.contract_address,
+            create_address("0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F", U256::from(2)).unwrap()
+        );
+        let decoded = crate::decoder::decode_transaction(&output.encoded).unwrap();
+        assert_eq!(decoded.sign_input.unwrap().to_address, "");
+        assert_eq!(decoded.from, "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+
+        // A transfer reports no contract address
+        input.to_address = "0x3535353535353535353535353535353535353535".to_owned();
+        let output = Signer::sign(&private_key, &input, input.chain_id).unwrap();
+        assert!(output.contract_address.is_empty());
+
+        // Deploying requires the init code
+        input.to_address = "".to_owned();
+        input.payload = vec![];
+        assert_eq!(
+            Signer::sign(&private_key, &input, input.chain_id)
+                .err()
+                .unwrap(),
+            Error::InvalidSignInput
+        );
+    }
+
     #[test]
     fn test_sign_eip1559() {
         let input = SignInput {
@@ -209,16 +419,113 @@ mod tests {
             amount: "0x1EE0C29F50CB1".to_owned(),
             payload: "".as_bytes().to_vec(),
             to_address: "0xB9F5771C27664bF2282D98E09D7F50cEc7cB01a7".to_owned(),
+            ..Default::default()
         };
         let private_key = PrivateKey::from_str(
             "4f96ed80e9a7555a6f74b3d658afdd9c756b0a40d4ca30c42c2039eb449bb904",
         )
         .unwrap();
-        let sign_result = Signer::sign(&private_key, &input).unwrap();
+        let sign_result = Signer::sign(&private_key, &input, input.chain_id).unwrap();
         assert_eq!(sign_result.v, 0);
         assert_eq!(hex::encode(sign_result.encoded), "02f8710306847735940084b2d05e0082526c94b9f5771c27664bf2282d98e09d7f50cec7cb01a78701ee0c29f50cb180c080a092c336138f7d0231fe9422bb30ee9ef10bf222761fe9e04442e3a11e88880c64a06487026011dae03dc281bc21c7d7ede5c2226d197befb813a4ecad686b559e58");
     }
 
+    fn access_list_input() -> SignInput {
+        SignInput {
+            chain_id: 1,
+            nonce: "0x0".to_owned(),
+            gas_limit: "0x62d4".to_owned(),
+            gas_price: "0x3b9aca00".to_owned(),
+            max_inclusion_fee_per_gas: "0x3b9aca00".to_owned(),
+            max_fee_per_gas: "0x77359400".to_owned(),
+            amount: "0x0".to_owned(),
+            payload: "".as_bytes().to_vec(),
+            to_address: "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae".to_owned(),
+            access_list: vec![AccessListItem {
+                address: "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae".to_owned(),
+                storage_keys: vec![
+                    "0x0000000000000000000000000000000000000000000000000000000000000003".to_owned(),
+                ],
+            }],
+            transaction_type: TransactionType::AccessList as i32,
+            ..Default::default()
+        }
+    }
+
+    // Recover the signer's public key from the signature over the transaction hash
+    fn recover(hash: &[u8], output: &SignOutput) -> secp256k1::PublicKey {
+        let mut signature = [0u8; 64];
+        signature[32 - output.r.len()..32].copy_from_slice(&output.r);
+        signature[64 - output.s.len()..].copy_from_slice(&output.s);
+        let recovery_id = secp256k1::ecdsa::RecoveryId::from_i32(output.v as i32).unwrap();
+        let signature =
+            secp256k1::ecdsa::RecoverableSignature::from_compact(&signature, recovery_id).unwrap();
+        Secp256k1::new()
+            .recover_ecdsa(&secp256k1::Message::from_slice(hash).unwrap(), &signature)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_sign_eip2930() {
+        let input = access_list_input();
+        let private_key = PrivateKey::from_str(
+            "4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+        let output = Signer::sign(&private_key, &input, input.chain_id).unwrap();
+        assert!(output.v <= 1);
+        assert_eq!(output.encoded[0], 0x01);
+        let rlp = rlp::Rlp::new(&output.encoded[1..]);
+        assert_eq!(rlp.item_count().unwrap(), 11);
+        assert_eq!(rlp.at(7).unwrap().item_count().unwrap(), 1);
+
+        let hash = Eip2930Transaction::try_from(&input)
+            .unwrap()
+            .hash(input.chain_id)
+            .unwrap();
+        let secret_key = secp256k1::SecretKey::from_slice(&private_key.data).unwrap();
+        assert_eq!(
+            recover(&hash, &output),
+            secp256k1::PublicKey::from_secret_key(&Secp256k1::new(), &secret_key)
+        );
+    }
+
+    #[test]
+    fn test_sign_eip1559_with_access_list() {
+        let mut input = access_list_input();
+        input.transaction_type = TransactionType::Eip1559 as i32;
+        let private_key = PrivateKey::from_str(
+            "4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+        let output = Signer::sign(&private_key, &input, input.chain_id).unwrap();
+        assert_eq!(output.encoded[0], 0x02);
+        let rlp = rlp::Rlp::new(&output.encoded[1..]);
+        assert_eq!(rlp.item_count().unwrap(), 12);
+        assert_eq!(rlp.at(8).unwrap().item_count().unwrap(), 1);
+
+        let hash = Eip1559Transaction::try_from(&input)
+            .unwrap()
+            .hash(input.chain_id)
+            .unwrap();
+        let secret_key = secp256k1::SecretKey::from_slice(&private_key.data).unwrap();
+        assert_eq!(
+            recover(&hash, &output),
+            secp256k1::PublicKey::from_secret_key(&Secp256k1::new(), &secret_key)
+        );
+
+        input.access_list[0].storage_keys[0] = "0x03".to_owned();
+        assert_eq!(
+            Signer::sign(&private_key, &input, input.chain_id).unwrap_err(),
+            Error::InvalidSignInput
+        );
+        input.transaction_type = 5;
+        assert_eq!(
+            Signer::sign(&private_key, &input, input.chain_id).unwrap_err(),
+            Error::InvalidSignInput
+        );
+    }
+
     #[test]
     fn test_approve() {
         let payload_bytes = hex::decode("5db05aba00000000000000000000000074636cc217dd1ee6151943a829459b59b419b6da000000000000000000000000000000000000000000000000000000000000000100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000015180b4d2dd61b7c3f040b39730ed791833447cf2584e5c0a462dc797fd12d1bfb35d000000000000000000000000000000000000000000000000000000000000014000000000000000000000000000000000000000000000000000000000000001800000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000038d7ea4c68000000000000000000000000000000000000000000000000000000000000000000c4265737420576973686573210000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000d6879647261303936393237313900000000000000000000000000000000000000").unwrap();
@@ -232,12 +539,13 @@ mod tests {
             amount: "0x38d7ea4c68000".to_owned(),
             payload: payload_bytes.to_vec(),
             to_address: "0xabbe1101fd8fa5847c452a6d70c8655532b03c33".to_owned(),
+            ..Default::default()
         };
         let private_key = PrivateKey::from_str(
             "76b01c1392fb4d2a0e2103162cf4fd1636c838ebcb3adfeceb82dd552253e287",
         )
         .unwrap();
-        let output = Signer::sign(&private_key, &input).unwrap();
+        let output = Signer::sign(&private_key, &input, input.chain_id).unwrap();
         assert_eq!(output.v, 37);
     }
 
@@ -248,4 +556,229 @@ mod tests {
         v += 35 + aurora_chain_id * 2;
         assert_eq!(v, 2626323143);
     }
+
+    #[test]
+    fn test_sign_typed_data() {
+        let typed_data = r#"{
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" }
+                ],
+                "Person": [
+                    { "name": "name", "type": "string" },
+                    { "name": "wallets", "type": "address[]" }
+                ],
+                "Mail": [
+                    { "name": "from", "type": "Person" },
+                    { "name": "to", "type": "Person[]" },
+                    { "name": "contents", "type": "string" }
+                ],
+                "Group": [
+                    { "name": "name", "type": "string" },
+                    { "name": "members", "type": "Person[]" }
+                ]
+            },
+            "domain": {
+                "name": "Ether Mail",
+                "version": "1",
+                "chainId": 1,
+                "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+            },
+            "primaryType": "Mail",
+            "message": {
+                "from": {
+                    "name": "Cow",
+                    "wallets": [
+                        "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826",
+                        "0xDeaDbeefdEAdbeefdEadbEEFdeadbeEFdEaDbeeF"
+                    ]
+                },
+                "to": [
+                    {
+                        "name": "Bob",
+                        "wallets": [
+                            "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB",
+                            "0xB0BdaBea57B0BDABeA57b0bdABEA57b0BDabEa57",
+                            "0xB0B0b0b0b0b0B000000000000000000000000000"
+                        ]
+                    }
+                ],
+                "contents": "Hello, Bob!"
+            }
+        }"#;
+        // keccak256("cow")
+        let private_key = PrivateKey::from_str(
+            "c85ef7d79691fe79573b1a7064c19c1a9819ebdbd1faaab1a8ec92344438aaf4",
+        )
+        .unwrap();
+        let signature =
+            Signer::sign_typed_data(&private_key, typed_data, TypedDataVersion::V4).unwrap();
+        assert_eq!(hex::encode(signature), "65cbd956f2fae28a601bebc9b906cea0191744bd4c4247bcd27cd08f8eb6b71c78efdf7a31dc9abee78f492292721f362d296cf86b4538e07b51303b67f749061b");
+        assert_eq!(
+            Signer::sign_typed_data(&private_key, typed_data, TypedDataVersion::V3).unwrap_err(),
+            Error::InvalidSignInput
+        );
+    }
+
+    #[test]
+    fn test_sign_user_operation() {
+        let private_key = PrivateKey::from_str(
+            "4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+        let user_operation = UserOperation {
+            version: EntryPointVersion::V07 as i32,
+            entry_point: "0x0000000071727De22E5E9d8BAf0edAc6f37da032".to_owned(),
+            chain_id: 1,
+            sender: "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F".to_owned(),
+            nonce: "0x1".to_owned(),
+            init_code: vec![],
+            call_data: hex::decode("b61d27f6").unwrap(),
+            call_gas_limit: "0x5208".to_owned(),
+            verification_gas_limit: "0x186a0".to_owned(),
+            pre_verification_gas: "0xc350".to_owned(),
+            max_fee_per_gas: "0x4a817c800".to_owned(),
+            max_priority_fee_per_gas: "0x3b9aca00".to_owned(),
+            paymaster_and_data: vec![],
+            paymaster: "".to_owned(),
+            paymaster_verification_gas_limit: "".to_owned(),
+            paymaster_post_op_gas_limit: "".to_owned(),
+            paymaster_data: vec![],
+        };
+        let output = Signer::sign_user_operation(&private_key, &user_operation).unwrap();
+        assert_eq!(
+            hex::encode(&output.user_op_hash),
+            "fddb3a7483fb34a637588f0150108b2fe894f7de2736114c599e4061b2fee0a0"
+        );
+        assert!(Signer::verify_message(
+            &output.user_op_hash,
+            &output.signature,
+            "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+        ));
+    }
+
+    #[test]
+    fn test_sign_authorization() {
+        let private_key = PrivateKey::from_str(
+            "4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+        let authorization = Authorization {
+            chain_id: 1,
+            address: "0x63c0c19a282a1B52b07dD5a65b58948A07DAE32B".to_owned(),
+            nonce: "0x0".to_owned(),
+            y_parity: 0,
+            r: vec![],
+            s: vec![],
+        };
+        let signed = Signer::sign_authorization(&private_key, &authorization).unwrap();
+        assert_eq!(signed.address, authorization.address);
+        assert!(signed.y_parity <= 1);
+        let recovered = recover_from_hash(
+            &authorization_hash(&authorization).unwrap(),
+            &signed.r,
+            &signed.s,
+            signed.y_parity as u64,
+        )
+        .unwrap();
+        assert_eq!(
+            recovered.address,
+            "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+        );
+    }
+
+    #[test]
+    fn test_sign_permit() {
+        let private_key = PrivateKey::from_str(
+            "4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+        let input = PermitInput {
+            kind: PermitKind::Eip2612 as i32,
+            chain_id: 1,
+            token_name: "USD Coin".to_owned(),
+            token_version: "2".to_owned(),
+            owner: "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F".to_owned(),
+            spender: "0x3fC91A3afd70395Cd496C647d5a6CC9D4B2b7FAD".to_owned(),
+            tokens: vec![PermitToken {
+                token: "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".to_owned(),
+                amount: "0xf4240".to_owned(),
+                expiration: "".to_owned(),
+                nonce: "".to_owned(),
+            }],
+            nonce: "0x5".to_owned(),
+            deadline: "0x6553f100".to_owned(),
+        };
+        let output = Signer::sign_permit(&private_key, &input).unwrap();
+        let recovered = recover_from_signature(&output.digest, &output.signature).unwrap();
+        assert_eq!(
+            recovered.address,
+            "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F"
+        );
+        // The echoed typed data is what was signed
+        assert_eq!(
+            Signer::sign_typed_data(&private_key, &output.typed_data, TypedDataVersion::V4)
+                .unwrap(),
+            output.signature
+        );
+    }
+
+    #[test]
+    fn test_sign_message() {
+        let private_key = PrivateKey::from_str(
+            "4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+        let address = "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F";
+        let signature = Signer::sign_message(&private_key, b"Hello World").unwrap();
+        assert_eq!(signature.len(), 65);
+        assert!(signature[64] == 27 || signature[64] == 28);
+        assert!(Signer::verify_message(b"Hello World", &signature, address));
+        assert!(Signer::verify_message(
+            b"Hello World",
+            &signature,
+            &address.to_lowercase()
+        ));
+        assert!(!Signer::verify_message(
+            b"Hello World!",
+            &signature,
+            address
+        ));
+        assert!(!Signer::verify_message(
+            b"Hello World",
+            &signature,
+            "0x3535353535353535353535353535353535353535"
+        ));
+        assert!(!Signer::verify_message(
+            b"Hello World",
+            &signature[..64],
+            address
+        ));
+    }
+
+    #[test]
+    fn test_verify_personal_sign() {
+        let private_key = PrivateKey::from_str(
+            "4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318",
+        )
+        .unwrap();
+        let signature = Signer::sign_message(&private_key, b"Some data").unwrap();
+        assert_eq!(hex::encode(&signature), "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c");
+        assert!(Signer::verify_message(
+            b"Some data",
+            &signature,
+            "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23"
+        ));
+        // The raw recovery id is accepted as well
+        let mut signature = signature;
+        signature[64] -= 27;
+        assert!(Signer::verify_message(
+            b"Some data",
+            &signature,
+            "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23"
+        ));
+    }
 }
@@ -0,0 +1,413 @@
+use super::address::EthereumAddress;
+use super::address_checksum::{checksum, ChecksumType};
+use chain_common::ethereum::{SiweMessage, SiweStatus};
+use chain_common::Error;
+
+const HEADER_SUFFIX: &str = " wants you to sign in with your Ethereum account:";
+
+/// Build the EIP-4361 message text from its fields, the address is EIP-55 checksummed
+pub fn build_message(message: &SiweMessage) -> Result<String, Error> {
+    let mut message = message.clone();
+    if message.version.is_empty() {
+        message.version = "1".to_owned();
+    }
+    if !EthereumAddress::is_valid(&message.address) {
+        return Err(Error::InvalidSignInput);
+    }
+    message.address = checksummed(&message.address)?;
+    check_fields(&message)?;
+
+    let mut text = match message.scheme.is_empty() {
+        true => message.domain.to_owned(),
+        false => format!("{}://{}", message.scheme, message.domain),
+    };
+    text.push_str(HEADER_SUFFIX);
+    text.push_str(&format!("\n{}\n\n", message.address));
+    if !message.statement.is_empty() {
+        text.push_str(&format!("{}\n", message.statement));
+    }
+    text.push_str(&format!("\nURI: {}", message.uri));
+    text.push_str(&format!("\nVersion: {}", message.version));
+    text.push_str(&format!("\nChain ID: {}", message.chain_id));
+    text.push_str(&format!("\nNonce: {}", message.nonce));
+    text.push_str(&format!("\nIssued At: {}", message.issued_at));
+    if !message.expiration_time.is_empty() {
+        text.push_str(&format!("\nExpiration Time: {}", message.expiration_time));
+    }
+    if !message.not_before.is_empty() {
+        text.push_str(&format!("\nNot Before: {}", message.not_before));
+    }
+    if !message.request_id.is_empty() {
+        text.push_str(&format!("\nRequest ID: {}", message.request_id));
+    }
+    if !message.resources.is_empty() {
+        text.push_str("\nResources:");
+        for resource in &message.resources {
+            text.push_str(&format!("\n- {}", resource));
+        }
+    }
+    Ok(text)
+}
+
+/// Parse an EIP-4361 message text into its fields
+///
+/// The address checksum is not enforced here, `validate_message` reports it
+pub fn parse_message(text: &str) -> Result<SiweMessage, Error> {
+    let mut lines = text.split('\n');
+    let mut next_line = || lines.next().ok_or(Error::InvalidSignInput);
+
+    let origin = next_line()?
+        .strip_suffix(HEADER_SUFFIX)
+        .ok_or(Error::InvalidSignInput)?;
+    let (scheme, domain) = match origin.split_once("://") {
+        Some((scheme, domain)) => (scheme, domain),
+        None => ("", origin),
+    };
+    let address = next_line()?;
+    if !EthereumAddress::is_valid(address) || !next_line()?.is_empty() {
+        return Err(Error::InvalidSignInput);
+    }
+    // The statement is followed by an empty line, without it there are two empty lines
+    let statement = match next_line()? {
+        "" => "",
+        statement => {
+            if !next_line()?.is_empty() {
+                return Err(Error::InvalidSignInput);
+            }
+            statement
+        }
+    };
+    let mut message = SiweMessage {
+        scheme: scheme.to_owned(),
+        domain: domain.to_owned(),
+        address: address.to_owned(),
+        statement: statement.to_owned(),
+        uri: tagged(next_line()?, "URI: ")?,
+        version: tagged(next_line()?, "Version: ")?,
+        chain_id: tagged(next_line()?, "Chain ID: ")?
+            .parse()
+            .map_err(|_| Error::InvalidSignInput)?,
+        nonce: tagged(next_line()?, "Nonce: ")?,
+        issued_at: tagged(next_line()?, "Issued At: ")?,
+        expiration_time: "".to_owned(),
+        not_before: "".to_owned(),
+        request_id: "".to_owned(),
+        resources: vec![],
+    };
+
+    let mut line = lines.next();
+    {
+        let mut optional_fields = [
+            ("Expiration Time: ", &mut message.expiration_time),
+            ("Not Before: ", &mut message.not_before),
+            ("Request ID: ", &mut message.request_id),
+        ]
+        .into_iter();
+        while let Some(current) = line {
+            if current == "Resources:" {
+                break;
+            }
+            // The optional fields keep their order, each appears at most once
+            let (tag, field) = optional_fields
+                .find(|(tag, _)| current.starts_with(tag))
+                .ok_or(Error::InvalidSignInput)?;
+            *field = tagged(current, tag)?;
+            line = lines.next();
+        }
+    }
+    if line.is_some() {
+        for resource in lines {
+            let resource = resource.strip_prefix("- ").ok_or(Error::InvalidSignInput)?;
+            message.resources.push(resource.to_owned());
+        }
+    }
+    check_fields(&message)?;
+    Ok(message)
+}
+
+/// Check the message against the relying party's `domain`, `chain_id` and `nonce` and the
+/// caller's clock, `now` in Unix seconds
+///
+/// An empty `nonce` or a zero `chain_id` are not checked
+pub fn validate_message(
+    message: &SiweMessage,
+    domain: &str,
+    chain_id: u64,
+    nonce: &str,
+    now: i64,
+) -> Result<SiweStatus, Error> {
+    let issued_at = parse_timestamp(&message.issued_at)?;
+    let status = if checksummed(&message.address)? != message.address {
+        SiweStatus::InvalidAddress
+    } else if message.domain != domain {
+        SiweStatus::DomainMismatch
+    } else if chain_id != 0 && message.chain_id != chain_id {
+        SiweStatus::ChainIdMismatch
+    } else if !nonce.is_empty() && message.nonce != nonce {
+        SiweStatus::NonceMismatch
+    } else if issued_at > now {
+        SiweStatus::IssuedInFuture
+    } else if !message.expiration_time.is_empty()
+        && parse_timestamp(&message.expiration_time)? <= now
+    {
+        SiweStatus::Expired
+    } else if !message.not_before.is_empty() && parse_timestamp(&message.not_before)? > now {
+        SiweStatus::NotYetValid
+    } else {
+        SiweStatus::Valid
+    };
+    Ok(status)
+}
+
+// `line` without its `tag` prefix
+fn tagged(line: &str, tag: &str) -> Result<String, Error> {
+    line.strip_prefix(tag)
+        .map(str::to_owned)
+        .ok_or(Error::InvalidSignInput)
+}
+
+fn checksummed(address: &str) -> Result<String, Error> {
+    let data = address
+        .strip_prefix("0x")
+        .and_then(|address| hex::decode(address).ok())
+        .ok_or(Error::InvalidSignInput)?;
+    Ok(checksum(
+        &EthereumAddress {
+            coin_id: "ethereum".to_owned(),
+            data,
+        },
+        ChecksumType::Eip55,
+    ))
+}
+
+fn check_fields(message: &SiweMessage) -> Result<(), Error> {
+    let single_line = |value: &str| !value.contains('\n');
+    let valid = !message.domain.is_empty()
+        && !message.domain.contains(char::is_whitespace)
+        && message
+            .scheme
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric() || char == '+' || char == '-' || char == '.')
+        && single_line(&message.statement)
+        && !message.uri.is_empty()
+        && !message.uri.contains(char::is_whitespace)
+        && message.version == "1"
+        && message.nonce.len() >= 8
+        && message
+            .nonce
+            .chars()
+            .all(|char| char.is_ascii_alphanumeric())
+        && single_line(&message.request_id)
+        && message
+            .resources
+            .iter()
+            .all(|resource| !resource.is_empty() && !resource.contains(char::is_whitespace));
+    if !valid {
+        return Err(Error::InvalidSignInput);
+    }
+    parse_timestamp(&message.issued_at)?;
+    for timestamp in [&message.expiration_time, &message.not_before] {
+        if !timestamp.is_empty() {
+            parse_timestamp(timestamp)?;
+        }
+    }
+    Ok(())
+}
+
+// Unix seconds of an RFC 3339 date-time such as `2021-09-30T16:25:24.000+02:00`
+fn parse_timestamp(value: &str) -> Result<i64, Error> {
+    let bytes = value.as_bytes();
+    let number = |range: std::ops::Range<usize>| -> Result<i64, Error> {
+        let digits = value.get(range).ok_or(Error::InvalidSignInput)?;
+        match digits.chars().all(|char| char.is_ascii_digit()) {
+            true => digits.parse().map_err(|_| Error::InvalidSignInput),
+            false => Err(Error::InvalidSignInput),
+        }
+    };
+    let separators = [(4, b'-'), (7, b'-'), (13, b':'), (16, b':')];
+    if bytes.len() < 20
+        || separators
+            .iter()
+            .any(|(index, char)| bytes[*index] != *char)
+        || !bytes[10].eq_ignore_ascii_case(&b'T')
+    {
+        return Err(Error::InvalidSignInput);
+    }
+    let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+    let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+    let is_leap_year = year % 4 == 0 && (year % 100 != 0 || year % 400 == 0);
+    let days_in_month = match month {
+        2 if is_leap_year => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    };
+    if !(1..=12).contains(&month)
+        || !(1..=days_in_month).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 60
+    {
+        return Err(Error::InvalidSignInput);
+    }
+
+    // Fractional seconds are ignored
+    let mut index = 19;
+    if bytes[index] == b'.' {
+        index += 1;
+        let start = index;
+        while index < bytes.len() && bytes[index].is_ascii_digit() {
+            index += 1;
+        }
+        if index == start {
+            return Err(Error::InvalidSignInput);
+        }
+    }
+    let offset = match &value[index..] {
+        "Z" | "z" => 0,
+        zone if zone.len() == 6 && (zone.starts_with('+') || zone.starts_with('-')) => {
+            let (hours, minutes) = (number(index + 1..index + 3)?, number(index + 4..index + 6)?);
+            if bytes[index + 3] != b':' || hours > 23 || minutes > 59 {
+                return Err(Error::InvalidSignInput);
+            }
+            let offset = hours * 3600 + minutes * 60;
+            match zone.starts_with('-') {
+                true => -offset,
+                false => offset,
+            }
+        }
+        _ => return Err(Error::InvalidSignInput),
+    };
+    let days = days_from_civil(year, month, day);
+    Ok(days * 86400 + hour * 3600 + minute * 60 + second - offset)
+}
+
+// Days since 1970-01-01 of a proleptic Gregorian date
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Example from EIP-4361
+    const MESSAGE: &str = "service.org wants you to sign in with your Ethereum account:
+0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2
+
+I accept the ServiceOrg Terms of Service: https://service.org/tos
+
+URI: https://service.org/login
+Version: 1
+Chain ID: 1
+Nonce: 32891756
+Issued At: 2021-09-30T16:25:24Z
+Resources:
+- ipfs://bafybeiemxf5abjwjbikoz4mc3a3dla6ual3jsgpdr4cjr3oz3evfyavhwq/
+- https://example.com/my-web2-claim.json";
+
+    const ISSUED_AT: i64 = 1633019124;
+
+    #[test]
+    fn test_parse_and_build() {
+        let message = parse_message(MESSAGE).unwrap();
+        assert_eq!(message.domain, "service.org");
+        assert_eq!(
+            message.address,
+            "0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2"
+        );
+        assert_eq!(
+            message.statement,
+            "I accept the ServiceOrg Terms of Service: https://service.org/tos"
+        );
+        assert_eq!(message.uri, "https://service.org/login");
+        assert_eq!(message.chain_id, 1);
+        assert_eq!(message.nonce, "32891756");
+        assert_eq!(message.resources.len(), 2);
+        assert_eq!(build_message(&message).unwrap(), MESSAGE);
+
+        // Optional fields and no statement
+        let mut message = message;
+        message.scheme = "https".to_owned();
+        message.statement = "".to_owned();
+        message.address = message.address.to_lowercase();
+        message.expiration_time = "2021-10-01T00:00:00.000+02:00".to_owned();
+        message.request_id = "some-id".to_owned();
+        message.resources = vec![];
+        let text = build_message(&message).unwrap();
+        assert!(text.starts_with(
+            "https://service.org wants you to sign in with your Ethereum account:\n\
+             0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2\n\n\nURI: "
+        ));
+        assert!(
+            text.ends_with("Expiration Time: 2021-10-01T00:00:00.000+02:00\nRequest ID: some-id")
+        );
+        let parsed = parse_message(&text).unwrap();
+        assert_eq!(parsed.scheme, "https");
+        assert_eq!(parsed.expiration_time, message.expiration_time);
+        assert_eq!(build_message(&parsed).unwrap(), text);
+    }
+
+    #[test]
+    fn test_parse_invalid() {
+        assert!(parse_message(&MESSAGE.replace("Nonce: 32891756", "Nonce: 1234")).is_err());
+        assert!(parse_message(&MESSAGE.replace("Version: 1", "Version: 2")).is_err());
+        assert!(parse_message(&MESSAGE.replace("Chain ID: 1\n", "")).is_err());
+        assert!(parse_message(&MESSAGE.replace("16:25:24Z", "16:25:24")).is_err());
+        assert!(parse_message(&MESSAGE.replace("2021-09-30", "2021-02-30")).is_err());
+        assert!(parse_message(&format!(
+            "{}\nextra",
+            MESSAGE.replace("\nResources:", "\nfoo")
+        ))
+        .is_err());
+        let reordered = MESSAGE.replace(
+            "\nResources:",
+            "\nRequest ID: 1\nExpiration Time: 2021-10-01T00:00:00Z\nResources:",
+        );
+        assert!(parse_message(&reordered).is_err());
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut message = parse_message(MESSAGE).unwrap();
+        let validate = |message: &SiweMessage, now| {
+            validate_message(message, "service.org", 1, "32891756", now).unwrap()
+        };
+        assert_eq!(validate(&message, ISSUED_AT), SiweStatus::Valid);
+        assert_eq!(
+            validate(&message, ISSUED_AT - 1),
+            SiweStatus::IssuedInFuture
+        );
+        assert_eq!(
+            validate_message(&message, "evil.org", 1, "32891756", ISSUED_AT).unwrap(),
+            SiweStatus::DomainMismatch
+        );
+        assert_eq!(
+            validate_message(&message, "service.org", 5, "", ISSUED_AT).unwrap(),
+            SiweStatus::ChainIdMismatch
+        );
+        assert_eq!(
+            validate_message(&message, "service.org", 0, "12345678", ISSUED_AT).unwrap(),
+            SiweStatus::NonceMismatch
+        );
+
+        // 17:25:24 UTC, one hour after the issued time
+        message.expiration_time = "2021-09-30T18:25:24+01:00".to_owned();
+        assert_eq!(validate(&message, ISSUED_AT + 3599), SiweStatus::Valid);
+        assert_eq!(validate(&message, ISSUED_AT + 3600), SiweStatus::Expired);
+        message.not_before = "2021-09-30T16:30:24.5Z".to_owned();
+        assert_eq!(validate(&message, ISSUED_AT + 299), SiweStatus::NotYetValid);
+        assert_eq!(validate(&message, ISSUED_AT + 300), SiweStatus::Valid);
+
+        message.address = message.address.to_lowercase();
+        assert_eq!(
+            validate(&message, ISSUED_AT + 300),
+            SiweStatus::InvalidAddress
+        );
+    }
+}
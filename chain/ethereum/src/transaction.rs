@@ -1,9 +1,11 @@
 use super::eip2930::AccessList;
-use chain_common::ethereum::SignInput;
+use super::eip4844::BlobSidecar;
+use super::eip7702::AuthorizationList;
+use chain_common::ethereum::{SignInput, TransactionType};
 use chain_common::Error;
 use crypto::hash::{Hasher, Keccak256};
 use crypto::Error as CryptoError;
-use ethereum_types::{H160, U256};
+use ethereum_types::{H160, H256, U256};
 use rlp::RlpStream;
 use std::convert::TryFrom;
 use std::str::FromStr;
@@ -12,6 +14,39 @@ pub trait Transaction {
     fn uses_replay_protection(&self) -> bool;
     fn hash(&self, chain_id: u64) -> Result<Vec<u8>, CryptoError>;
     fn encode_transaction(&self, v: u64, r: &[u8], s: &[u8], chain_id: u64) -> Vec<u8>;
+
+    /// Hash identifying the signed transaction, keccak256 of the encoded transaction
+    fn transaction_hash(
+        &self,
+        encoded: &[u8],
+        _v: u64,
+        _r: &[u8],
+        _s: &[u8],
+    ) -> Result<Vec<u8>, CryptoError> {
+        Hasher::hash(Keccak256, encoded)
+    }
+
+    /// The form broadcast to the network, which is the encoded transaction unless it carries
+    /// data outside of it
+    fn network_encoding(&self, encoded: Vec<u8>) -> Vec<u8> {
+        encoded
+    }
+}
+
+/// The type of `input`, an unspecified one is EIP-1559 if the gas price is zero and legacy otherwise
+pub fn transaction_type(input: &SignInput) -> Result<TransactionType, Error> {
+    match TransactionType::from_i32(input.transaction_type) {
+        Some(TransactionType::Unspecified) => {
+            let gas_price =
+                U256::from_str_radix(&input.gas_price, 16).map_err(|_| Error::InvalidSignInput)?;
+            match gas_price.is_zero() {
+                true => Ok(TransactionType::Eip1559),
+                false => Ok(TransactionType::Legacy),
+            }
+        }
+        Some(r#type) => Ok(r#type),
+        None => Err(Error::InvalidSignInput),
+    }
 }
 
 pub struct TransactionBase {
@@ -35,15 +70,56 @@ pub struct LegacyTransaction {
     pub gas_price: U256,
 }
 
+pub struct Eip2930Transaction {
+    pub base: TransactionBase,
+
+    /// The transaction gas price in wei
+    pub gas_price: U256,
+
+    pub access_list: AccessList,
+}
+
 pub struct Eip1559Transaction {
     pub base: TransactionBase,
 
     pub max_inclusion_fee_per_gas: U256,
 
     pub max_fee_per_gas: U256,
+
+    pub access_list: AccessList,
 }
 
-impl TryFrom<&SignInput> for LegacyTransaction {
+pub struct Eip4844Transaction {
+    pub base: TransactionBase,
+
+    pub max_inclusion_fee_per_gas: U256,
+
+    pub max_fee_per_gas: U256,
+
+    pub access_list: AccessList,
+
+    pub max_fee_per_blob_gas: U256,
+
+    pub blob_versioned_hashes: Vec<H256>,
+
+    /// The blobs, only known when signing or decoding the network wrapper form
+    pub sidecar: Option<BlobSidecar>,
+}
+
+pub struct Eip7702Transaction {
+    pub base: TransactionBase,
+
+    pub max_inclusion_fee_per_gas: U256,
+
+    pub max_fee_per_gas: U256,
+
+    pub access_list: AccessList,
+
+    /// Signed delegations of accounts to contract code
+    pub authorization_list: AuthorizationList,
+}
+
+impl TryFrom<&SignInput> for TransactionBase {
     type Error = Error;
     fn try_from(input: &SignInput) -> Result<Self, Self::Error> {
         // Trim the '0x' prefix
@@ -55,64 +131,161 @@ impl TryFrom<&SignInput> for LegacyTransaction {
             U256::from_str_radix(&input.amount, 16).map_err(|_| Error::InvalidSignInput)?;
         let gas_limit =
             U256::from_str_radix(&input.gas_limit, 16).map_err(|_| Error::InvalidSignInput)?;
-        let gas_price =
-            U256::from_str_radix(&input.gas_price, 16).map_err(|_| Error::InvalidSignInput)?;
         let nonce = U256::from_str_radix(&input.nonce, 16).map_err(|_| Error::InvalidSignInput)?;
         let receiver: Option<H160> = match trimmed_address.is_empty() {
             true => None,
             false => Some(H160::from_str(&trimmed_address).map_err(|_| Error::InvalidSignInput)?),
         };
 
-        let base = TransactionBase {
+        Ok(TransactionBase {
             nonce,
             payload: input.payload.to_vec(),
             gas_limit,
             receiver,
             amount,
-        };
+        })
+    }
+}
+
+impl TryFrom<&SignInput> for LegacyTransaction {
+    type Error = Error;
+    fn try_from(input: &SignInput) -> Result<Self, Self::Error> {
+        let base = TransactionBase::try_from(input)?;
+        let gas_price =
+            U256::from_str_radix(&input.gas_price, 16).map_err(|_| Error::InvalidSignInput)?;
 
         Ok(LegacyTransaction { base, gas_price })
     }
 }
 
+impl TryFrom<&SignInput> for Eip2930Transaction {
+    type Error = Error;
+    fn try_from(input: &SignInput) -> Result<Self, Self::Error> {
+        let base = TransactionBase::try_from(input)?;
+        let gas_price =
+            U256::from_str_radix(&input.gas_price, 16).map_err(|_| Error::InvalidSignInput)?;
+        let access_list = AccessList::try_from(input.access_list.as_slice())?;
+
+        Ok(Eip2930Transaction {
+            base,
+            gas_price,
+            access_list,
+        })
+    }
+}
+
 impl TryFrom<&SignInput> for Eip1559Transaction {
     type Error = Error;
     fn try_from(input: &SignInput) -> Result<Self, Self::Error> {
-        // Trim the '0x' prefix
-        let trimmed_address = match input.to_address.starts_with("0x") {
-            true => input.to_address[2..].to_string(),
-            false => input.to_address.to_string(),
-        };
-        let amount =
-            U256::from_str_radix(&input.amount, 16).map_err(|_| Error::InvalidSignInput)?;
-        let gas_limit =
-            U256::from_str_radix(&input.gas_limit, 16).map_err(|_| Error::InvalidSignInput)?;
+        let base = TransactionBase::try_from(input)?;
         let max_fee_per_gas = U256::from_str_radix(&input.max_fee_per_gas, 16)
             .map_err(|_| Error::InvalidSignInput)?;
         let max_inclusion_fee_per_gas = U256::from_str_radix(&input.max_inclusion_fee_per_gas, 16)
             .map_err(|_| Error::InvalidSignInput)?;
-        let nonce = U256::from_str_radix(&input.nonce, 16).map_err(|_| Error::InvalidSignInput)?;
-        let receiver: Option<H160> = match trimmed_address.is_empty() {
-            true => None,
-            false => Some(H160::from_str(&trimmed_address).map_err(|_| Error::InvalidSignInput)?),
-        };
+        let access_list = AccessList::try_from(input.access_list.as_slice())?;
 
-        let base = TransactionBase {
-            nonce,
-            payload: input.payload.to_vec(),
-            gas_limit,
-            receiver,
-            amount,
+        Ok(Eip1559Transaction {
+            base,
+            max_inclusion_fee_per_gas,
+            max_fee_per_gas,
+            access_list,
+        })
+    }
+}
+
+impl TryFrom<&SignInput> for Eip4844Transaction {
+    type Error = Error;
+    fn try_from(input: &SignInput) -> Result<Self, Self::Error> {
+        let Eip1559Transaction {
+            base,
+            max_inclusion_fee_per_gas,
+            max_fee_per_gas,
+            access_list,
+        } = Eip1559Transaction::try_from(input)?;
+        // Blob transactions can't create contracts
+        if base.receiver.is_none() {
+            return Err(Error::InvalidSignInput);
+        }
+        let max_fee_per_blob_gas = U256::from_str_radix(&input.max_fee_per_blob_gas, 16)
+            .map_err(|_| Error::InvalidSignInput)?;
+        let sidecar = match input.blob_sidecar {
+            Some(ref sidecar) => Some(BlobSidecar::try_from(sidecar)?),
+            None => None,
+        };
+        let blob_versioned_hashes = match (&sidecar, input.blob_versioned_hashes.is_empty()) {
+            (Some(sidecar), true) => sidecar.versioned_hashes()?,
+            (None, true) => return Err(Error::InvalidSignInput),
+            (_, false) => input
+                .blob_versioned_hashes
+                .iter()
+                .map(|hash| match hash.len() {
+                    32 => Ok(H256::from_slice(hash)),
+                    _ => Err(Error::InvalidSignInput),
+                })
+                .collect::<Result<Vec<H256>, Error>>()?,
         };
+        // The given hashes must be the ones of the blobs
+        if let Some(ref sidecar) = sidecar {
+            if sidecar.versioned_hashes()? != blob_versioned_hashes {
+                return Err(Error::InvalidSignInput);
+            }
+        }
 
-        Ok(Eip1559Transaction {
+        Ok(Eip4844Transaction {
+            base,
+            max_inclusion_fee_per_gas,
+            max_fee_per_gas,
+            access_list,
+            max_fee_per_blob_gas,
+            blob_versioned_hashes,
+            sidecar,
+        })
+    }
+}
+
+impl TryFrom<&SignInput> for Eip7702Transaction {
+    type Error = Error;
+    fn try_from(input: &SignInput) -> Result<Self, Self::Error> {
+        let Eip1559Transaction {
             base,
             max_inclusion_fee_per_gas,
             max_fee_per_gas,
+            access_list,
+        } = Eip1559Transaction::try_from(input)?;
+        // Set code transactions can't create contracts and must carry authorizations
+        if base.receiver.is_none() || input.authorization_list.is_empty() {
+            return Err(Error::InvalidSignInput);
+        }
+        let authorization_list = AuthorizationList::try_from(input.authorization_list.as_slice())?;
+
+        Ok(Eip7702Transaction {
+            base,
+            max_inclusion_fee_per_gas,
+            max_fee_per_gas,
+            access_list,
+            authorization_list,
         })
     }
 }
 
+impl LegacyTransaction {
+    /// Hash signed before EIP-155, of the 6 fields without the chain id
+    pub fn pre_eip155_hash(&self) -> Result<Vec<u8>, CryptoError> {
+        let mut rlp_stream = RlpStream::new_list(6);
+        rlp_stream.append(&self.base.nonce);
+        rlp_stream.append(&self.gas_price);
+        rlp_stream.append(&self.base.gas_limit);
+        if let Some(ref t) = self.base.receiver {
+            rlp_stream.append(t);
+        } else {
+            rlp_stream.append(&vec![]);
+        }
+        rlp_stream.append(&self.base.amount);
+        rlp_stream.append(&self.base.payload);
+        Hasher::hash(Keccak256, &rlp_stream.out())
+    }
+}
+
 impl Transaction for LegacyTransaction {
     fn uses_replay_protection(&self) -> bool {
         true
@@ -156,6 +329,59 @@ impl Transaction for LegacyTransaction {
     }
 }
 
+impl Transaction for Eip2930Transaction {
+    fn uses_replay_protection(&self) -> bool {
+        false
+    }
+
+    fn hash(&self, chain_id: u64) -> Result<Vec<u8>, CryptoError> {
+        let mut rlp_stream = RlpStream::new_list(8);
+        rlp_stream.append(&chain_id);
+        rlp_stream.append(&self.base.nonce);
+        rlp_stream.append(&self.gas_price);
+        rlp_stream.append(&self.base.gas_limit);
+        if let Some(ref t) = self.base.receiver {
+            rlp_stream.append(t);
+        } else {
+            rlp_stream.append(&vec![]);
+        }
+        rlp_stream.append(&self.base.amount);
+        rlp_stream.append(&self.base.payload);
+        rlp_stream.append(&self.access_list);
+        let rlp_encoded = rlp_stream.out();
+
+        let mut encoded = vec![];
+        encoded.extend_from_slice(&[0x1]);
+        encoded.extend_from_slice(&rlp_encoded);
+        Hasher::hash(Keccak256, &encoded)
+    }
+
+    fn encode_transaction(&self, v: u64, r: &[u8], s: &[u8], chain_id: u64) -> Vec<u8> {
+        let mut rlp_stream = RlpStream::new_list(11);
+        rlp_stream.append(&chain_id);
+        rlp_stream.append(&self.base.nonce);
+        rlp_stream.append(&self.gas_price);
+        rlp_stream.append(&self.base.gas_limit);
+        if let Some(ref t) = self.base.receiver {
+            rlp_stream.append(t);
+        } else {
+            rlp_stream.append(&vec![]);
+        }
+        rlp_stream.append(&self.base.amount);
+        rlp_stream.append(&self.base.payload);
+        rlp_stream.append(&self.access_list);
+        rlp_stream.append(&v);
+        rlp_stream.append(&r);
+        rlp_stream.append(&s);
+        let rlp_encoded = rlp_stream.out();
+
+        let mut encoded = vec![];
+        encoded.extend_from_slice(&[0x1]);
+        encoded.extend_from_slice(&rlp_encoded);
+        encoded
+    }
+}
+
 impl Transaction for Eip1559Transaction {
     fn uses_replay_protection(&self) -> bool {
         false
@@ -175,8 +401,7 @@ impl Transaction for Eip1559Transaction {
         }
         rlp_stream.append(&self.base.amount);
         rlp_stream.append(&self.base.payload);
-        let access_list = AccessList::from(vec![]);
-        rlp_stream.append(&access_list);
+        rlp_stream.append(&self.access_list);
         let rlp_encoded = rlp_stream.out();
 
         let mut encoded = vec![];
@@ -200,8 +425,7 @@ impl Transaction for Eip1559Transaction {
         }
         rlp_stream.append(&self.base.amount);
         rlp_stream.append(&self.base.payload);
-        let access_list = AccessList::from(vec![]);
-        rlp_stream.append(&access_list);
+        rlp_stream.append(&self.access_list);
         rlp_stream.append(&v);
         rlp_stream.append(&r);
         rlp_stream.append(&s);
@@ -214,3 +438,126 @@ impl Transaction for Eip1559Transaction {
         encoded.to_vec()
     }
 }
+
+impl Transaction for Eip4844Transaction {
+    fn uses_replay_protection(&self) -> bool {
+        false
+    }
+
+    fn hash(&self, chain_id: u64) -> Result<Vec<u8>, CryptoError> {
+        let mut rlp_stream = RlpStream::new_list(11);
+        rlp_stream.append(&chain_id);
+        rlp_stream.append(&self.base.nonce);
+        rlp_stream.append(&self.max_inclusion_fee_per_gas);
+        rlp_stream.append(&self.max_fee_per_gas);
+        rlp_stream.append(&self.base.gas_limit);
+        if let Some(ref t) = self.base.receiver {
+            rlp_stream.append(t);
+        } else {
+            rlp_stream.append(&vec![]);
+        }
+        rlp_stream.append(&self.base.amount);
+        rlp_stream.append(&self.base.payload);
+        rlp_stream.append(&self.access_list);
+        rlp_stream.append(&self.max_fee_per_blob_gas);
+        rlp_stream.append_list(&self.blob_versioned_hashes);
+        let rlp_encoded = rlp_stream.out();
+
+        let mut encoded = vec![];
+        encoded.extend_from_slice(&[0x3]);
+        encoded.extend_from_slice(&rlp_encoded);
+        Hasher::hash(Keccak256, &encoded)
+    }
+
+    fn encode_transaction(&self, v: u64, r: &[u8], s: &[u8], chain_id: u64) -> Vec<u8> {
+        let mut rlp_stream = RlpStream::new_list(14);
+        rlp_stream.append(&chain_id);
+        rlp_stream.append(&self.base.nonce);
+        rlp_stream.append(&self.max_inclusion_fee_per_gas);
+        rlp_stream.append(&self.max_fee_per_gas);
+        rlp_stream.append(&self.base.gas_limit);
+        if let Some(ref t) = self.base.receiver {
+            rlp_stream.append(t);
+        } else {
+            rlp_stream.append(&vec![]);
+        }
+        rlp_stream.append(&self.base.amount);
+        rlp_stream.append(&self.base.payload);
+        rlp_stream.append(&self.access_list);
+        rlp_stream.append(&self.max_fee_per_blob_gas);
+        rlp_stream.append_list(&self.blob_versioned_hashes);
+        rlp_stream.append(&v);
+        rlp_stream.append(&r);
+        rlp_stream.append(&s);
+        let rlp_encoded = rlp_stream.out();
+
+        let mut encoded = vec![];
+        encoded.extend_from_slice(&[0x3]);
+        encoded.extend_from_slice(&rlp_encoded);
+        encoded
+    }
+
+    fn network_encoding(&self, encoded: Vec<u8>) -> Vec<u8> {
+        match self.sidecar {
+            Some(ref sidecar) => sidecar.wrap(&encoded),
+            None => encoded,
+        }
+    }
+}
+
+impl Transaction for Eip7702Transaction {
+    fn uses_replay_protection(&self) -> bool {
+        false
+    }
+
+    fn hash(&self, chain_id: u64) -> Result<Vec<u8>, CryptoError> {
+        let mut rlp_stream = RlpStream::new_list(10);
+        rlp_stream.append(&chain_id);
+        rlp_stream.append(&self.base.nonce);
+        rlp_stream.append(&self.max_inclusion_fee_per_gas);
+        rlp_stream.append(&self.max_fee_per_gas);
+        rlp_stream.append(&self.base.gas_limit);
+        if let Some(ref t) = self.base.receiver {
+            rlp_stream.append(t);
+        } else {
+            rlp_stream.append(&vec![]);
+        }
+        rlp_stream.append(&self.base.amount);
+        rlp_stream.append(&self.base.payload);
+        rlp_stream.append(&self.access_list);
+        rlp_stream.append(&self.authorization_list);
+        let rlp_encoded = rlp_stream.out();
+
+        let mut encoded = vec![];
+        encoded.extend_from_slice(&[0x4]);
+        encoded.extend_from_slice(&rlp_encoded);
+        Hasher::hash(Keccak256, &encoded)
+    }
+
+    fn encode_transaction(&self, v: u64, r: &[u8], s: &[u8], chain_id: u64) -> Vec<u8> {
+        let mut rlp_stream = RlpStream::new_list(13);
+        rlp_stream.append(&chain_id);
+        rlp_stream.append(&self.base.nonce);
+        rlp_stream.append(&self.max_inclusion_fee_per_gas);
+        rlp_stream.append(&self.max_fee_per_gas);
+        rlp_stream.append(&self.base.gas_limit);
+        if let Some(ref t) = self.base.receiver {
+            rlp_stream.append(t);
+        } else {
+            rlp_stream.append(&vec![]);
+        }
+        rlp_stream.append(&self.base.amount);
+        rlp_stream.append(&self.base.payload);
+        rlp_stream.append(&self.access_list);
+        rlp_stream.append(&self.authorization_list);
+        rlp_stream.append(&v);
+        rlp_stream.append(&r);
+        rlp_stream.append(&s);
+        let rlp_encoded = rlp_stream.out();
+
+        let mut encoded = vec![];
+        encoded.extend_from_slice(&[0x4]);
+        encoded.extend_from_slice(&rlp_encoded);
+        encoded
+    }
+}
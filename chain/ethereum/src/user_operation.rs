@@ -0,0 +1,186 @@
+use super::address::EthereumAddress;
+use super::contract::create2_address;
+use super::eip712::{decode_hex, encode_uint, keccak256};
+use chain_common::ethereum::{EntryPointVersion, UserOperation};
+use chain_common::Error;
+use ethereum_types::U256;
+
+/// The userOpHash that the EntryPoint computes and the account validates the signature against:
+/// `keccak256(abi.encode(keccak256(pack(userOp)), entryPoint, chainId))`
+pub fn user_operation_hash(user_operation: &UserOperation) -> Result<Vec<u8>, Error> {
+    let packed = match EntryPointVersion::from_i32(user_operation.version) {
+        Some(EntryPointVersion::V06) => pack_v06(user_operation)?,
+        Some(EntryPointVersion::V07) => pack_v07(user_operation)?,
+        None => return Err(Error::InvalidSignInput),
+    };
+    let mut encoded = keccak256(&packed)?;
+    encoded.extend_from_slice(&address_word(&user_operation.entry_point)?);
+    encoded.extend_from_slice(&encode_uint(U256::from(user_operation.chain_id)));
+    keccak256(&encoded)
+}
+
+/// Address of a smart account before it's deployed, from the factory deploying it with CREATE2
+pub fn counterfactual_address(
+    factory: &str,
+    salt: &[u8],
+    init_code_hash: &[u8],
+) -> Result<String, Error> {
+    create2_address(factory, salt, init_code_hash)
+}
+
+// abi.encode of the UserOperation fields with the dynamic ones hashed
+fn pack_v06(user_operation: &UserOperation) -> Result<Vec<u8>, Error> {
+    let mut packed = common_fields(user_operation)?;
+    for quantity in [
+        &user_operation.call_gas_limit,
+        &user_operation.verification_gas_limit,
+        &user_operation.pre_verification_gas,
+        &user_operation.max_fee_per_gas,
+        &user_operation.max_priority_fee_per_gas,
+    ] {
+        packed.extend_from_slice(&encode_uint(quantity_value(quantity)?));
+    }
+    packed.extend_from_slice(&keccak256(&user_operation.paymaster_and_data)?);
+    Ok(packed)
+}
+
+// abi.encode of the PackedUserOperation fields, gas limits and fees are packed in pairs of uint128
+fn pack_v07(user_operation: &UserOperation) -> Result<Vec<u8>, Error> {
+    let mut packed = common_fields(user_operation)?;
+    packed.extend_from_slice(&pack_uint128_pair(
+        &user_operation.verification_gas_limit,
+        &user_operation.call_gas_limit,
+    )?);
+    packed.extend_from_slice(&encode_uint(quantity_value(
+        &user_operation.pre_verification_gas,
+    )?));
+    packed.extend_from_slice(&pack_uint128_pair(
+        &user_operation.max_priority_fee_per_gas,
+        &user_operation.max_fee_per_gas,
+    )?);
+
+    // paymaster ‖ uint128 verification gas limit ‖ uint128 post-op gas limit ‖ paymaster data
+    let mut paymaster_and_data = vec![];
+    if !user_operation.paymaster.is_empty() {
+        paymaster_and_data.extend_from_slice(&address_word(&user_operation.paymaster)?[12..]);
+        paymaster_and_data.extend_from_slice(&pack_uint128_pair(
+            &user_operation.paymaster_verification_gas_limit,
+            &user_operation.paymaster_post_op_gas_limit,
+        )?);
+        paymaster_and_data.extend_from_slice(&user_operation.paymaster_data);
+    }
+    packed.extend_from_slice(&keccak256(&paymaster_and_data)?);
+    Ok(packed)
+}
+
+// sender, nonce, keccak256(initCode) and keccak256(callData)
+fn common_fields(user_operation: &UserOperation) -> Result<Vec<u8>, Error> {
+    let mut encoded = address_word(&user_operation.sender)?;
+    encoded.extend_from_slice(&encode_uint(quantity_value(&user_operation.nonce)?));
+    encoded.extend_from_slice(&keccak256(&user_operation.init_code)?);
+    encoded.extend_from_slice(&keccak256(&user_operation.call_data)?);
+    Ok(encoded)
+}
+
+// `high << 128 | low`, both must fit in 128 bits
+fn pack_uint128_pair(high: &str, low: &str) -> Result<Vec<u8>, Error> {
+    let (high, low) = (quantity_value(high)?, quantity_value(low)?);
+    if high.bits() > 128 || low.bits() > 128 {
+        return Err(Error::InvalidSignInput);
+    }
+    Ok(encode_uint(high << 128 | low))
+}
+
+fn quantity_value(quantity: &str) -> Result<U256, Error> {
+    let quantity = quantity.strip_prefix("0x").unwrap_or(quantity);
+    U256::from_str_radix(quantity, 16).map_err(|_| Error::InvalidSignInput)
+}
+
+fn address_word(address: &str) -> Result<Vec<u8>, Error> {
+    if !EthereumAddress::is_valid(address) {
+        return Err(Error::InvalidSignInput);
+    }
+    Ok(encode_uint(U256::from_big_endian(&decode_hex(address)?)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user_operation(version: EntryPointVersion, entry_point: &str) -> UserOperation {
+        UserOperation {
+            version: version as i32,
+            entry_point: entry_point.to_owned(),
+            chain_id: 1,
+            sender: "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F".to_owned(),
+            nonce: "0x1".to_owned(),
+            init_code: vec![],
+            call_data: hex::decode("b61d27f6").unwrap(),
+            call_gas_limit: "0x5208".to_owned(),
+            verification_gas_limit: "0x186a0".to_owned(),
+            pre_verification_gas: "0xc350".to_owned(),
+            max_fee_per_gas: "0x4a817c800".to_owned(),
+            max_priority_fee_per_gas: "0x3b9aca00".to_owned(),
+            paymaster_and_data: vec![],
+            paymaster: "".to_owned(),
+            paymaster_verification_gas_limit: "".to_owned(),
+            paymaster_post_op_gas_limit: "".to_owned(),
+            paymaster_data: vec![],
+        }
+    }
+
+    #[test]
+    fn test_user_operation_hash_v06() {
+        let mut user_operation = user_operation(
+            EntryPointVersion::V06,
+            "0x5FF137D4b0FDCD49DcA30c7CF57E578a026d2789",
+        );
+        assert_eq!(
+            hex::encode(user_operation_hash(&user_operation).unwrap()),
+            "bbf54fe09a2362aea6aec632f03546398b4c819f5af7ff71c398436851425d35"
+        );
+        user_operation.chain_id = 5;
+        assert_ne!(
+            hex::encode(user_operation_hash(&user_operation).unwrap()),
+            "bbf54fe09a2362aea6aec632f03546398b4c819f5af7ff71c398436851425d35"
+        );
+    }
+
+    #[test]
+    fn test_counterfactual_address() {
+        // EIP-1014 example
+        let init_code_hash = keccak256(&[0x00]).unwrap();
+        assert_eq!(
+            counterfactual_address(
+                "0xdeadbeef00000000000000000000000000000000",
+                &[0u8; 32],
+                &init_code_hash
+            )
+            .unwrap(),
+            "0xB928f69Bb1D91Cd65274e3c79d8986362984fDA3"
+        );
+    }
+
+    #[test]
+    fn test_user_operation_hash_v07() {
+        let mut user_operation = user_operation(
+            EntryPointVersion::V07,
+            "0x0000000071727De22E5E9d8BAf0edAc6f37da032",
+        );
+        assert_eq!(
+            hex::encode(user_operation_hash(&user_operation).unwrap()),
+            "fddb3a7483fb34a637588f0150108b2fe894f7de2736114c599e4061b2fee0a0"
+        );
+        user_operation.paymaster = "0x3535353535353535353535353535353535353535".to_owned();
+        user_operation.paymaster_verification_gas_limit = "0x7530".to_owned();
+        user_operation.paymaster_post_op_gas_limit = "0x0".to_owned();
+        user_operation.paymaster_data = vec![0xde, 0xad];
+        assert_eq!(
+            hex::encode(user_operation_hash(&user_operation).unwrap()),
+            "c2d3d3a2658dd5efe5724193b1e74a7905809ee03d630260a78941f5482dcc20"
+        );
+
+        user_operation.call_gas_limit = format!("{:#x}", U256::one() << 128);
+        assert!(user_operation_hash(&user_operation).is_err());
+    }
+}
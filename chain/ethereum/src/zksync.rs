@@ -0,0 +1,306 @@
+use super::eip2930::trim_hex_prefix;
+use super::eip712::TypedData;
+use super::transaction::{Eip1559Transaction, Transaction, TransactionBase};
+use chain_common::ethereum::{SignInput, TypedDataVersion};
+use chain_common::Error;
+use crypto::hash::{Hasher, Keccak256, Sha256};
+use crypto::Error as CryptoError;
+use ethereum_types::{H160, U256};
+use rlp::RlpStream;
+use serde_json::json;
+use std::convert::TryFrom;
+use std::str::FromStr;
+
+/// Type of zkSync Era EIP-712 transactions
+const EIP712_TX_TYPE: u8 = 0x71;
+
+/// The gas per pubdata byte limit zkSync SDKs default to
+const DEFAULT_GAS_PER_PUBDATA: u64 = 50_000;
+
+/// zkSync Era transaction signed as EIP-712 typed data, with paymaster support and the
+/// bytecodes of the contracts it deploys
+pub struct ZkSyncTransaction {
+    pub base: TransactionBase,
+
+    pub max_inclusion_fee_per_gas: U256,
+
+    pub max_fee_per_gas: U256,
+
+    /// The signing account, part of the signed data
+    pub from: H160,
+
+    pub gas_per_pubdata: U256,
+
+    /// Bytecodes of the contracts the transaction may deploy
+    pub factory_deps: Vec<Vec<u8>>,
+
+    /// Paymaster paying the fee and its input, none when the account pays
+    pub paymaster: Option<(H160, Vec<u8>)>,
+
+    /// EIP-712 digest of the transaction
+    digest: Vec<u8>,
+}
+
+impl ZkSyncTransaction {
+    pub fn new(input: &SignInput, from: H160) -> Result<Self, Error> {
+        let Eip1559Transaction {
+            base,
+            max_inclusion_fee_per_gas,
+            max_fee_per_gas,
+            ..
+        } = Eip1559Transaction::try_from(input)?;
+        // Contracts are deployed by calling the ContractDeployer system contract
+        if base.receiver.is_none() {
+            return Err(Error::InvalidSignInput);
+        }
+        let gas_per_pubdata = match input.gas_per_pubdata.is_empty() {
+            true => U256::from(DEFAULT_GAS_PER_PUBDATA),
+            false => U256::from_str_radix(&input.gas_per_pubdata, 16)
+                .map_err(|_| Error::InvalidSignInput)?,
+        };
+        let paymaster = match input.paymaster.is_empty() {
+            true => None,
+            false => {
+                let paymaster = H160::from_str(trim_hex_prefix(&input.paymaster))
+                    .map_err(|_| Error::InvalidSignInput)?;
+                Some((paymaster, input.paymaster_input.to_vec()))
+            }
+        };
+        let mut transaction = ZkSyncTransaction {
+            base,
+            max_inclusion_fee_per_gas,
+            max_fee_per_gas,
+            from,
+            gas_per_pubdata,
+            factory_deps: input.factory_deps.to_vec(),
+            paymaster,
+            digest: vec![],
+        };
+        transaction.digest = transaction.typed_data_hash(input.chain_id)?;
+        Ok(transaction)
+    }
+
+    fn typed_data_hash(&self, chain_id: u64) -> Result<Vec<u8>, Error> {
+        let factory_deps = self
+            .factory_deps
+            .iter()
+            .map(|bytecode| Ok(format!("0x{}", hex::encode(bytecode_hash(bytecode)?))))
+            .collect::<Result<Vec<_>, Error>>()?;
+        let (paymaster, paymaster_input) = match self.paymaster {
+            Some((ref paymaster, ref input)) => (*paymaster, input.as_slice()),
+            None => (H160::zero(), &[][..]),
+        };
+        let receiver = self.base.receiver.ok_or(Error::InvalidSignInput)?;
+        let typed_data = json!({
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" }
+                ],
+                "Transaction": [
+                    { "name": "txType", "type": "uint256" },
+                    { "name": "from", "type": "uint256" },
+                    { "name": "to", "type": "uint256" },
+                    { "name": "gasLimit", "type": "uint256" },
+                    { "name": "gasPerPubdataByteLimit", "type": "uint256" },
+                    { "name": "maxFeePerGas", "type": "uint256" },
+                    { "name": "maxPriorityFeePerGas", "type": "uint256" },
+                    { "name": "paymaster", "type": "uint256" },
+                    { "name": "nonce", "type": "uint256" },
+                    { "name": "value", "type": "uint256" },
+                    { "name": "data", "type": "bytes" },
+                    { "name": "factoryDeps", "type": "bytes32[]" },
+                    { "name": "paymasterInput", "type": "bytes" }
+                ]
+            },
+            "primaryType": "Transaction",
+            "domain": { "name": "zkSync", "version": "2", "chainId": chain_id },
+            "message": {
+                "txType": EIP712_TX_TYPE,
+                "from": format!("{:#x}", self.from),
+                "to": format!("{:#x}", receiver),
+                "gasLimit": self.base.gas_limit.to_string(),
+                "gasPerPubdataByteLimit": self.gas_per_pubdata.to_string(),
+                "maxFeePerGas": self.max_fee_per_gas.to_string(),
+                "maxPriorityFeePerGas": self.max_inclusion_fee_per_gas.to_string(),
+                "paymaster": format!("{:#x}", paymaster),
+                "nonce": self.base.nonce.to_string(),
+                "value": self.base.amount.to_string(),
+                "data": format!("0x{}", hex::encode(&self.base.payload)),
+                "factoryDeps": factory_deps,
+                "paymasterInput": format!("0x{}", hex::encode(paymaster_input)),
+            }
+        });
+        TypedData::new(&typed_data.to_string(), TypedDataVersion::V4)?.hash()
+    }
+}
+
+impl Transaction for ZkSyncTransaction {
+    fn uses_replay_protection(&self) -> bool {
+        false
+    }
+
+    fn hash(&self, _chain_id: u64) -> Result<Vec<u8>, CryptoError> {
+        Ok(self.digest.to_vec())
+    }
+
+    // The signature goes in the custom signature field, the `v, r, s` fields hold the chain id
+    // and two empty values as encoded by zkSync SDKs
+    fn encode_transaction(&self, v: u64, r: &[u8], s: &[u8], chain_id: u64) -> Vec<u8> {
+        let mut rlp_stream = RlpStream::new_list(16);
+        rlp_stream.append(&self.base.nonce);
+        rlp_stream.append(&self.max_inclusion_fee_per_gas);
+        rlp_stream.append(&self.max_fee_per_gas);
+        rlp_stream.append(&self.base.gas_limit);
+        if let Some(ref t) = self.base.receiver {
+            rlp_stream.append(t);
+        } else {
+            rlp_stream.append(&vec![]);
+        }
+        rlp_stream.append(&self.base.amount);
+        rlp_stream.append(&self.base.payload);
+        rlp_stream.append(&chain_id);
+        rlp_stream.append_empty_data();
+        rlp_stream.append_empty_data();
+        rlp_stream.append(&chain_id);
+        rlp_stream.append(&self.from);
+        rlp_stream.append(&self.gas_per_pubdata);
+        rlp_stream.append_list::<Vec<u8>, _>(&self.factory_deps);
+        rlp_stream.append(&custom_signature(v, r, s));
+        match self.paymaster {
+            Some((ref paymaster, ref input)) => {
+                rlp_stream.begin_list(2);
+                rlp_stream.append(paymaster);
+                rlp_stream.append(input);
+            }
+            None => {
+                rlp_stream.begin_list(0);
+            }
+        }
+        let rlp_encoded = rlp_stream.out();
+
+        let mut encoded = vec![EIP712_TX_TYPE];
+        encoded.extend_from_slice(&rlp_encoded);
+        encoded
+    }
+
+    // keccak256(digest || keccak256(custom signature))
+    fn transaction_hash(
+        &self,
+        _encoded: &[u8],
+        v: u64,
+        r: &[u8],
+        s: &[u8],
+    ) -> Result<Vec<u8>, CryptoError> {
+        let mut data = self.digest.to_vec();
+        data.extend_from_slice(&Hasher::hash(Keccak256, &custom_signature(v, r, s))?);
+        Hasher::hash(Keccak256, &data)
+    }
+}
+
+// `r || s || v` with v of 27 or 28, r and s may come without their leading zeros
+fn custom_signature(v: u64, r: &[u8], s: &[u8]) -> Vec<u8> {
+    let mut signature = vec![0u8; 64];
+    signature[32 - r.len()..32].copy_from_slice(r);
+    signature[64 - s.len()..].copy_from_slice(s);
+    signature.push(v as u8 + 27);
+    signature
+}
+
+/// zkSync bytecode hash of a factory dependency, the sha256 of the bytecode with the version and
+/// its length in 32-byte words in the first 4 bytes
+pub fn bytecode_hash(bytecode: &[u8]) -> Result<Vec<u8>, Error> {
+    let words = bytecode.len() / 32;
+    // A whole and odd number of words
+    if words * 32 != bytecode.len() || words & 1 == 0 || words >= 1 << 16 {
+        return Err(Error::InvalidSignInput);
+    }
+    let mut hash = Hasher::hash(Sha256, bytecode).map_err(|_| Error::InvalidSignInput)?;
+    hash[0] = 1;
+    hash[1] = 0;
+    hash[2..4].copy_from_slice(&(words as u16).to_be_bytes());
+    Ok(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::recovery::recover_from_hash;
+    use crate::signer::Signer;
+    use chain_common::ethereum::TransactionType;
+    use chain_common::private_key::PrivateKey;
+    use rlp::Rlp;
+
+    #[test]
+    fn test_sign_zksync_transaction() {
+        let private_key = PrivateKey::from_str(
+            "4646464646464646464646464646464646464646464646464646464646464646",
+        )
+        .unwrap();
+        let mut input = SignInput {
+            chain_id: 324,
+            nonce: "0x1".to_owned(),
+            gas_price: "".to_owned(),
+            gas_limit: "0x2dc6c0".to_owned(),
+            max_inclusion_fee_per_gas: "0x5f5e100".to_owned(),
+            max_fee_per_gas: "0xee6b280".to_owned(),
+            amount: "0x0".to_owned(),
+            to_address: "0x3535353535353535353535353535353535353535".to_owned(),
+            payload: hex::decode("a9059cbb").unwrap(),
+            transaction_type: TransactionType::ZkSyncEip712 as i32,
+            factory_deps: vec![vec![0xab; 96]],
+            paymaster: "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed".to_owned(),
+            paymaster_input: hex::decode("8c5a3445").unwrap(),
+            ..Default::default()
+        };
+        let output = Signer::sign(&private_key, &input, input.chain_id).unwrap();
+        assert_eq!(output.encoded[0], EIP712_TX_TYPE);
+
+        let rlp = Rlp::new(&output.encoded[1..]);
+        assert_eq!(rlp.item_count().unwrap(), 16);
+        assert_eq!(rlp.val_at::<u64>(10).unwrap(), 324);
+        assert_eq!(
+            rlp.val_at::<H160>(11).unwrap(),
+            H160::from_str("9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F").unwrap()
+        );
+        assert_eq!(rlp.val_at::<u64>(12).unwrap(), DEFAULT_GAS_PER_PUBDATA);
+        assert_eq!(rlp.list_at::<Vec<u8>>(13).unwrap(), input.factory_deps);
+        assert_eq!(
+            rlp.at(15).unwrap().val_at::<Vec<u8>>(1).unwrap(),
+            input.paymaster_input
+        );
+
+        // The custom signature is over the EIP-712 digest
+        let digest =
+            hex::decode("51d664115fdacedcf5665d43506bb30b72c1494243456ba4034c9a61c538a3c7")
+                .unwrap();
+        let signature: Vec<u8> = rlp.val_at(14).unwrap();
+        let signer = recover_from_hash(
+            &digest,
+            &signature[..32],
+            &signature[32..64],
+            signature[64] as u64,
+        )
+        .unwrap();
+        assert_eq!(signer.address, "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F");
+        let mut hashed = digest;
+        hashed.extend_from_slice(&Hasher::hash(Keccak256, &signature).unwrap());
+        assert_eq!(output.hash, Hasher::hash(Keccak256, &hashed).unwrap());
+
+        // Contracts are deployed through the ContractDeployer
+        input.to_address = "".to_owned();
+        assert!(Signer::sign(&private_key, &input, input.chain_id).is_err());
+    }
+
+    #[test]
+    fn test_bytecode_hash() {
+        assert_eq!(
+            hex::encode(bytecode_hash(&[0xab; 96]).unwrap()),
+            "010000039e91871a7d6b22fb9f27b83cd2ad7a21e6585ca8b6768f9b59d7b0a1"
+        );
+        // Bytecodes are an odd number of words
+        assert!(bytecode_hash(&[0xab; 64]).is_err());
+        assert!(bytecode_hash(&[0xab; 95]).is_err());
+    }
+}
@@ -0,0 +1,161 @@
+use bitcoin::util::base58;
+use blake2::{Blake2b512, Digest};
+use chain_common::public_key::PublicKey;
+use crypto::public_key::PublicKeyType;
+use crypto::Error;
+use std::str::FromStr;
+
+/// SS58 network prefixes
+pub const POLKADOT_PREFIX: u16 = 0;
+pub const KUSAMA_PREFIX: u16 = 2;
+pub const GENERIC_PREFIX: u16 = 42;
+
+const ACCOUNT_SIZE: usize = 32;
+const CHECKSUM_SIZE: usize = 2;
+const CHECKSUM_CONTEXT: &[u8] = b"SS58PRE";
+
+// Prefixes up to 63 take a single byte, larger ones two
+const SIMPLE_PREFIX_MAX: u16 = 63;
+const PREFIX_MAX: u16 = 16_383;
+
+pub struct PolkadotAddress {
+    pub prefix: u16,
+    pub data: Vec<u8>,
+}
+
+impl PolkadotAddress {
+    /// Whether `address` is a valid SS58 address of the network with `prefix`
+    pub fn is_valid(address: &str, prefix: u16) -> bool {
+        match PolkadotAddress::from_str(address) {
+            Ok(address) => address.prefix == prefix,
+            Err(_) => false,
+        }
+    }
+
+    pub fn new(public_key: &PublicKey, prefix: u16) -> Result<Self, Error> {
+        if public_key.r#type != PublicKeyType::Ed25519
+            && public_key.r#type != PublicKeyType::Sr25519
+        {
+            return Err(Error::NotSupportedPublicKeyType);
+        }
+        if public_key.data.len() != ACCOUNT_SIZE || prefix > PREFIX_MAX {
+            return Err(Error::InvalidPublicKey);
+        }
+        Ok(PolkadotAddress {
+            prefix,
+            data: public_key.data.to_vec(),
+        })
+    }
+}
+
+impl FromStr for PolkadotAddress {
+    type Err = Error;
+    fn from_str(address: &str) -> Result<Self, Self::Err> {
+        let decoded = base58::from(address).map_err(|_| Error::InvalidPublicKey)?;
+        let (prefix, prefix_size) = match decoded.first() {
+            Some(&first) if first <= SIMPLE_PREFIX_MAX as u8 => (first as u16, 1),
+            Some(&first) if first < 0b1000_0000 && decoded.len() > 1 => {
+                let second = decoded[1];
+                let lower = (first << 2) | (second >> 6);
+                let upper = second & 0b0011_1111;
+                (lower as u16 | (upper as u16) << 8, 2)
+            }
+            _ => return Err(Error::InvalidPublicKey),
+        };
+        if decoded.len() != prefix_size + ACCOUNT_SIZE + CHECKSUM_SIZE {
+            return Err(Error::InvalidPublicKey);
+        }
+        let (payload, checksum) = decoded.split_at(prefix_size + ACCOUNT_SIZE);
+        if ss58_checksum(payload) != checksum {
+            return Err(Error::InvalidPublicKey);
+        }
+        Ok(PolkadotAddress {
+            prefix,
+            data: payload[prefix_size..].to_vec(),
+        })
+    }
+}
+
+impl std::fmt::Display for PolkadotAddress {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut payload = encode_prefix(self.prefix);
+        payload.extend_from_slice(&self.data);
+        let checksum = ss58_checksum(&payload);
+        payload.extend_from_slice(&checksum);
+        write!(f, "{}", base58::encode_slice(&payload))
+    }
+}
+
+fn encode_prefix(prefix: u16) -> Vec<u8> {
+    if prefix <= SIMPLE_PREFIX_MAX {
+        return vec![prefix as u8];
+    }
+    let first = ((prefix & 0b1111_1100) >> 2) as u8 | 0b0100_0000;
+    let second = (prefix >> 8) as u8 | ((prefix & 0b0000_0011) << 6) as u8;
+    vec![first, second]
+}
+
+/// First bytes of `blake2b_512("SS58PRE" || prefix || account)`
+fn ss58_checksum(payload: &[u8]) -> Vec<u8> {
+    let mut hasher = Blake2b512::new();
+    hasher.update(CHECKSUM_CONTEXT);
+    hasher.update(payload);
+    hasher.finalize()[..CHECKSUM_SIZE].to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d";
+
+    fn public_key() -> PublicKey {
+        PublicKey {
+            r#type: PublicKeyType::Ed25519,
+            data: hex::decode(ALICE).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_derive_from_pub_key() {
+        let address = |prefix| {
+            PolkadotAddress::new(&public_key(), prefix)
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(
+            address(POLKADOT_PREFIX),
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5"
+        );
+        assert_eq!(
+            address(KUSAMA_PREFIX),
+            "HNZata7iMYWmk5RvZRTiAsSDhV8366zq2YGb3tLH5Upf74F"
+        );
+        assert_eq!(
+            address(GENERIC_PREFIX),
+            "5GrwvaEF5zXb26Fz9rcQpDWS57CtERHpNehXCPcNoHGKutQY"
+        );
+
+        // Two byte prefixes round trip
+        let address = PolkadotAddress::from_str(&address(1284)).unwrap();
+        assert_eq!(address.prefix, 1284);
+        assert_eq!(hex::encode(address.data), ALICE);
+    }
+
+    #[test]
+    fn test_validate_address() {
+        let polkadot = "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5";
+        assert!(PolkadotAddress::is_valid(polkadot, POLKADOT_PREFIX));
+        assert!(!PolkadotAddress::is_valid(polkadot, KUSAMA_PREFIX));
+        // Bad checksum
+        assert!(!PolkadotAddress::is_valid(
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp6",
+            POLKADOT_PREFIX
+        ));
+        assert!(!PolkadotAddress::is_valid(
+            "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6",
+            POLKADOT_PREFIX
+        ));
+        assert!(!PolkadotAddress::is_valid("", POLKADOT_PREFIX));
+    }
+}
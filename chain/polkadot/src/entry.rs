@@ -0,0 +1,103 @@
+use super::address::{PolkadotAddress, POLKADOT_PREFIX};
+use super::signer::Signer;
+use bytes::BytesMut;
+use chain_common::api::{AddressChecksum, ChecksumValidation};
+use chain_common::coin::Coin;
+use chain_common::entry::{ChainExportType, ChainImportType, Entry};
+use chain_common::polkadot::SignInput;
+use chain_common::private_key::PrivateKey;
+use chain_common::public_key::PublicKey;
+use crypto::curve::Curve;
+use crypto::Error;
+use prost::Message;
+use std::str::FromStr;
+
+pub struct PolkadotEntry;
+
+impl PolkadotEntry {
+    /// SS58 prefix of the coin's network, declared as `ss58_prefix` in the coin registry
+    fn prefix(coin: &Coin) -> u16 {
+        coin.all_info
+            .get("ss58_prefix")
+            .and_then(|value| value.as_u64())
+            .map_or(POLKADOT_PREFIX, |prefix| prefix as u16)
+    }
+}
+
+impl Entry for PolkadotEntry {
+    fn get_supported_import_types(&self) -> Vec<ChainImportType> {
+        vec![ChainImportType::PrivateKey, ChainImportType::Mnemonic]
+    }
+
+    fn get_supported_export_types(&self) -> Vec<ChainExportType> {
+        vec![ChainExportType::PrivateKey]
+    }
+
+    fn validate_address(&self, address: &str) -> bool {
+        PolkadotAddress::is_valid(address, POLKADOT_PREFIX)
+    }
+
+    fn validate_address_checksum(
+        &self,
+        coin: &Coin,
+        address: &str,
+        _validation: ChecksumValidation,
+    ) -> (bool, AddressChecksum) {
+        (
+            PolkadotAddress::is_valid(address, PolkadotEntry::prefix(coin)),
+            AddressChecksum::NoChecksum,
+        )
+    }
+
+    fn derive_address(
+        &self,
+        coin: &Coin,
+        public_key: &PublicKey,
+        _p2pkh: &[u8],
+        _hrp: &[u8],
+    ) -> Result<String, Error> {
+        let address = PolkadotAddress::new(public_key, PolkadotEntry::prefix(coin))?;
+        Ok(address.to_string())
+    }
+
+    fn sign(
+        &self,
+        coin: &Coin,
+        private_key: &PrivateKey,
+        payload: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        let sign_input: SignInput = match SignInput::decode(payload) {
+            Ok(request) => request,
+            Err(_) => return Err(Error::InvalidPrivateKey),
+        };
+        let curve = Curve::from_str(&coin.curve)?;
+        let output =
+            Signer::sign(private_key, &curve, &sign_input).map_err(|_| Error::InvalidPrivateKey)?;
+
+        let mut buf = BytesMut::with_capacity(output.encoded_len());
+        output
+            .encode(&mut buf)
+            .expect("Fail to encode the SignOutput");
+        Ok(buf.to_vec())
+    }
+
+    fn sign_message(
+        &self,
+        coin: &Coin,
+        private_key: &PrivateKey,
+        message: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        let curve = Curve::from_str(&coin.curve)?;
+        Signer::sign_message(private_key, &curve, message).map_err(|_| Error::InvalidPrivateKey)
+    }
+
+    fn verify_message(
+        &self,
+        _coin: &Coin,
+        message: &[u8],
+        signature: &[u8],
+        address: &str,
+    ) -> Result<bool, Error> {
+        Ok(Signer::verify_message(message, signature, address))
+    }
+}
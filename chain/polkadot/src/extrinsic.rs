@@ -0,0 +1,196 @@
+use super::address::PolkadotAddress;
+use super::scale::compact;
+use chain_common::polkadot::{sign_input::Call, CallIndices, Era, SignInput, Transfer};
+use chain_common::Error;
+use crypto::hash::{Blake2b256, Hasher};
+use std::str::FromStr;
+
+const HASH_SIZE: usize = 32;
+
+// Payloads longer than this are signed by their hash
+const MAX_PAYLOAD_SIZE: usize = 256;
+
+// Version 4 of the extrinsic format with the signed bit set
+const SIGNED_EXTRINSIC_VERSION: u8 = 0x84;
+
+// MultiAddress::Id
+const ADDRESS_TYPE_ID: u8 = 0x00;
+
+const POLKADOT_GENESIS_HASH: &str =
+    "91b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3";
+const KUSAMA_GENESIS_HASH: &str =
+    "b0a8d493285c2df73290dfb7e61f870f17b41801197a149ca93654499ea3dafe";
+
+/// An extrinsic to be signed, the call with the extra and additional signed extension data
+pub struct Extrinsic {
+    call: Vec<u8>,
+    extra: Vec<u8>,
+    additional: Vec<u8>,
+}
+
+impl Extrinsic {
+    pub fn new(sign_input: &SignInput) -> Result<Self, Error> {
+        if sign_input.genesis_hash.len() != HASH_SIZE {
+            return Err(Error::InvalidSignInput);
+        }
+        let call = match &sign_input.call {
+            Some(Call::Transfer(transfer)) => encode_transfer(transfer, &sign_input.genesis_hash)?,
+            Some(Call::RawCall(raw_call)) if !raw_call.is_empty() => raw_call.to_vec(),
+            _ => return Err(Error::InvalidSignInput),
+        };
+
+        // The era is checked against the hash of its first block, the genesis for an immortal one
+        let era = sign_input.era.as_ref().filter(|era| era.period > 0);
+        let block_hash = match (era, sign_input.block_hash.is_empty()) {
+            (None, true) => &sign_input.genesis_hash,
+            _ => &sign_input.block_hash,
+        };
+        if block_hash.len() != HASH_SIZE {
+            return Err(Error::InvalidSignInput);
+        }
+
+        let mut extra = encode_era(era);
+        extra.extend(compact(sign_input.nonce as u128));
+        extra.extend(compact(decode_amount(&sign_input.tip)?));
+
+        let mut additional = [
+            sign_input.spec_version.to_le_bytes(),
+            sign_input.transaction_version.to_le_bytes(),
+        ]
+        .concat();
+        additional.extend_from_slice(&sign_input.genesis_hash);
+        additional.extend_from_slice(block_hash);
+
+        if sign_input.check_metadata_hash {
+            match sign_input.metadata_hash.len() {
+                0 => {
+                    extra.push(0x00);
+                    additional.push(0x00);
+                }
+                HASH_SIZE => {
+                    extra.push(0x01);
+                    additional.push(0x01);
+                    additional.extend_from_slice(&sign_input.metadata_hash);
+                }
+                _ => return Err(Error::InvalidSignInput),
+            }
+        }
+
+        Ok(Extrinsic {
+            call,
+            extra,
+            additional,
+        })
+    }
+
+    /// The call followed by the extra and additional signed extension data
+    pub fn payload(&self) -> Vec<u8> {
+        [
+            self.call.as_slice(),
+            self.extra.as_slice(),
+            self.additional.as_slice(),
+        ]
+        .concat()
+    }
+
+    /// The data actually signed, the payload or its blake2b-256 hash if it's too long
+    pub fn signing_data(&self) -> Result<Vec<u8>, Error> {
+        let payload = self.payload();
+        if payload.len() <= MAX_PAYLOAD_SIZE {
+            return Ok(payload);
+        }
+        Hasher::hash(Blake2b256, &payload).map_err(|_| Error::InvalidSignInput)
+    }
+
+    /// The length prefixed signed extrinsic
+    pub fn encode_signed(&self, signer: &[u8], signature_type: u8, signature: &[u8]) -> Vec<u8> {
+        let mut encoded = vec![SIGNED_EXTRINSIC_VERSION, ADDRESS_TYPE_ID];
+        encoded.extend_from_slice(signer);
+        encoded.push(signature_type);
+        encoded.extend_from_slice(signature);
+        encoded.extend_from_slice(&self.extra);
+        encoded.extend_from_slice(&self.call);
+        [compact(encoded.len() as u128), encoded].concat()
+    }
+}
+
+fn encode_transfer(transfer: &Transfer, genesis_hash: &[u8]) -> Result<Vec<u8>, Error> {
+    let (module_index, method_index) = match &transfer.call_indices {
+        Some(CallIndices {
+            module_index,
+            method_index,
+        }) => (*module_index, *method_index),
+        None => match hex::encode(genesis_hash).as_str() {
+            POLKADOT_GENESIS_HASH => (5, 3),
+            KUSAMA_GENESIS_HASH => (4, 3),
+            _ => return Err(Error::InvalidSignInput),
+        },
+    };
+    if module_index > u8::MAX as u32 || method_index > u8::MAX as u32 {
+        return Err(Error::InvalidSignInput);
+    }
+    let to_address =
+        PolkadotAddress::from_str(&transfer.to_address).map_err(|_| Error::InvalidSignInput)?;
+
+    let mut call = vec![module_index as u8, method_index as u8, ADDRESS_TYPE_ID];
+    call.extend_from_slice(&to_address.data);
+    call.extend(compact(decode_amount(&transfer.value)?));
+    Ok(call)
+}
+
+/// Decode a big endian amount of at most 128 bits
+fn decode_amount(value: &[u8]) -> Result<u128, Error> {
+    if value.len() > 16 {
+        return Err(Error::InvalidSignInput);
+    }
+    Ok(value
+        .iter()
+        .fold(0u128, |amount, byte| (amount << 8) | *byte as u128))
+}
+
+/// Two bytes mortal era, or a single zero byte for an immortal one
+fn encode_era(era: Option<&Era>) -> Vec<u8> {
+    let era = match era {
+        Some(era) => era,
+        None => return vec![0x00],
+    };
+    let period = era
+        .period
+        .checked_next_power_of_two()
+        .unwrap_or(1 << 16)
+        .clamp(4, 1 << 16);
+    let phase = era.block_number % period;
+    let quantize_factor = (period >> 12).max(1);
+    let encoded =
+        (period.trailing_zeros() as u64 - 1).clamp(1, 15) | ((phase / quantize_factor) << 4);
+    (encoded as u16).to_le_bytes().to_vec()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_era() {
+        assert_eq!(encode_era(None), vec![0x00]);
+        let era = Era {
+            block_number: 42,
+            period: 64,
+        };
+        assert_eq!(hex::encode(encode_era(Some(&era))), "a502");
+        // The period is rounded up to a power of two
+        let era = Era {
+            block_number: 10_672_081,
+            period: 50,
+        };
+        assert_eq!(hex::encode(encode_era(Some(&era))), "1501");
+    }
+
+    #[test]
+    fn test_decode_amount() {
+        assert_eq!(decode_amount(&[]).unwrap(), 0);
+        assert_eq!(decode_amount(&[0x30, 0x39]).unwrap(), 12345);
+        assert_eq!(decode_amount(&[0xff; 16]).unwrap(), u128::MAX);
+        assert_eq!(decode_amount(&[0x01; 17]), Err(Error::InvalidSignInput));
+    }
+}
@@ -0,0 +1,36 @@
+/// SCALE compact encoding of an unsigned integer
+pub fn compact(value: u128) -> Vec<u8> {
+    match value {
+        0..=0x3f => vec![(value as u8) << 2],
+        0x40..=0x3fff => (((value as u16) << 2) | 0b01).to_le_bytes().to_vec(),
+        0x4000..=0x3fff_ffff => (((value as u32) << 2) | 0b10).to_le_bytes().to_vec(),
+        _ => {
+            // Big integer mode, the byte count minus 4 followed by the little endian bytes
+            let size = 16 - value.leading_zeros() as usize / 8;
+            let mut encoded = vec![(((size - 4) as u8) << 2) | 0b11];
+            encoded.extend_from_slice(&value.to_le_bytes()[..size]);
+            encoded
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compact() {
+        assert_eq!(hex::encode(compact(0)), "00");
+        assert_eq!(hex::encode(compact(63)), "fc");
+        assert_eq!(hex::encode(compact(64)), "0101");
+        assert_eq!(hex::encode(compact(12345)), "e5c0");
+        assert_eq!(hex::encode(compact(16383)), "fdff");
+        assert_eq!(hex::encode(compact(16384)), "02000100");
+        assert_eq!(hex::encode(compact(1 << 30)), "0300000040");
+        assert_eq!(hex::encode(compact(10_000_000_000)), "0700e40b5402");
+        assert_eq!(
+            hex::encode(compact(u128::MAX)),
+            "33ffffffffffffffffffffffffffffffff"
+        );
+    }
+}
@@ -0,0 +1,271 @@
+use super::address::PolkadotAddress;
+use super::extrinsic::Extrinsic;
+use chain_common::polkadot::{SignInput, SignOutput};
+use chain_common::private_key::PrivateKey;
+use chain_common::Error;
+use crypto::curve::Curve;
+use crypto::sr25519;
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signature, Signer as _, Verifier};
+use std::str::FromStr;
+
+// polkadot-js wraps raw messages so they can't be mistaken for an extrinsic
+const MESSAGE_PREFIX: &[u8] = b"<Bytes>";
+const MESSAGE_SUFFIX: &[u8] = b"</Bytes>";
+
+const SR25519_SIGNING_CONTEXT: &[u8] = b"substrate";
+
+// MultiSignature variants
+const ED25519_SIGNATURE_TYPE: u8 = 0x00;
+const SR25519_SIGNATURE_TYPE: u8 = 0x01;
+
+pub struct Signer;
+
+impl Signer {
+    /// Sign the extrinsic of `sign_input`, with an ed25519 or sr25519 key according to `curve`
+    pub fn sign(
+        private_key: &PrivateKey,
+        curve: &Curve,
+        sign_input: &SignInput,
+    ) -> Result<SignOutput, Error> {
+        let (public_key_type, signature_type) = match curve {
+            Curve::Ed25519 => ("ed25519", ED25519_SIGNATURE_TYPE),
+            Curve::Sr25519 => ("sr25519", SR25519_SIGNATURE_TYPE),
+            _ => return Err(Error::InvalidPrivateKey),
+        };
+        let public_key = private_key
+            .get_public_key(public_key_type)
+            .map_err(|_| Error::InvalidPrivateKey)?;
+
+        let extrinsic = Extrinsic::new(sign_input)?;
+        let signature = sign(private_key, curve, &extrinsic.signing_data()?)?;
+        Ok(SignOutput {
+            encoded: extrinsic.encode_signed(&public_key.data, signature_type, &signature),
+            signature,
+            payload: extrinsic.payload(),
+        })
+    }
+
+    /// Sign `message` the way polkadot-js `signRaw` does, wrapped in `<Bytes>` tags
+    pub fn sign_message(
+        private_key: &PrivateKey,
+        curve: &Curve,
+        message: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        sign(private_key, curve, &wrap_message(message))
+    }
+
+    /// Whether `signature` is a `signRaw` signature of `message` by the account of `address`
+    pub fn verify_message(message: &[u8], signature: &[u8], address: &str) -> bool {
+        let address = match PolkadotAddress::from_str(address) {
+            Ok(address) => address,
+            Err(_) => return false,
+        };
+        let message = wrap_message(message);
+        // The address doesn't tell the key scheme, so both are tried
+        let ed25519 = match (
+            PublicKey::from_bytes(&address.data),
+            Signature::from_bytes(signature),
+        ) {
+            (Ok(public_key), Ok(signature)) => public_key.verify(&message, &signature).is_ok(),
+            _ => false,
+        };
+        ed25519 || {
+            let public_key = schnorrkel::PublicKey::from_bytes(&address.data);
+            let signature = schnorrkel::Signature::from_bytes(signature);
+            match (public_key, signature) {
+                (Ok(public_key), Ok(signature)) => public_key
+                    .verify_simple(SR25519_SIGNING_CONTEXT, &message, &signature)
+                    .is_ok(),
+                _ => false,
+            }
+        }
+    }
+}
+
+fn sign(private_key: &PrivateKey, curve: &Curve, payload: &[u8]) -> Result<Vec<u8>, Error> {
+    if let Curve::Sr25519 = curve {
+        let secret =
+            sr25519::secret_key(&private_key.data).map_err(|_| Error::InvalidPrivateKey)?;
+        let public = secret.to_public();
+        let signature = secret.sign_simple(SR25519_SIGNING_CONTEXT, payload, &public);
+        return Ok(signature.to_bytes().to_vec());
+    }
+    let secret = SecretKey::from_bytes(&private_key.data).map_err(|_| Error::InvalidPrivateKey)?;
+    let public = PublicKey::from(&secret);
+    let keypair = Keypair { secret, public };
+    Ok(keypair.sign(payload).to_bytes().to_vec())
+}
+
+fn wrap_message(message: &[u8]) -> Vec<u8> {
+    if message.starts_with(MESSAGE_PREFIX) && message.ends_with(MESSAGE_SUFFIX) {
+        return message.to_vec();
+    }
+    [MESSAGE_PREFIX, message, MESSAGE_SUFFIX].concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::address::{PolkadotAddress, POLKADOT_PREFIX};
+    use chain_common::polkadot::{sign_input::Call, Era, Transfer};
+
+    const GENESIS_HASH: &str = "91b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3";
+
+    fn transfer_input() -> SignInput {
+        SignInput {
+            block_hash: hex::decode(
+                "343a3f4258fd92f5ca6ca5abdf473d86a78b0bcd0dc09c568ca594245cc8c642",
+            )
+            .unwrap(),
+            genesis_hash: hex::decode(GENESIS_HASH).unwrap(),
+            nonce: 1,
+            spec_version: 1_003_000,
+            transaction_version: 26,
+            tip: vec![],
+            era: Some(Era {
+                block_number: 10_672_081,
+                period: 64,
+            }),
+            check_metadata_hash: true,
+            metadata_hash: vec![],
+            call: Some(Call::Transfer(Transfer {
+                to_address: "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5".to_owned(),
+                value: vec![0x30, 0x39],
+                call_indices: None,
+            })),
+        }
+    }
+
+    fn address(private_key: &PrivateKey, public_key_type: &str) -> String {
+        let public_key = private_key.get_public_key(public_key_type).unwrap();
+        PolkadotAddress::new(&public_key, POLKADOT_PREFIX)
+            .unwrap()
+            .to_string()
+    }
+
+    #[test]
+    fn test_sign_message() {
+        let private_key = PrivateKey::from_str(
+            "0000000000000000000000000000000000000000000000000000000000000001",
+        )
+        .unwrap();
+        let address = address(&private_key, "ed25519");
+
+        let signature = Signer::sign_message(&private_key, &Curve::Ed25519, b"Hello Mask").unwrap();
+        assert_eq!(signature.len(), 64);
+        assert!(Signer::verify_message(b"Hello Mask", &signature, &address));
+        // A wrapped message is signed as is
+        assert!(Signer::verify_message(
+            b"<Bytes>Hello Mask</Bytes>",
+            &signature,
+            &address
+        ));
+        assert!(!Signer::verify_message(b"Hello", &signature, &address));
+    }
+
+    #[test]
+    fn test_sign_message_sr25519() {
+        let private_key = PrivateKey::from_str(
+            "0000000000000000000000000000000000000000000000000000000000000001",
+        )
+        .unwrap();
+        let address = address(&private_key, "sr25519");
+        assert_ne!(
+            PolkadotAddress::from_str(&address).unwrap().data,
+            private_key.get_public_key("ed25519").unwrap().data
+        );
+
+        let signature = Signer::sign_message(&private_key, &Curve::Sr25519, b"Hello Mask").unwrap();
+        assert!(Signer::verify_message(b"Hello Mask", &signature, &address));
+        assert!(!Signer::verify_message(b"Hello", &signature, &address));
+    }
+
+    #[test]
+    fn test_sign_transfer() {
+        let private_key = PrivateKey::from_str(
+            "abf8e5bdbe30c65656c0a3cbd181ff8a56294a69dfedd27982aace4a76909115",
+        )
+        .unwrap();
+        let output = Signer::sign(&private_key, &Curve::Ed25519, &transfer_input()).unwrap();
+        assert_eq!(
+            hex::encode(&output.payload),
+            "050300d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27de5c01501040000f84d0f001a00000091b171bb158e2d3848fa23a9f1c25182fb8e20313b2c1eb49219da7a70ce90c3343a3f4258fd92f5ca6ca5abdf473d86a78b0bcd0dc09c568ca594245cc8c64200"
+        );
+        assert_eq!(
+            hex::encode(&output.signature),
+            "e1b4ff32d80fbcc8c36ea185bc578c7c087ac1da1969968ad7cb17cafba876d53505e4a7ac2ad7633444261ee225863c56194cfda4b015e64132d1ae022e8402"
+        );
+        assert_eq!(
+            hex::encode(&output.encoded),
+            "3502840088dc3417d5058ec4b4503e0c12ea1a0a89be200fe98922423d4334014fa6b0ee00e1b4ff32d80fbcc8c36ea185bc578c7c087ac1da1969968ad7cb17cafba876d53505e4a7ac2ad7633444261ee225863c56194cfda4b015e64132d1ae022e84021501040000050300d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27de5c0"
+        );
+    }
+
+    #[test]
+    fn test_sign_transfer_sr25519() {
+        let private_key = PrivateKey::from_str(
+            "0000000000000000000000000000000000000000000000000000000000000001",
+        )
+        .unwrap();
+        let output = Signer::sign(&private_key, &Curve::Sr25519, &transfer_input()).unwrap();
+        let public_key = private_key.get_public_key("sr25519").unwrap().data;
+
+        let public = schnorrkel::PublicKey::from_bytes(&public_key).unwrap();
+        let signature = schnorrkel::Signature::from_bytes(&output.signature).unwrap();
+        assert!(public
+            .verify_simple(SR25519_SIGNING_CONTEXT, &output.payload, &signature)
+            .is_ok());
+        // The signer is followed by the sr25519 signature type
+        assert_eq!(&output.encoded[4..36], public_key.as_slice());
+        assert_eq!(output.encoded[36], SR25519_SIGNATURE_TYPE);
+    }
+
+    #[test]
+    fn test_sign_long_raw_call() {
+        let private_key = PrivateKey::from_str(
+            "abf8e5bdbe30c65656c0a3cbd181ff8a56294a69dfedd27982aace4a76909115",
+        )
+        .unwrap();
+        // An immortal extrinsic is checked against the genesis hash
+        let input = SignInput {
+            block_hash: vec![],
+            nonce: 0,
+            era: None,
+            check_metadata_hash: false,
+            call: Some(Call::RawCall((0..=255).collect())),
+            ..transfer_input()
+        };
+        let output = Signer::sign(&private_key, &Curve::Ed25519, &input).unwrap();
+        assert_eq!(output.payload.len(), 256 + 3 + 72);
+        // The payload is signed by its hash
+        assert_eq!(
+            hex::encode(&output.signature),
+            "621a1a55b0e202c2c537a0b53a49818b7094a65d56d393d849596d467c54ef9d1cc98419d6b2313732937db3822cc777258462495ff189d0547c79e288863f01"
+        );
+    }
+
+    #[test]
+    fn test_sign_invalid_input() {
+        let private_key = PrivateKey::from_str(
+            "abf8e5bdbe30c65656c0a3cbd181ff8a56294a69dfedd27982aace4a76909115",
+        )
+        .unwrap();
+        // Transfer call indices are only known for Polkadot and Kusama
+        let input = SignInput {
+            genesis_hash: vec![0; 32],
+            ..transfer_input()
+        };
+        assert_eq!(
+            Signer::sign(&private_key, &Curve::Ed25519, &input).unwrap_err(),
+            Error::InvalidSignInput
+        );
+        let input = SignInput {
+            call: None,
+            ..transfer_input()
+        };
+        assert_eq!(
+            Signer::sign(&private_key, &Curve::Ed25519, &input).unwrap_err(),
+            Error::InvalidSignInput
+        );
+    }
+}
@@ -1,9 +1,13 @@
 use super::address::SolanaAddress;
+use super::signer::Signer;
+use bytes::BytesMut;
 use chain_common::coin::Coin;
 use chain_common::entry::{ChainExportType, ChainImportType, Entry};
 use chain_common::private_key::PrivateKey;
 use chain_common::public_key::PublicKey;
+use chain_common::solana::SignInput;
 use crypto::Error;
+use prost::Message;
 
 pub struct SolanaEntry;
 
@@ -34,21 +38,39 @@ impl Entry for SolanaEntry {
     fn sign(
         &self,
         _coin: &Coin,
-        _private_key: &PrivateKey,
-        _payload: &[u8],
+        private_key: &PrivateKey,
+        payload: &[u8],
     ) -> Result<Vec<u8>, Error> {
-        Ok(vec![])
-        // let sign_input: SignInput = match SignInput::decode(payload) {
-        //     Ok(request) => request,
-        //     Err(_) => return Err(Error::InvalidPrivateKey),
-        // };
-        // let output =
-        //     Signer::sign(&private_key, &sign_input).map_err(|_| Error::InvalidPrivateKey)?;
-
-        // let mut buf = BytesMut::with_capacity(output.encoded_len());
-        // output
-        //     .encode(&mut buf)
-        //     .expect("Fail to encode the SignOutput");
-        // Ok(buf.to_vec())
+        let sign_input: SignInput = match SignInput::decode(payload) {
+            Ok(request) => request,
+            Err(_) => return Err(Error::InvalidPrivateKey),
+        };
+        let output =
+            Signer::sign(private_key, &sign_input).map_err(|_| Error::InvalidPrivateKey)?;
+
+        let mut buf = BytesMut::with_capacity(output.encoded_len());
+        output
+            .encode(&mut buf)
+            .expect("Fail to encode the SignOutput");
+        Ok(buf.to_vec())
+    }
+
+    fn sign_message(
+        &self,
+        _coin: &Coin,
+        private_key: &PrivateKey,
+        message: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        Signer::sign_message(private_key, message).map_err(|_| Error::InvalidPrivateKey)
+    }
+
+    fn verify_message(
+        &self,
+        _coin: &Coin,
+        message: &[u8],
+        signature: &[u8],
+        address: &str,
+    ) -> Result<bool, Error> {
+        Ok(Signer::verify_message(message, signature, address))
     }
 }
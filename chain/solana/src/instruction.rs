@@ -0,0 +1,93 @@
+use bitcoin::util::base58;
+use chain_common::Error;
+
+pub type Pubkey = [u8; 32];
+
+pub const SYSTEM_PROGRAM_ID: &str = "11111111111111111111111111111111";
+pub const MEMO_PROGRAM_ID: &str = "MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr";
+
+// Index of the `Transfer` variant in the System Program instruction enum
+const SYSTEM_TRANSFER_INDEX: u32 = 2;
+
+pub fn decode_pubkey(address: &str) -> Result<Pubkey, Error> {
+    let data = base58::from(address).map_err(|_| Error::InvalidSignInput)?;
+    Pubkey::try_from(data.as_slice()).map_err(|_| Error::InvalidSignInput)
+}
+
+pub fn program_id(address: &str) -> Pubkey {
+    decode_pubkey(address).expect("Invalid program id")
+}
+
+#[derive(Clone)]
+pub struct AccountMeta {
+    pub pubkey: Pubkey,
+    pub is_signer: bool,
+    pub is_writable: bool,
+}
+
+impl AccountMeta {
+    pub fn new(pubkey: Pubkey, is_signer: bool) -> Self {
+        AccountMeta {
+            pubkey,
+            is_signer,
+            is_writable: true,
+        }
+    }
+
+    pub fn new_readonly(pubkey: Pubkey, is_signer: bool) -> Self {
+        AccountMeta {
+            pubkey,
+            is_signer,
+            is_writable: false,
+        }
+    }
+}
+
+pub struct Instruction {
+    pub program_id: Pubkey,
+    pub accounts: Vec<AccountMeta>,
+    pub data: Vec<u8>,
+}
+
+/// System Program instruction which moves `lamports` from `from` to `to`
+pub fn transfer(from: &Pubkey, to: &Pubkey, lamports: u64) -> Instruction {
+    let mut data = SYSTEM_TRANSFER_INDEX.to_le_bytes().to_vec();
+    data.extend_from_slice(&lamports.to_le_bytes());
+    Instruction {
+        program_id: program_id(SYSTEM_PROGRAM_ID),
+        accounts: vec![AccountMeta::new(*from, true), AccountMeta::new(*to, false)],
+        data,
+    }
+}
+
+/// Memo Program instruction which records `memo` in the transaction log
+pub fn memo(memo: &str) -> Instruction {
+    Instruction {
+        program_id: program_id(MEMO_PROGRAM_ID),
+        accounts: vec![],
+        data: memo.as_bytes().to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_data() {
+        let from = decode_pubkey("6eoo7i1khGhVm8tLBMAdq4ax2FxkKP4G7mCcfHyr3STN").unwrap();
+        let to = decode_pubkey("EN2sCsJ1WDV8UFqsiTXHcUPUxQ4juE71eCknHYYMifkd").unwrap();
+        let instruction = transfer(&from, &to, 42);
+        assert_eq!(hex::encode(&instruction.data), "020000002a00000000000000");
+        assert_eq!(instruction.program_id, [0u8; 32]);
+        assert!(instruction.accounts[0].is_signer);
+        assert!(!instruction.accounts[1].is_signer);
+    }
+
+    #[test]
+    fn test_decode_pubkey() {
+        assert!(decode_pubkey("2gVkYWexTHR5Hb2aLeQN3tnngvWzisFKXDUPrgMHpdST").is_ok());
+        assert!(decode_pubkey("2gVkYWexTHR5Hb2aLeQN3tnngvWzisFKXDUPrgMHpd").is_err());
+        assert!(decode_pubkey("0OIl").is_err());
+    }
+}
@@ -1,2 +1,6 @@
 pub mod address;
 pub mod entry;
+mod instruction;
+mod signer;
+mod token;
+mod transaction;
@@ -0,0 +1,412 @@
+use super::instruction::{self, decode_pubkey, Instruction, Pubkey};
+use super::token;
+use super::transaction::{Message, Transaction, SIGNATURE_SIZE};
+use bitcoin::util::base58;
+use chain_common::private_key::PrivateKey;
+use chain_common::solana::{
+    sign_input::TransactionType, Encoding, SignInput, SignOutput, TokenProgram,
+};
+use chain_common::Error;
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signature, Signer as _, Verifier};
+
+pub struct Signer;
+
+impl Signer {
+    pub fn sign(private_key: &PrivateKey, sign_input: &SignInput) -> Result<SignOutput, Error> {
+        let secret =
+            SecretKey::from_bytes(&private_key.data).map_err(|_| Error::InvalidPrivateKey)?;
+        let public = PublicKey::from(&secret);
+        let keypair = Keypair { secret, public };
+        let signer_key = public.to_bytes();
+
+        // Signatures of the other required signers are kept if present, or left empty for them to fill in
+        let (message, signatures) = match &sign_input.transaction_type {
+            Some(TransactionType::RawMessage(raw_message)) => (raw_message.to_vec(), None),
+            Some(TransactionType::RawTransaction(raw_transaction)) => {
+                let transaction = Transaction::deserialize(raw_transaction)?;
+                (transaction.message, Some(transaction.signatures))
+            }
+            _ => {
+                let recent_blockhash = decode_pubkey(&sign_input.recent_blockhash)?;
+                let fee_payer = fee_payer(sign_input, &signer_key)?;
+                let instructions = build_instructions(sign_input, &signer_key)?;
+                let message = Message::new(&instructions, &fee_payer, &recent_blockhash);
+                (message.serialize(), None)
+            }
+        };
+
+        let parsed_message = Message::deserialize(&message)?;
+        let signer_index = parsed_message
+            .signer_index(&signer_key)
+            .ok_or(Error::InvalidSignInput)?;
+        let signature = keypair.sign(&message).to_bytes();
+        let mut signatures = signatures.unwrap_or_else(|| {
+            vec![[0u8; SIGNATURE_SIZE]; parsed_message.header.num_required_signatures as usize]
+        });
+        signatures[signer_index] = signature;
+
+        let raw = Transaction {
+            signatures,
+            message: message.to_vec(),
+        }
+        .serialize();
+        let encoded = match Encoding::from_i32(sign_input.encoding) {
+            Some(Encoding::Base58) => base58::encode_slice(&raw),
+            Some(Encoding::Base64) => base64::encode(&raw),
+            None => return Err(Error::InvalidSignInput),
+        };
+        Ok(SignOutput {
+            encoded,
+            raw,
+            signature: base58::encode_slice(&signature),
+            message,
+        })
+    }
+}
+
+impl Signer {
+    /// Sign an off-chain message with ed25519, the message is signed as is
+    pub fn sign_message(private_key: &PrivateKey, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let secret =
+            SecretKey::from_bytes(&private_key.data).map_err(|_| Error::InvalidPrivateKey)?;
+        let public = PublicKey::from(&secret);
+        let keypair = Keypair { secret, public };
+        Ok(keypair.sign(message).to_bytes().to_vec())
+    }
+
+    /// Whether `signature` is an ed25519 signature of `message` by the public key `address`
+    pub fn verify_message(message: &[u8], signature: &[u8], address: &str) -> bool {
+        let public_key = match decode_pubkey(address) {
+            Ok(pubkey) => PublicKey::from_bytes(&pubkey),
+            Err(_) => return false,
+        };
+        let signature = Signature::from_bytes(signature);
+        match (public_key, signature) {
+            (Ok(public_key), Ok(signature)) => public_key.verify(message, &signature).is_ok(),
+            _ => false,
+        }
+    }
+}
+
+// Instructions of the transaction types built by the signer
+fn build_instructions(
+    sign_input: &SignInput,
+    signer_key: &Pubkey,
+) -> Result<Vec<Instruction>, Error> {
+    match &sign_input.transaction_type {
+        Some(TransactionType::TransferTransaction(transfer)) => {
+            let recipient = decode_pubkey(&transfer.recipient)?;
+            let mut instructions = vec![instruction::transfer(
+                signer_key,
+                &recipient,
+                transfer.value,
+            )];
+            if !transfer.memo.is_empty() {
+                instructions.push(instruction::memo(&transfer.memo));
+            }
+            Ok(instructions)
+        }
+        Some(TransactionType::TokenTransferTransaction(transfer)) => {
+            let token_program = token_program(transfer.token_program)?;
+            let mint = decode_pubkey(&transfer.mint)?;
+            let recipient = decode_pubkey(&transfer.recipient)?;
+            let decimals = u8::try_from(transfer.decimals).map_err(|_| Error::InvalidSignInput)?;
+            let mut instructions = vec![];
+            if transfer.create_recipient_account {
+                let fee_payer = fee_payer(sign_input, signer_key)?;
+                instructions.push(token::create_associated_token_account_idempotent(
+                    &fee_payer,
+                    &recipient,
+                    &mint,
+                    token_program,
+                ));
+            }
+            instructions.push(token::transfer_checked(
+                &token::associated_token_address(signer_key, &mint, token_program),
+                &mint,
+                &token::associated_token_address(&recipient, &mint, token_program),
+                signer_key,
+                transfer.amount,
+                decimals,
+                token_program,
+            ));
+            if !transfer.memo.is_empty() {
+                instructions.push(instruction::memo(&transfer.memo));
+            }
+            Ok(instructions)
+        }
+        Some(TransactionType::CreateTokenAccountTransaction(create)) => {
+            let fee_payer = fee_payer(sign_input, signer_key)?;
+            Ok(vec![token::create_associated_token_account_idempotent(
+                &fee_payer,
+                &decode_pubkey(&create.owner)?,
+                &decode_pubkey(&create.mint)?,
+                token_program(create.token_program)?,
+            )])
+        }
+        Some(TransactionType::CloseTokenAccountTransaction(close)) => {
+            let token_program = token_program(close.token_program)?;
+            let mint = decode_pubkey(&close.mint)?;
+            let destination = match close.destination.is_empty() {
+                true => *signer_key,
+                false => decode_pubkey(&close.destination)?,
+            };
+            Ok(vec![token::close_account(
+                &token::associated_token_address(signer_key, &mint, token_program),
+                &destination,
+                signer_key,
+                token_program,
+            )])
+        }
+        Some(TransactionType::RawMessage(_)) | Some(TransactionType::RawTransaction(_)) | None => {
+            Err(Error::InvalidSignInput)
+        }
+    }
+}
+
+fn fee_payer(sign_input: &SignInput, signer_key: &Pubkey) -> Result<Pubkey, Error> {
+    match sign_input.fee_payer.is_empty() {
+        true => Ok(*signer_key),
+        false => decode_pubkey(&sign_input.fee_payer),
+    }
+}
+
+fn token_program(value: i32) -> Result<TokenProgram, Error> {
+    TokenProgram::from_i32(value).ok_or(Error::InvalidSignInput)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transaction::{
+        CompiledInstruction, MessageAddressTableLookup, MessageHeader, MessageVersion,
+    };
+    use chain_common::solana::{CloseTokenAccount, TokenTransfer, Transfer};
+
+    fn private_key() -> PrivateKey {
+        let data = base58::from("A7psj2GW7ZMdY4E5hJq14KMeYg7HFjULSsWSrTXZLvYr").unwrap();
+        PrivateKey::new(&data).unwrap()
+    }
+
+    fn transfer_input() -> SignInput {
+        SignInput {
+            recent_blockhash: "11111111111111111111111111111111".to_owned(),
+            fee_payer: "".to_owned(),
+            encoding: Encoding::Base58 as i32,
+            transaction_type: Some(TransactionType::TransferTransaction(Transfer {
+                recipient: "EN2sCsJ1WDV8UFqsiTXHcUPUxQ4juE71eCknHYYMifkd".to_owned(),
+                value: 42,
+                memo: "".to_owned(),
+            })),
+        }
+    }
+
+    #[test]
+    fn test_sign_transfer() {
+        let output = Signer::sign(&private_key(), &transfer_input()).unwrap();
+        assert_eq!(
+            output.signature,
+            "5T6uZBHnHFd8uWErDBTFRVkbKuhbcm94K5MJ2beTYDruzqv4FjS7EMKvC94ZfxNAiWUXZ6bZxS3WXUbhJwYNPWn"
+        );
+        assert_eq!(output.encoded, "3p2kzZ1DvquqC6LApPuxpTg5CCDVPqJFokGSnGhnBHrta4uq7S2EyehV1XNUVXp51D69GxGzQZUjikfDzbWBG2aFtG3gHT1QfLzyFKHM4HQtMQMNXqay1NAeiiYZjNhx9UvMX4uAQZ4Q6rx6m2AYfQ7aoMUrejq298q1wBFdtS9XVB5QTiStnzC7zs97FUEK2T4XapjF1519EyFBViTfHpGpnf5bfizDzsW9kYUtRDW1UC2LgHr7npgq5W9TBmHf9hSmRgM9XXucjXLqubNWE7HUMhbKjuBqkirRM");
+        assert_eq!(output.raw, base58::from(&output.encoded).unwrap());
+    }
+
+    #[test]
+    fn test_sign_transfer_base64() {
+        let mut input = transfer_input();
+        input.encoding = Encoding::Base64 as i32;
+        let output = Signer::sign(&private_key(), &input).unwrap();
+        assert_eq!(base64::decode(&output.encoded).unwrap(), output.raw);
+    }
+
+    #[test]
+    fn test_sign_transfer_with_fee_payer() {
+        let mut input = transfer_input();
+        input.fee_payer = "2gVkYWexTHR5Hb2aLeQN3tnngvWzisFKXDUPrgMHpdST".to_owned();
+        let output = Signer::sign(&private_key(), &input).unwrap();
+        let message = Message::deserialize(&output.message).unwrap();
+        assert_eq!(message.header.num_required_signatures, 2);
+        assert_eq!(
+            message.account_keys[0],
+            decode_pubkey(&input.fee_payer).unwrap()
+        );
+        // Fee payer's signature is left empty
+        assert_eq!(output.raw[0], 2);
+        assert_eq!(output.raw[1..65], [0u8; 64]);
+        assert_eq!(
+            output.raw[65..129],
+            base58::from(&output.signature).unwrap()[..]
+        );
+    }
+
+    #[test]
+    fn test_sign_raw_message() {
+        let transfer_output = Signer::sign(&private_key(), &transfer_input()).unwrap();
+        let input = SignInput {
+            recent_blockhash: "".to_owned(),
+            fee_payer: "".to_owned(),
+            encoding: Encoding::Base58 as i32,
+            transaction_type: Some(TransactionType::RawMessage(transfer_output.message)),
+        };
+        let output = Signer::sign(&private_key(), &input).unwrap();
+        assert_eq!(output.encoded, transfer_output.encoded);
+
+        // The signer is not a required signer of the message
+        let other_key =
+            PrivateKey::new(&base58::from("2gVkYWexTHR5Hb2aLeQN3tnngvWzisFKXDUPrgMHpdST").unwrap())
+                .unwrap();
+        assert_eq!(
+            Signer::sign(&other_key, &input).unwrap_err(),
+            Error::InvalidSignInput
+        );
+    }
+
+    #[test]
+    fn test_sign_token_transfer() {
+        let input = SignInput {
+            recent_blockhash: "11111111111111111111111111111111".to_owned(),
+            fee_payer: "".to_owned(),
+            encoding: Encoding::Base58 as i32,
+            transaction_type: Some(TransactionType::TokenTransferTransaction(TokenTransfer {
+                mint: "SRMuApVNdxXokk5GT7XD5cUUgXMBCoAz2LHeuAoKWRt".to_owned(),
+                recipient: "EN2sCsJ1WDV8UFqsiTXHcUPUxQ4juE71eCknHYYMifkd".to_owned(),
+                amount: 4000,
+                decimals: 6,
+                token_program: TokenProgram::Token as i32,
+                create_recipient_account: true,
+                memo: "".to_owned(),
+            })),
+        };
+        let output = Signer::sign(&private_key(), &input).unwrap();
+        let message = Message::deserialize(&output.message).unwrap();
+        assert_eq!(message.header.num_required_signatures, 1);
+        assert_eq!(message.instructions.len(), 2);
+
+        let program_id = |index: usize| {
+            message.account_keys[message.instructions[index].program_id_index as usize]
+        };
+        assert_eq!(
+            program_id(0),
+            decode_pubkey(token::ASSOCIATED_TOKEN_PROGRAM_ID).unwrap()
+        );
+        assert_eq!(
+            program_id(1),
+            decode_pubkey(token::TOKEN_PROGRAM_ID).unwrap()
+        );
+        assert_eq!(
+            hex::encode(&message.instructions[1].data),
+            "0ca00f00000000000006"
+        );
+
+        let mut input = input;
+        if let Some(TransactionType::TokenTransferTransaction(transfer)) =
+            &mut input.transaction_type
+        {
+            transfer.decimals = 256;
+        }
+        assert_eq!(
+            Signer::sign(&private_key(), &input).unwrap_err(),
+            Error::InvalidSignInput
+        );
+    }
+
+    #[test]
+    fn test_sign_close_token_account() {
+        let input = SignInput {
+            recent_blockhash: "11111111111111111111111111111111".to_owned(),
+            fee_payer: "".to_owned(),
+            encoding: Encoding::Base58 as i32,
+            transaction_type: Some(TransactionType::CloseTokenAccountTransaction(
+                CloseTokenAccount {
+                    mint: "SRMuApVNdxXokk5GT7XD5cUUgXMBCoAz2LHeuAoKWRt".to_owned(),
+                    token_program: TokenProgram::Token2022 as i32,
+                    destination: "".to_owned(),
+                },
+            )),
+        };
+        let output = Signer::sign(&private_key(), &input).unwrap();
+        let message = Message::deserialize(&output.message).unwrap();
+        let instruction = &message.instructions[0];
+        assert_eq!(instruction.data, vec![9]);
+        assert_eq!(
+            message.account_keys[instruction.program_id_index as usize],
+            decode_pubkey(token::TOKEN_2022_PROGRAM_ID).unwrap()
+        );
+        // The rent goes back to the signer, who is also the fee payer
+        assert_eq!(instruction.accounts[1], 0);
+    }
+
+    #[test]
+    fn test_sign_partially_signed_v0_transaction() {
+        let fee_payer = decode_pubkey("2gVkYWexTHR5Hb2aLeQN3tnngvWzisFKXDUPrgMHpdST").unwrap();
+        let signer_key = decode_pubkey("7v91N7iZ9mNicL8WfG6cgSCKyRXydQjLh6UYBWwm6y1Q").unwrap();
+        let message = Message {
+            version: MessageVersion::V0,
+            header: MessageHeader {
+                num_required_signatures: 2,
+                num_readonly_signed_accounts: 1,
+                num_readonly_unsigned_accounts: 1,
+            },
+            account_keys: vec![fee_payer, signer_key, [0u8; 32]],
+            recent_blockhash: [1u8; 32],
+            instructions: vec![CompiledInstruction {
+                program_id_index: 2,
+                accounts: vec![0, 1, 3],
+                data: vec![2, 0, 0, 0, 42, 0, 0, 0, 0, 0, 0, 0],
+            }],
+            address_table_lookups: vec![MessageAddressTableLookup {
+                account_key: [2u8; 32],
+                writable_indexes: vec![7],
+                readonly_indexes: vec![],
+            }],
+        }
+        .serialize();
+        let fee_payer_signature = [5u8; SIGNATURE_SIZE];
+        let raw_transaction = Transaction {
+            signatures: vec![fee_payer_signature, [0u8; SIGNATURE_SIZE]],
+            message: message.to_vec(),
+        }
+        .serialize();
+
+        let mut input = SignInput {
+            recent_blockhash: "".to_owned(),
+            fee_payer: "".to_owned(),
+            encoding: Encoding::Base64 as i32,
+            transaction_type: Some(TransactionType::RawTransaction(raw_transaction)),
+        };
+        let output = Signer::sign(&private_key(), &input).unwrap();
+        let transaction = Transaction::deserialize(&output.raw).unwrap();
+        assert_eq!(output.message, message);
+        assert_eq!(transaction.signatures[0], fee_payer_signature);
+        assert_eq!(
+            transaction.signatures[1][..],
+            base58::from(&output.signature).unwrap()[..]
+        );
+
+        // The same message signed alone leaves the fee payer's slot empty
+        input.transaction_type = Some(TransactionType::RawMessage(message));
+        let output = Signer::sign(&private_key(), &input).unwrap();
+        let transaction = Transaction::deserialize(&output.raw).unwrap();
+        assert_eq!(transaction.signatures[0], [0u8; SIGNATURE_SIZE]);
+    }
+
+    #[test]
+    fn test_sign_message() {
+        let address = "7v91N7iZ9mNicL8WfG6cgSCKyRXydQjLh6UYBWwm6y1Q";
+        let signature = Signer::sign_message(&private_key(), b"Hello Solana").unwrap();
+        assert_eq!(signature.len(), 64);
+        assert!(Signer::verify_message(b"Hello Solana", &signature, address));
+        assert!(!Signer::verify_message(b"Hello", &signature, address));
+        assert!(!Signer::verify_message(
+            b"Hello Solana",
+            &signature,
+            "EN2sCsJ1WDV8UFqsiTXHcUPUxQ4juE71eCknHYYMifkd"
+        ));
+        assert!(!Signer::verify_message(
+            b"Hello Solana",
+            &signature[1..],
+            address
+        ));
+    }
+}
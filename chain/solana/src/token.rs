@@ -0,0 +1,151 @@
+use super::instruction::{program_id, AccountMeta, Instruction, Pubkey, SYSTEM_PROGRAM_ID};
+use chain_common::solana::TokenProgram;
+use curve25519_dalek::edwards::CompressedEdwardsY;
+use sha2::{Digest, Sha256};
+
+pub const TOKEN_PROGRAM_ID: &str = "TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA";
+pub const TOKEN_2022_PROGRAM_ID: &str = "TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb";
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: &str = "ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL";
+
+const PDA_MARKER: &[u8] = b"ProgramDerivedAddress";
+
+// Indexes of the variants in the Token Program instruction enum, shared by Token-2022
+const CLOSE_ACCOUNT_INDEX: u8 = 9;
+const TRANSFER_CHECKED_INDEX: u8 = 12;
+
+// Index of the `CreateIdempotent` variant in the Associated Token Account Program instruction enum
+const CREATE_IDEMPOTENT_INDEX: u8 = 1;
+
+pub fn token_program_id(token_program: TokenProgram) -> Pubkey {
+    match token_program {
+        TokenProgram::Token => program_id(TOKEN_PROGRAM_ID),
+        TokenProgram::Token2022 => program_id(TOKEN_2022_PROGRAM_ID),
+    }
+}
+
+/// Find the first program derived address off the ed25519 curve, bumping the seed from 255 downwards
+pub fn find_program_address(seeds: &[&[u8]], program_id: &Pubkey) -> Option<(Pubkey, u8)> {
+    (0..=u8::MAX).rev().find_map(|bump| {
+        let mut hasher = Sha256::new();
+        seeds.iter().for_each(|seed| hasher.update(seed));
+        hasher.update([bump]);
+        hasher.update(program_id);
+        hasher.update(PDA_MARKER);
+        let address: Pubkey = hasher.finalize().into();
+        match CompressedEdwardsY(address).decompress() {
+            Some(_) => None,
+            None => Some((address, bump)),
+        }
+    })
+}
+
+/// The associated token account of `owner` for `mint`, a PDA over owner, token program and mint
+pub fn associated_token_address(
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program: TokenProgram,
+) -> Pubkey {
+    let token_program_id = token_program_id(token_program);
+    let (address, _) = find_program_address(
+        &[owner, &token_program_id, mint],
+        &program_id(ASSOCIATED_TOKEN_PROGRAM_ID),
+    )
+    .expect("Unable to find a viable program address bump seed");
+    address
+}
+
+/// Token Program instruction which moves `amount` of `mint` from `source` to `destination` token accounts
+pub fn transfer_checked(
+    source: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    owner: &Pubkey,
+    amount: u64,
+    decimals: u8,
+    token_program: TokenProgram,
+) -> Instruction {
+    let mut data = vec![TRANSFER_CHECKED_INDEX];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(decimals);
+    Instruction {
+        program_id: token_program_id(token_program),
+        accounts: vec![
+            AccountMeta::new(*source, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+        data,
+    }
+}
+
+/// Associated Token Account Program instruction which creates the associated token account of
+/// `owner` for `mint`, it succeeds if the account already exists
+pub fn create_associated_token_account_idempotent(
+    payer: &Pubkey,
+    owner: &Pubkey,
+    mint: &Pubkey,
+    token_program: TokenProgram,
+) -> Instruction {
+    Instruction {
+        program_id: program_id(ASSOCIATED_TOKEN_PROGRAM_ID),
+        accounts: vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(associated_token_address(owner, mint, token_program), false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(program_id(SYSTEM_PROGRAM_ID), false),
+            AccountMeta::new_readonly(token_program_id(token_program), false),
+        ],
+        data: vec![CREATE_IDEMPOTENT_INDEX],
+    }
+}
+
+/// Token Program instruction which closes the token `account` and moves its rent to `destination`
+pub fn close_account(
+    account: &Pubkey,
+    destination: &Pubkey,
+    owner: &Pubkey,
+    token_program: TokenProgram,
+) -> Instruction {
+    Instruction {
+        program_id: token_program_id(token_program),
+        accounts: vec![
+            AccountMeta::new(*account, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new_readonly(*owner, true),
+        ],
+        data: vec![CLOSE_ACCOUNT_INDEX],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::instruction::decode_pubkey;
+
+    #[test]
+    fn test_associated_token_address() {
+        let owner = decode_pubkey("B1iGmDJdvmxyUiYM8UEo2Uw2D58EmUrw4KyLYMmrhf8V").unwrap();
+        let mint = decode_pubkey("SRMuApVNdxXokk5GT7XD5cUUgXMBCoAz2LHeuAoKWRt").unwrap();
+        let address = associated_token_address(&owner, &mint, TokenProgram::Token);
+        assert_eq!(
+            address,
+            decode_pubkey("EDNd1ycsydWYwVmrYZvqYazFqwk1QjBgAUKFjBoz1jKP").unwrap()
+        );
+
+        let address_2022 = associated_token_address(&owner, &mint, TokenProgram::Token2022);
+        assert_ne!(address_2022, address);
+        assert!(CompressedEdwardsY(address_2022).decompress().is_none());
+    }
+
+    #[test]
+    fn test_transfer_checked_data() {
+        let key = [1u8; 32];
+        let instruction =
+            transfer_checked(&key, &key, &key, &key, 4000, 6, TokenProgram::Token2022);
+        assert_eq!(hex::encode(&instruction.data), "0ca00f00000000000006");
+        assert_eq!(instruction.program_id, program_id(TOKEN_2022_PROGRAM_ID));
+        assert!(instruction.accounts[3].is_signer);
+    }
+}
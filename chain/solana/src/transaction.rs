@@ -0,0 +1,483 @@
+use super::instruction::{AccountMeta, Instruction, Pubkey};
+use chain_common::Error;
+
+pub const SIGNATURE_SIZE: usize = 64;
+const PUBKEY_SIZE: usize = 32;
+
+// The highest bit of the first byte of a versioned message is set, the remaining bits hold the version
+const VERSION_PREFIX: u8 = 0x80;
+
+// Account indexes are u8, so a message can't reference more accounts
+const MAX_ACCOUNT_KEYS: usize = 256;
+
+#[derive(Debug, PartialEq)]
+pub enum MessageVersion {
+    Legacy,
+    V0,
+}
+
+pub struct MessageHeader {
+    /// The number of signatures required for this message to be considered valid
+    pub num_required_signatures: u8,
+
+    /// The last `num_readonly_signed_accounts` of the signed keys are read-only accounts
+    pub num_readonly_signed_accounts: u8,
+
+    /// The last `num_readonly_unsigned_accounts` of the unsigned keys are read-only accounts
+    pub num_readonly_unsigned_accounts: u8,
+}
+
+pub struct CompiledInstruction {
+    pub program_id_index: u8,
+    pub accounts: Vec<u8>,
+    pub data: Vec<u8>,
+}
+
+/// Accounts loaded from an address lookup table by a v0 message
+pub struct MessageAddressTableLookup {
+    pub account_key: Pubkey,
+    pub writable_indexes: Vec<u8>,
+    pub readonly_indexes: Vec<u8>,
+}
+
+/// A legacy or v0 transaction message
+pub struct Message {
+    pub version: MessageVersion,
+    pub header: MessageHeader,
+
+    /// The static account keys, the accounts loaded from lookup tables are indexed after them
+    pub account_keys: Vec<Pubkey>,
+    pub recent_blockhash: [u8; 32],
+    pub instructions: Vec<CompiledInstruction>,
+
+    /// Always empty for legacy messages
+    pub address_table_lookups: Vec<MessageAddressTableLookup>,
+}
+
+impl Message {
+    pub fn new(instructions: &[Instruction], payer: &Pubkey, recent_blockhash: &[u8; 32]) -> Self {
+        // The fee payer always comes first, followed by the accounts of each instruction,
+        // the program ids are appended as read-only unsigned accounts
+        let mut metas = vec![AccountMeta::new(*payer, true)];
+        instructions
+            .iter()
+            .flat_map(|instruction| instruction.accounts.iter())
+            .for_each(|meta| merge_account_meta(&mut metas, meta));
+        instructions.iter().for_each(|instruction| {
+            merge_account_meta(
+                &mut metas,
+                &AccountMeta::new_readonly(instruction.program_id, false),
+            )
+        });
+
+        // Stable sort keeps the payer at the first position
+        metas.sort_by_key(|meta| (!meta.is_signer, !meta.is_writable));
+
+        let header = MessageHeader {
+            num_required_signatures: metas.iter().filter(|meta| meta.is_signer).count() as u8,
+            num_readonly_signed_accounts: metas
+                .iter()
+                .filter(|meta| meta.is_signer && !meta.is_writable)
+                .count() as u8,
+            num_readonly_unsigned_accounts: metas
+                .iter()
+                .filter(|meta| !meta.is_signer && !meta.is_writable)
+                .count() as u8,
+        };
+        let account_keys: Vec<Pubkey> = metas.iter().map(|meta| meta.pubkey).collect();
+        let index_of = |pubkey: &Pubkey| {
+            account_keys
+                .iter()
+                .position(|key| key == pubkey)
+                .expect("Account is not compiled") as u8
+        };
+        let compiled_instructions = instructions
+            .iter()
+            .map(|instruction| CompiledInstruction {
+                program_id_index: index_of(&instruction.program_id),
+                accounts: instruction
+                    .accounts
+                    .iter()
+                    .map(|meta| index_of(&meta.pubkey))
+                    .collect(),
+                data: instruction.data.to_vec(),
+            })
+            .collect();
+
+        Message {
+            version: MessageVersion::Legacy,
+            header,
+            account_keys,
+            recent_blockhash: *recent_blockhash,
+            instructions: compiled_instructions,
+            address_table_lookups: vec![],
+        }
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut encoded = vec![];
+        if self.version == MessageVersion::V0 {
+            encoded.push(VERSION_PREFIX);
+        }
+        encoded.extend_from_slice(&[
+            self.header.num_required_signatures,
+            self.header.num_readonly_signed_accounts,
+            self.header.num_readonly_unsigned_accounts,
+        ]);
+        encode_length(self.account_keys.len(), &mut encoded);
+        self.account_keys
+            .iter()
+            .for_each(|key| encoded.extend_from_slice(key));
+        encoded.extend_from_slice(&self.recent_blockhash);
+        encode_length(self.instructions.len(), &mut encoded);
+        for instruction in &self.instructions {
+            encoded.push(instruction.program_id_index);
+            encode_length(instruction.accounts.len(), &mut encoded);
+            encoded.extend_from_slice(&instruction.accounts);
+            encode_length(instruction.data.len(), &mut encoded);
+            encoded.extend_from_slice(&instruction.data);
+        }
+        if self.version == MessageVersion::V0 {
+            encode_length(self.address_table_lookups.len(), &mut encoded);
+            for lookup in &self.address_table_lookups {
+                encoded.extend_from_slice(&lookup.account_key);
+                encode_length(lookup.writable_indexes.len(), &mut encoded);
+                encoded.extend_from_slice(&lookup.writable_indexes);
+                encode_length(lookup.readonly_indexes.len(), &mut encoded);
+                encoded.extend_from_slice(&lookup.readonly_indexes);
+            }
+        }
+        encoded
+    }
+
+    /// Parse and sanitize a serialized legacy or v0 message
+    pub fn deserialize(data: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader { data, offset: 0 };
+        let version = match data.first() {
+            Some(prefix) if prefix & VERSION_PREFIX != 0 => match prefix & !VERSION_PREFIX {
+                0 => {
+                    reader.read_u8()?;
+                    MessageVersion::V0
+                }
+                _ => return Err(Error::InvalidSignInput),
+            },
+            _ => MessageVersion::Legacy,
+        };
+        let header = MessageHeader {
+            num_required_signatures: reader.read_u8()?,
+            num_readonly_signed_accounts: reader.read_u8()?,
+            num_readonly_unsigned_accounts: reader.read_u8()?,
+        };
+        let account_keys = (0..reader.read_length()?)
+            .map(|_| reader.read_pubkey())
+            .collect::<Result<Vec<Pubkey>, Error>>()?;
+        let recent_blockhash = reader.read_pubkey()?;
+        let instructions = (0..reader.read_length()?)
+            .map(|_| {
+                let program_id_index = reader.read_u8()?;
+                let accounts_len = reader.read_length()?;
+                let accounts = reader.read_bytes(accounts_len)?.to_vec();
+                let data_len = reader.read_length()?;
+                let data = reader.read_bytes(data_len)?.to_vec();
+                Ok(CompiledInstruction {
+                    program_id_index,
+                    accounts,
+                    data,
+                })
+            })
+            .collect::<Result<Vec<CompiledInstruction>, Error>>()?;
+        let address_table_lookups = match version {
+            MessageVersion::Legacy => vec![],
+            MessageVersion::V0 => (0..reader.read_length()?)
+                .map(|_| {
+                    let account_key = reader.read_pubkey()?;
+                    let writable_len = reader.read_length()?;
+                    let writable_indexes = reader.read_bytes(writable_len)?.to_vec();
+                    let readonly_len = reader.read_length()?;
+                    let readonly_indexes = reader.read_bytes(readonly_len)?.to_vec();
+                    Ok(MessageAddressTableLookup {
+                        account_key,
+                        writable_indexes,
+                        readonly_indexes,
+                    })
+                })
+                .collect::<Result<Vec<MessageAddressTableLookup>, Error>>()?,
+        };
+        if reader.offset != data.len() {
+            return Err(Error::InvalidSignInput);
+        }
+        let message = Message {
+            version,
+            header,
+            account_keys,
+            recent_blockhash,
+            instructions,
+            address_table_lookups,
+        };
+        message.sanitize()?;
+        Ok(message)
+    }
+
+    fn sanitize(&self) -> Result<(), Error> {
+        let header = &self.header;
+        let num_static_keys = self.account_keys.len();
+        let num_lookup_keys: usize = self
+            .address_table_lookups
+            .iter()
+            .map(|lookup| lookup.writable_indexes.len() + lookup.readonly_indexes.len())
+            .sum();
+        let num_keys = num_static_keys + num_lookup_keys;
+
+        // The fee payer is the first signer and must be writable
+        let valid_header = header.num_required_signatures > 0
+            && header.num_readonly_signed_accounts < header.num_required_signatures
+            && header.num_required_signatures as usize
+                + header.num_readonly_unsigned_accounts as usize
+                <= num_static_keys;
+        let valid_lookups = self.address_table_lookups.iter().all(|lookup| {
+            !lookup.writable_indexes.is_empty() || !lookup.readonly_indexes.is_empty()
+        });
+        // Programs can't be loaded from lookup tables, nor be the fee payer
+        let valid_instructions = self.instructions.iter().all(|instruction| {
+            let program_id_index = instruction.program_id_index as usize;
+            program_id_index > 0
+                && program_id_index < num_static_keys
+                && instruction
+                    .accounts
+                    .iter()
+                    .all(|index| (*index as usize) < num_keys)
+        });
+        let mut unique_keys = self.account_keys.to_vec();
+        unique_keys.sort_unstable();
+        unique_keys.dedup();
+
+        match valid_header
+            && valid_lookups
+            && valid_instructions
+            && num_keys <= MAX_ACCOUNT_KEYS
+            && unique_keys.len() == num_static_keys
+        {
+            true => Ok(()),
+            false => Err(Error::InvalidSignInput),
+        }
+    }
+
+    /// The position of `pubkey` among the required signers, which is also the position of its signature
+    pub fn signer_index(&self, pubkey: &Pubkey) -> Option<usize> {
+        self.account_keys[..self.header.num_required_signatures as usize]
+            .iter()
+            .position(|key| key == pubkey)
+    }
+}
+
+pub struct Transaction {
+    pub signatures: Vec<[u8; SIGNATURE_SIZE]>,
+
+    /// The serialized message
+    pub message: Vec<u8>,
+}
+
+impl Transaction {
+    /// Parse a wire transaction, its signature count must match the signers required by the message
+    pub fn deserialize(data: &[u8]) -> Result<Self, Error> {
+        let mut reader = Reader { data, offset: 0 };
+        let signatures = (0..reader.read_length()?)
+            .map(|_| {
+                <[u8; SIGNATURE_SIZE]>::try_from(reader.read_bytes(SIGNATURE_SIZE)?)
+                    .map_err(|_| Error::InvalidSignInput)
+            })
+            .collect::<Result<Vec<[u8; SIGNATURE_SIZE]>, Error>>()?;
+        let message = data[reader.offset..].to_vec();
+        let parsed_message = Message::deserialize(&message)?;
+        if signatures.len() != parsed_message.header.num_required_signatures as usize {
+            return Err(Error::InvalidSignInput);
+        }
+        Ok(Transaction {
+            signatures,
+            message,
+        })
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut encoded = vec![];
+        encode_length(self.signatures.len(), &mut encoded);
+        self.signatures
+            .iter()
+            .for_each(|signature| encoded.extend_from_slice(signature));
+        encoded.extend_from_slice(&self.message);
+        encoded
+    }
+}
+
+fn merge_account_meta(metas: &mut Vec<AccountMeta>, meta: &AccountMeta) {
+    match metas
+        .iter_mut()
+        .find(|existing| existing.pubkey == meta.pubkey)
+    {
+        Some(existing) => {
+            existing.is_signer |= meta.is_signer;
+            existing.is_writable |= meta.is_writable;
+        }
+        None => metas.push(meta.clone()),
+    }
+}
+
+// Encode the length in the compact-u16 (aka shortvec) format
+fn encode_length(len: usize, output: &mut Vec<u8>) {
+    let mut remaining = len as u16;
+    loop {
+        let mut byte = (remaining & 0x7f) as u8;
+        remaining >>= 7;
+        if remaining == 0 {
+            output.push(byte);
+            break;
+        }
+        byte |= 0x80;
+        output.push(byte);
+    }
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    offset: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        let end = self
+            .offset
+            .checked_add(len)
+            .ok_or(Error::InvalidSignInput)?;
+        let bytes = self
+            .data
+            .get(self.offset..end)
+            .ok_or(Error::InvalidSignInput)?;
+        self.offset = end;
+        Ok(bytes)
+    }
+
+    fn read_u8(&mut self) -> Result<u8, Error> {
+        Ok(self.read_bytes(1)?[0])
+    }
+
+    fn read_pubkey(&mut self) -> Result<Pubkey, Error> {
+        let bytes = self.read_bytes(PUBKEY_SIZE)?;
+        Pubkey::try_from(bytes).map_err(|_| Error::InvalidSignInput)
+    }
+
+    // Decode a compact-u16 length, which takes up to 3 bytes
+    fn read_length(&mut self) -> Result<usize, Error> {
+        let mut len: usize = 0;
+        for i in 0..3 {
+            let byte = self.read_u8()?;
+            len |= ((byte & 0x7f) as usize) << (i * 7);
+            if byte & 0x80 == 0 {
+                return Ok(len);
+            }
+        }
+        Err(Error::InvalidSignInput)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_length() {
+        let cases: [(usize, &str); 6] = [
+            (0, "00"),
+            (0x7f, "7f"),
+            (0x80, "8001"),
+            (0xff, "ff01"),
+            (0x3fff, "ff7f"),
+            (0x4000, "808001"),
+        ];
+        for (len, expected) in cases {
+            let mut output = vec![];
+            encode_length(len, &mut output);
+            assert_eq!(hex::encode(&output), expected);
+
+            let mut reader = Reader {
+                data: &output,
+                offset: 0,
+            };
+            assert_eq!(reader.read_length().unwrap(), len);
+        }
+    }
+
+    #[test]
+    fn test_deserialize_invalid_message() {
+        assert!(Message::deserialize(&[]).is_err());
+        assert!(Message::deserialize(&[1, 0, 1, 1]).is_err());
+        // No required signer
+        let mut message = Message {
+            version: MessageVersion::Legacy,
+            header: MessageHeader {
+                num_required_signatures: 0,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 0,
+            },
+            account_keys: vec![[1u8; 32]],
+            recent_blockhash: [0u8; 32],
+            instructions: vec![],
+            address_table_lookups: vec![],
+        };
+        assert!(Message::deserialize(&message.serialize()).is_err());
+        // Read-only fee payer
+        message.header.num_required_signatures = 1;
+        message.header.num_readonly_signed_accounts = 1;
+        assert!(Message::deserialize(&message.serialize()).is_err());
+        message.header.num_readonly_signed_accounts = 0;
+        assert!(Message::deserialize(&message.serialize()).is_ok());
+        // Unsupported version
+        let mut encoded = message.serialize();
+        encoded.insert(0, VERSION_PREFIX | 1);
+        assert!(Message::deserialize(&encoded).is_err());
+    }
+
+    #[test]
+    fn test_v0_message() {
+        let mut message = Message {
+            version: MessageVersion::V0,
+            header: MessageHeader {
+                num_required_signatures: 1,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 1,
+            },
+            account_keys: vec![[1u8; 32], [2u8; 32]],
+            recent_blockhash: [3u8; 32],
+            instructions: vec![CompiledInstruction {
+                program_id_index: 1,
+                accounts: vec![0, 2, 3],
+                data: vec![1, 2, 3],
+            }],
+            address_table_lookups: vec![MessageAddressTableLookup {
+                account_key: [4u8; 32],
+                writable_indexes: vec![0],
+                readonly_indexes: vec![5],
+            }],
+        };
+        let encoded = message.serialize();
+        assert_eq!(encoded[0], VERSION_PREFIX);
+        let decoded = Message::deserialize(&encoded).unwrap();
+        assert_eq!(decoded.version, MessageVersion::V0);
+        assert_eq!(decoded.address_table_lookups[0].account_key, [4u8; 32]);
+        assert_eq!(decoded.address_table_lookups[0].readonly_indexes, vec![5]);
+        assert_eq!(decoded.serialize(), encoded);
+
+        // Account index out of the loaded accounts
+        message.instructions[0].accounts.push(4);
+        assert!(Message::deserialize(&message.serialize()).is_err());
+        message.instructions[0].accounts.pop();
+        // Program loaded from a lookup table
+        message.instructions[0].program_id_index = 2;
+        assert!(Message::deserialize(&message.serialize()).is_err());
+        message.instructions[0].program_id_index = 1;
+        // Lookup without any index
+        message.instructions[0].accounts = vec![0];
+        message.address_table_lookups[0].writable_indexes.clear();
+        message.address_table_lookups[0].readonly_indexes.clear();
+        assert!(Message::deserialize(&message.serialize()).is_err());
+    }
+}
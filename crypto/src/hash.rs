@@ -24,6 +24,23 @@ impl Hashable for Keccak256 {
     }
 }
 
+pub struct Sha256;
+impl Hashable for Sha256 {
+    fn hash(&self, input: &[u8]) -> Result<Vec<u8>, Error> {
+        use sha2::Digest;
+        Ok(sha2::Sha256::digest(input).to_vec())
+    }
+}
+
+pub struct Blake2b256;
+impl Hashable for Blake2b256 {
+    fn hash(&self, input: &[u8]) -> Result<Vec<u8>, Error> {
+        use blake2::digest::consts::U32;
+        use blake2::{Blake2b, Digest};
+        Ok(Blake2b::<U32>::digest(input).to_vec())
+    }
+}
+
 /* Helper hash functions */
 pub fn compute_mac(derived_key: &[u8], encrypted_text: &[u8]) -> Vec<u8> {
     use tiny_keccak::{Hasher as KeccakHasher, Keccak};
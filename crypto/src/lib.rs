@@ -4,7 +4,9 @@ pub mod curve;
 pub mod hash;
 pub mod kdf_params;
 pub mod key_store_json;
+pub mod polkadot_json;
 pub mod public_key;
+pub mod rsa;
 pub mod scrypt_params;
 
 pub mod bip32;
@@ -14,6 +16,7 @@ pub mod number_util;
 
 pub mod jwk;
 pub mod pbkdf2;
+pub mod sr25519;
 
 #[derive(Debug, PartialOrd, PartialEq)]
 pub enum Error {
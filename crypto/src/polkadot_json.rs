@@ -0,0 +1,242 @@
+use crate::curve::Curve;
+use crate::Error;
+use poly1305::universal_hash::NewUniversalHash;
+use poly1305::{Poly1305, Tag};
+use salsa20::cipher::{generic_array::GenericArray, NewCipher, StreamCipher};
+use salsa20::XSalsa20;
+use scrypt::{scrypt, Params};
+use serde::{Deserialize, Serialize};
+use std::convert::TryInto;
+use std::str::FromStr;
+
+const SUPPORTED_VERSION: &str = "3";
+
+const SALT_SIZE: usize = 32;
+const SCRYPT_PARAMS_SIZE: usize = 12;
+// polkadot-js only encrypts with N = 2^15, p = 1 and r = 8, anything else may be a file asking for
+// an unbounded amount of memory
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_P: u32 = 1;
+const SCRYPT_R: u32 = 8;
+const NONCE_SIZE: usize = 24;
+const KEY_SIZE: usize = 32;
+const TAG_SIZE: usize = 16;
+
+const PKCS8_HEADER: &[u8] = &[48, 83, 2, 1, 1, 48, 5, 6, 3, 43, 101, 112, 4, 34, 4, 32];
+const PKCS8_DIVIDER: &[u8] = &[161, 35, 3, 33, 0];
+const SECRET_KEY_SIZE: usize = 64;
+const PUBLIC_KEY_SIZE: usize = 32;
+
+/// An account exported by polkadot-js, the secret encrypted with scrypt and xsalsa20-poly1305
+#[derive(Serialize, Deserialize)]
+pub struct PolkadotJson {
+    #[serde(default)]
+    pub address: String,
+    pub encoded: String,
+    pub encoding: Encoding,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct Encoding {
+    /// The key encoding followed by the key type, like `["pkcs8", "sr25519"]`
+    pub content: Vec<String>,
+    pub r#type: Vec<String>,
+    pub version: String,
+}
+
+impl FromStr for PolkadotJson {
+    type Err = Error;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        serde_json::from_str(s).map_err(|_| Error::InvalidKeyStoreJson)
+    }
+}
+
+impl PolkadotJson {
+    /// The curve of the account and its secret, a 64 bytes sr25519 secret key or an ed25519 seed
+    pub fn decrypt(&self, password: &[u8]) -> Result<(Curve, Vec<u8>), Error> {
+        let curve = match self.encoding.content.as_slice() {
+            [encoding, key_type] if encoding == "pkcs8" => Curve::from_str(key_type)?,
+            _ => return Err(Error::InvalidKeyStoreJson),
+        };
+        if !matches!(curve, Curve::Ed25519 | Curve::Sr25519) {
+            return Err(Error::NotSupportedCurve);
+        }
+        let is_encrypted_by = |cipher: &str| self.encoding.r#type.iter().any(|t| t == cipher);
+        if self.encoding.version != SUPPORTED_VERSION
+            || !is_encrypted_by("scrypt")
+            || !is_encrypted_by("xsalsa20-poly1305")
+        {
+            return Err(Error::NotSupportedCipher);
+        }
+
+        let encoded = base64::decode(&self.encoded).map_err(|_| Error::InvalidKeyStoreJson)?;
+        let header_size = SALT_SIZE + SCRYPT_PARAMS_SIZE + NONCE_SIZE;
+        if encoded.len() < header_size + TAG_SIZE {
+            return Err(Error::InvalidCiphertext);
+        }
+        let (salt, rest) = encoded.split_at(SALT_SIZE);
+        let (params, rest) = rest.split_at(SCRYPT_PARAMS_SIZE);
+        let (nonce, ciphertext) = rest.split_at(NONCE_SIZE);
+
+        let key = derive_key(password, salt, params)?;
+        let decrypted = open_secretbox(&key, nonce, ciphertext)?;
+        let secret = decode_pkcs8(&decrypted)?;
+
+        match curve {
+            Curve::Sr25519 => {
+                // polkadot-js keeps the sr25519 scalar in the ed25519 form, multiplied by the cofactor
+                let secret = schnorrkel::SecretKey::from_ed25519_bytes(secret)
+                    .map_err(|_| Error::InvalidPrivateKey)?;
+                Ok((curve, secret.to_bytes().to_vec()))
+            }
+            _ => Ok((curve, secret[..KEY_SIZE].to_vec())),
+        }
+    }
+}
+
+/// scrypt key of the password, with N, p and r little endian after the salt
+fn derive_key(password: &[u8], salt: &[u8], params: &[u8]) -> Result<Vec<u8>, Error> {
+    let param = |index: usize| -> u32 {
+        u32::from_le_bytes(params[index * 4..index * 4 + 4].try_into().unwrap())
+    };
+    if (param(0), param(1), param(2)) != (1 << SCRYPT_LOG_N, SCRYPT_P, SCRYPT_R) {
+        return Err(Error::KdfParamsInvalid);
+    }
+    let params =
+        Params::new(SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P).map_err(|_| Error::KdfParamsInvalid)?;
+    let mut output = [0u8; KEY_SIZE * 2];
+    scrypt(password, salt, &params, &mut output).map_err(|_| Error::KdfParamsInvalid)?;
+    Ok(output[..KEY_SIZE].to_vec())
+}
+
+/// Decrypt a NaCl secretbox, the poly1305 tag followed by the xsalsa20 ciphertext
+fn open_secretbox(key: &[u8], nonce: &[u8], sealed: &[u8]) -> Result<Vec<u8>, Error> {
+    let (tag, ciphertext) = sealed.split_at(TAG_SIZE);
+    let mut cipher = XSalsa20::new(
+        GenericArray::from_slice(key),
+        GenericArray::from_slice(nonce),
+    );
+    // The first block of the keystream is the poly1305 key
+    let mut mac_key = [0u8; KEY_SIZE];
+    cipher.apply_keystream(&mut mac_key);
+    let mac = Poly1305::new(GenericArray::from_slice(&mac_key)).compute_unpadded(ciphertext);
+    if mac != Tag::new(*GenericArray::from_slice(tag)) {
+        return Err(Error::PasswordIncorrect);
+    }
+
+    let mut decrypted = ciphertext.to_vec();
+    cipher.apply_keystream(&mut decrypted);
+    Ok(decrypted)
+}
+
+/// The secret key of `header ‖ secret ‖ divider ‖ public`
+fn decode_pkcs8(decoded: &[u8]) -> Result<&[u8], Error> {
+    let secret_end = PKCS8_HEADER.len() + SECRET_KEY_SIZE;
+    if decoded.len() != secret_end + PKCS8_DIVIDER.len() + PUBLIC_KEY_SIZE
+        || !decoded.starts_with(PKCS8_HEADER)
+        || &decoded[secret_end..secret_end + PKCS8_DIVIDER.len()] != PKCS8_DIVIDER
+    {
+        return Err(Error::InvalidPrivateKey);
+    }
+    Ok(&decoded[PKCS8_HEADER.len()..secret_end])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sr25519;
+
+    // The sr25519 root key of the Substrate dev phrase, encrypted with `password`
+    const SR25519_JSON: &str = r#"
+    {
+        "address": "5DfhGyQdFobKM8NsWvEeAKk5EQQgYe9AydgJ7rMB6E1EqRzV",
+        "encoded": "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8AgAAAAQAAAAgAAABkZWZnaGlqa2xtbm9wcXJzdHV2d3h5ens0TnEcVllzAC/J5oJ+9E7pX0/hR0f4SfmZ0gcOukEBz58Pb8O44mCahbJQQtV2p9FIB7KkKMaN2/uAkvIc3+QOof8/9Pmc7tCuRpFul0c0fgifkaMuGEVxU7mwOsB75VYoeZnJDmsNih73/fPVEXYHqcqS2vGeQAGY2mYWzje64eK5YF15",
+        "encoding": {
+            "content": ["pkcs8", "sr25519"],
+            "type": ["scrypt", "xsalsa20-poly1305"],
+            "version": "3"
+        },
+        "meta": {
+            "genesisHash": "",
+            "name": "dev",
+            "whenCreated": 1600000000000
+        }
+    }
+    "#;
+
+    const ED25519_JSON: &str = r#"
+    {
+        "address": "146SvjUZXoMaemdeiecyxgALeYMm8ZWh1yrGo8RtpoPfe7WL",
+        "encoded": "AQIDBAUGBwgJCgsMDQ4PEBESExQVFhcYGRobHB0eHyAAgAAAAQAAAAgAAAAAAQIDBAUGBwgJCgsMDQ4PEBESExQVFheGmP0tzNG+xkx+PWy2vZ6mRG508j96H5qJIpAFPs3FzoMuC4+pkXuSG4TtaMO28K+CHMZuom0DUWJc5VgEOi5nl7BdMaVdDcofJe5xRitq6ewHUpQ2iy1bEgFy7RUDPiTeJoqoMl766fcl1qpMt1iCvKiuDAY040Cw209eGLFEhpXchsJk",
+        "encoding": {
+            "content": ["pkcs8", "ed25519"],
+            "type": ["scrypt", "xsalsa20-poly1305"],
+            "version": "3"
+        },
+        "meta": {}
+    }
+    "#;
+
+    #[test]
+    fn test_decrypt_sr25519() {
+        let json = PolkadotJson::from_str(SR25519_JSON).unwrap();
+        let (curve, secret) = json.decrypt(b"password").unwrap();
+        assert!(matches!(curve, Curve::Sr25519));
+        assert_eq!(secret.len(), SECRET_KEY_SIZE);
+        // The root key of the Substrate dev phrase
+        assert_eq!(
+            hex::encode(sr25519::public_key(&secret).unwrap()),
+            "46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a"
+        );
+        assert_eq!(
+            json.decrypt(b"wrong password").unwrap_err(),
+            Error::PasswordIncorrect
+        );
+    }
+
+    #[test]
+    fn test_decrypt_ed25519() {
+        let json = PolkadotJson::from_str(ED25519_JSON).unwrap();
+        let (curve, secret) = json.decrypt(b"Maskbook123").unwrap();
+        assert!(matches!(curve, Curve::Ed25519));
+        assert_eq!(
+            hex::encode(secret),
+            "abf8e5bdbe30c65656c0a3cbd181ff8a56294a69dfedd27982aace4a76909115"
+        );
+    }
+
+    #[test]
+    fn test_unsupported_encoding() {
+        let json = ED25519_JSON.replace("\"3\"", "\"2\"");
+        let json = PolkadotJson::from_str(&json).unwrap();
+        assert_eq!(
+            json.decrypt(b"Maskbook123").unwrap_err(),
+            Error::NotSupportedCipher
+        );
+        let json = ED25519_JSON.replace("\"ed25519\"", "\"ethereum\"");
+        let json = PolkadotJson::from_str(&json).unwrap();
+        assert_eq!(
+            json.decrypt(b"Maskbook123").unwrap_err(),
+            Error::NotSupportedCurve
+        );
+        assert!(PolkadotJson::from_str("{}").is_err());
+    }
+
+    #[test]
+    fn test_untrusted_scrypt_params() {
+        let mut json = PolkadotJson::from_str(ED25519_JSON).unwrap();
+        let encoded = base64::decode(&json.encoded).unwrap();
+        // N = 2^30 would take 1 TiB of memory, p and r of the weaker or costlier defaults of
+        // other tools aren't accepted either
+        for (index, value) in [(0, 1u32 << 30), (0, 1 << 10), (1, 8), (2, 1)] {
+            let mut tampered = encoded.clone();
+            let offset = SALT_SIZE + index * 4;
+            tampered[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+            json.encoded = base64::encode(&tampered);
+            assert_eq!(
+                json.decrypt(b"Maskbook123").unwrap_err(),
+                Error::KdfParamsInvalid
+            );
+        }
+    }
+}
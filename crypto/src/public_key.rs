@@ -8,6 +8,8 @@ pub enum PublicKeyType {
     Secp256k1,
     Secp256k1Extended,
     Ed25519,
+    Sr25519,
+    Rsa,
 }
 
 impl FromStr for PublicKeyType {
@@ -18,6 +20,8 @@ impl FromStr for PublicKeyType {
             "secp256k1" => Ok(Self::Secp256k1),
             "secp256k1extended" => Ok(Self::Secp256k1Extended),
             "ed25519" => Ok(Self::Ed25519),
+            "sr25519" => Ok(Self::Sr25519),
+            "rsa" => Ok(Self::Rsa),
             _ => Err(()),
         }
     }
@@ -68,6 +72,22 @@ impl PublicKeyConvert for Ed25519Converter {
     }
 }
 
+struct Sr25519Converter;
+
+impl PublicKeyConvert for Sr25519Converter {
+    fn convert(&self, private_key: &[u8]) -> Result<Vec<u8>, Error> {
+        crate::sr25519::public_key(private_key)
+    }
+}
+
+struct RsaConverter;
+
+impl PublicKeyConvert for RsaConverter {
+    fn convert(&self, private_key: &[u8]) -> Result<Vec<u8>, Error> {
+        Ok(crate::rsa::RsaKey::from_der(private_key)?.modulus())
+    }
+}
+
 trait PublicKeyConvert {
     fn convert(&self, private_key: &[u8]) -> Result<Vec<u8>, Error>;
 }
@@ -94,5 +114,7 @@ pub fn get_public_key(
             PublickKeyConvertter::convert(Secp256k1ExtendConverter, private_key)
         }
         PublicKeyType::Ed25519 => PublickKeyConvertter::convert(Ed25519Converter, private_key),
+        PublicKeyType::Sr25519 => PublickKeyConvertter::convert(Sr25519Converter, private_key),
+        PublicKeyType::Rsa => PublickKeyConvertter::convert(RsaConverter, private_key),
     }
 }
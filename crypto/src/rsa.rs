@@ -0,0 +1,162 @@
+use crate::Error;
+use rsa::pkcs1::{self, EncodeRsaPrivateKey};
+use rsa::pss::{BlindedSigningKey, VerifyingKey};
+use rsa::rand_core::OsRng;
+use rsa::signature::{RandomizedSigner, Signature, Verifier};
+use rsa::{BigUint, PublicKeyParts, RsaPrivateKey, RsaPublicKey};
+use sha2::Sha256;
+
+/// The public exponent of the keys on chains which only publish the modulus
+pub const PUBLIC_EXPONENT: u32 = 65537;
+
+/// Keys with a smaller modulus are rejected
+pub const MIN_MODULUS_BITS: usize = 2048;
+
+const SALT_SIZE: usize = 32;
+
+/// A two-prime RSA private key, stored as PKCS#1 DER
+pub struct RsaKey {
+    key: RsaPrivateKey,
+}
+
+impl RsaKey {
+    /// Key of big endian components, in the order of PKCS#1. The CRT components must be the
+    /// ones of the key
+    pub fn from_components(components: [&[u8]; 8]) -> Result<Self, Error> {
+        let [n, e, d, p, q, dp, dq, qi] = components.map(BigUint::from_bytes_be);
+        let key = RsaPrivateKey::from_components(n, e, d, vec![p, q])
+            .map_err(|_| Error::InvalidPrivateKey)?;
+        key.validate().map_err(|_| Error::InvalidPrivateKey)?;
+        if key.n().bits() < MIN_MODULUS_BITS
+            || key.dp() != Some(&dp)
+            || key.dq() != Some(&dq)
+            || key.crt_coefficient() != Some(qi)
+        {
+            return Err(Error::InvalidPrivateKey);
+        }
+        Ok(RsaKey { key })
+    }
+
+    pub fn from_der(der: &[u8]) -> Result<Self, Error> {
+        let key = pkcs1::RsaPrivateKey::try_from(der).map_err(|_| Error::InvalidPrivateKey)?;
+        if key.other_prime_infos.is_some() {
+            return Err(Error::InvalidPrivateKey);
+        }
+        Self::from_components([
+            key.modulus.as_bytes(),
+            key.public_exponent.as_bytes(),
+            key.private_exponent.as_bytes(),
+            key.prime1.as_bytes(),
+            key.prime2.as_bytes(),
+            key.exponent1.as_bytes(),
+            key.exponent2.as_bytes(),
+            key.coefficient.as_bytes(),
+        ])
+    }
+
+    pub fn to_der(&self) -> Result<Vec<u8>, Error> {
+        let der = self
+            .key
+            .to_pkcs1_der()
+            .map_err(|_| Error::InvalidPrivateKey)?;
+        Ok(der.as_bytes().to_vec())
+    }
+
+    /// The big endian modulus
+    pub fn modulus(&self) -> Vec<u8> {
+        self.key.n().to_bytes_be()
+    }
+
+    /// RSASSA-PSS signature of `message`, with SHA-256, MGF1 and a 32 bytes salt
+    pub fn sign_pss(&self, message: &[u8]) -> Result<Vec<u8>, Error> {
+        let signing_key =
+            BlindedSigningKey::<Sha256>::new_with_salt_len(self.key.clone(), SALT_SIZE);
+        let signature = signing_key
+            .try_sign_with_rng(OsRng, message)
+            .map_err(|_| Error::InvalidPrivateKey)?;
+        Ok(signature.as_bytes().to_vec())
+    }
+}
+
+/// Whether `signature` is an RSASSA-PSS signature of `message` by the key of `modulus`
+pub fn verify_pss(modulus: &[u8], message: &[u8], signature: &[u8]) -> bool {
+    let n = BigUint::from_bytes_be(modulus);
+    if n.bits() < MIN_MODULUS_BITS {
+        return false;
+    }
+    let public_key = match RsaPublicKey::new(n, BigUint::from(PUBLIC_EXPONENT)) {
+        Ok(public_key) => public_key,
+        Err(_) => return false,
+    };
+    let signature = match Signature::from_bytes(signature) {
+        Ok(signature) => signature,
+        Err(_) => return false,
+    };
+    VerifyingKey::<Sha256>::new(public_key)
+        .verify(message, &signature)
+        .is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PRIVATE_KEY: &str = "308204a40201000282010100d02b62d2300784b089ef86636bf181c97482b8988ba6ab47a0f70c7c011ce1c73205bba7791fe11d59becae36e6f2e437db2778ec6a920f169a6fb5e268725170255ca0c983bfdabf0d4a77f41ec141737cdc0b1dae1009ab9c467d62455210e0b9d31a26b7df50dd1c2b13696ee787c118a0c6560136c4a6982d21436125f25c16c9239c4507a460cb1490fb7b5ff89f33aa236216d8d5410d3401d94af5da1ba2ca44ce42e9192c35b29346c2edba3e120cce387fc743ae45f32cc4199abeb286a6ce3deabe19483da2859161b4f5282069afe612de19fe853df6007623095783eff0ba79096daf8daf80147556869a9062145326577ba39565ce68368a53302030100010282010007caa7ef4016f7f377a63c7c7a2cd6f3c865a02d2d84f6823ed720637fd329ec3fc611290a9293e57a5776d69e6a90ebbbc7b34be7c9aacefe6a5e9c3e8aee6b770de8fb21b395221b688a406c223efa437f7307f52e58cc71c9952d61bcc1f8f99c4fa049afb43d692d855e8ab5b052cc814347841a16028f027384882595e40d97d6d2ccdfade2b336d82d703e30c5a09506780093b892b89cee072d5f27663633e0be311e9171c4e42c1fdddac280e241ffdfddb8af4750e5c684a36c0ff154d4187b143a51b162185aac53b694941f8806729c913401c6b6dca745d542c9dc13ea3c0f496cb7d23f9e19741a3cc3909a938dc73d193431fa2bf976760ed902818100eebb444946678b463702ebca8d392a69d66ac054e741f09c152b076d4798bd2f1a1f0eea8abeb16d622afdee047f9f1fa6b417ec9221aa377f99d791e201927e1d2c56a43509338e2a2c0d693c72205a48b060da57a9dfe3ded4fb0720dad7d95f41d91024c39279139ca05f952e27bb6876b5eef072420625b7f0596190b05502818100df3a2f15fd492e34015a1a72efdff99119823b50ae5aa9ed303008abc59789a89f35749b732296be8c4bb80e27f5548e95c0e9c93007fa5531efe48675a550b25d39fc091c99d139d2a9bf06fa70b173fc5fb9eaf318df16a70404ff9ec6bbddb7545cc670ccced1abb1bafa11c7fbed3f9b321c0b971dc61cc3afb1b7c1e7670281810099425d9750fc7e3be86b16a6a53d12ce795974f9ccb0d75fcc732044769746b4ab674ef32179447c7d6e2e309470bddddaee790165daf8e4c22528703e105654623429b727592de0d190105803f71c6ff98449d942380b44c5b42327b35eeafd609aec57e1d230014ca6db8a21424d3d91d5cbfc861e43e6a06652857f0e1d350281803d865cd9f3b08ad27c98e7f74bd3f0862480b7767a47b4d411d6fb5cd49dfaac060d86b11d9fa7eaa6e26bbd4318241114875228485bba8c66b216cb547b96ed4232ac1b9acd6e544092d67ac6e5ee92e8393e15daa0bd44120e528e5da5aabbe63e933f827180835b8e25d9acb2f0e5a8054f5f597672175b7037e9849b25fb02818100cc72350d07d65bfef521506a221508ffa30f67ac519e784a105c9eb84414c142199b7dc151b98bd8ccca5adc57460bca1026459ebbf0b80d033596fcbbb569bd396f8c39c2284dff8f17e3e705fba6a03f6ba26452b3e105601b4c74d0c3321f76bb107c753691b82753ca17ddbd6d715c15f1e88078520a4a8a47d245eb932b";
+
+    // A 1024 bits key
+    const WEAK_PRIVATE_KEY: &str = "3082025d02010002818100c5c0b5d8c344db875bbe308c332e5e0cff0289d91a55e81481326bb0545681a3d3feaee755f2ce9d5adfe08a6a60dd16a6b0be4aee2986128f88be59d2a45acb322365d21657f8f61e1bd40ca07ad46a793f506bb7959836db03d3f0cc88c4f4bfe0f92cd3ab1dfe8692a56be68626b26160d9c241cb117161cf23b1d2479747020301000102818100bf29a8606f7b4be0109ecd1d6a8883207b0f80241fe235a444b4720795afbe4899b154708ad45a4920c4d4b1d95de3bb76cdda5662de3f1e0824bf25e38b3fbc0318a979538009b3ade4b3c46e0080c73033ce9cded9f7acc4dd322caf9476016da7354b1d7b27bb01027cc6e675063d0e6425a740d7f2da0b9cffe4d9812ed9024100f4fe44455bf6d5de80b172e89254da333a0ba88f92939f901d3f5592708aea2605f56b166f0f16df04fbc2c9de2353de3315ca51562312fd11ad9466e2ed65a3024100cea31e512c91804ec737732fce970fbe1f5a1092618947f547f6fbe0bd0622d715881f3dd949b7001d27f6fd3ad1f7e73f3f6f429a30153c978335792a2bba0d0240656e03dbe7683941666c911362ca08909ede86c9f238761e574d8a63177f1bd3e431a845607391d7819bae189f8466cb9ec0cdc380f302808dda1c8fc031e31b024100a19729f42cb833f06715d3bc050058cd42d339b65f13edf3c02d42d782ba33b0a323e9da81fb85624f6a9b5978100e9f05f4e35ae01ccddeae556b11af8ab659024072beabeaa6daf9a9ff74c41622c4b4c37cabbbf41799cb9a9fdde75611acb299554d72828be02512f9adb9455372f01e7a0fbf96d2f386e84cee375c9a019274";
+
+    #[test]
+    fn test_der_roundtrip() {
+        let der = hex::decode(PRIVATE_KEY).unwrap();
+        let key = RsaKey::from_der(&der).unwrap();
+        assert_eq!(key.to_der().unwrap(), der);
+        assert_eq!(
+            hex::encode(key.modulus()),
+            "d02b62d2300784b089ef86636bf181c97482b8988ba6ab47a0f70c7c011ce1c73205bba7791fe11d59becae36e6f2e437db2778ec6a920f169a6fb5e268725170255ca0c983bfdabf0d4a77f41ec141737cdc0b1dae1009ab9c467d62455210e0b9d31a26b7df50dd1c2b13696ee787c118a0c6560136c4a6982d21436125f25c16c9239c4507a460cb1490fb7b5ff89f33aa236216d8d5410d3401d94af5da1ba2ca44ce42e9192c35b29346c2edba3e120cce387fc743ae45f32cc4199abeb286a6ce3deabe19483da2859161b4f5282069afe612de19fe853df6007623095783eff0ba79096daf8daf80147556869a9062145326577ba39565ce68368a533"
+        );
+        assert!(RsaKey::from_der(&der[..der.len() - 1]).is_err());
+        assert!(RsaKey::from_der(&[0u8; 32]).is_err());
+    }
+
+    #[test]
+    fn test_invalid_key() {
+        let der = hex::decode(WEAK_PRIVATE_KEY).unwrap();
+        assert_eq!(RsaKey::from_der(&der).err(), Some(Error::InvalidPrivateKey));
+
+        let der = hex::decode(PRIVATE_KEY).unwrap();
+        let key = pkcs1::RsaPrivateKey::try_from(der.as_slice()).unwrap();
+        let components = [
+            key.modulus.as_bytes(),
+            key.public_exponent.as_bytes(),
+            key.private_exponent.as_bytes(),
+            key.prime1.as_bytes(),
+            key.prime2.as_bytes(),
+            key.exponent1.as_bytes(),
+            key.exponent2.as_bytes(),
+            key.coefficient.as_bytes(),
+        ];
+        assert!(RsaKey::from_components(components).is_ok());
+        // Every private component must be the one of the key
+        for index in 2..8 {
+            let mut tampered = components[index].to_vec();
+            *tampered.last_mut().unwrap() ^= 0x02;
+            let mut invalid = components;
+            invalid[index] = &tampered;
+            assert!(RsaKey::from_components(invalid).is_err());
+        }
+    }
+
+    #[test]
+    fn test_sign_pss() {
+        let key = RsaKey::from_der(&hex::decode(PRIVATE_KEY).unwrap()).unwrap();
+        let signature = key.sign_pss(b"Hello Mask").unwrap();
+        assert_eq!(signature.len(), 256);
+        assert!(verify_pss(&key.modulus(), b"Hello Mask", &signature));
+        assert!(!verify_pss(&key.modulus(), b"Hello", &signature));
+
+        // A signature made by OpenSSL
+        let signature = hex::decode("33728f3395b01b4a810c9499877a4d104cca89fecdd757ed825f8def871def987f96c94b48038812079107658df49a09513e222dfa96f5039cc2beac5a5e2de8f1af6412cbfed286648e0462e570992dca7e05c70ce918ee6ed84cfde6b803baadba672229db816725364b5d8ee457e8ce289b670471446646bdd5c9495f535ba35b4da8664a90090dd36fda6e8e35ebec139a4e69288ea1cf50bf73d1979e4559d29d25c0669c5355db96e02bb77546483ec1f58546c8b4a0b11ec200f889e5ae6f1d33e0b0f075ede4285d4565a558959b01f47289802184c2908057a5467c8d506fddc8fda700839825ab10635a59174f88c7937a624c2a7c7fcdcccb0e46").unwrap();
+        assert!(verify_pss(&key.modulus(), b"Hello Mask", &signature));
+    }
+}
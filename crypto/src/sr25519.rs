@@ -0,0 +1,142 @@
+use crate::hash::{Blake2b256, Hasher};
+use crate::Error;
+use hmac::Hmac;
+use schnorrkel::derive::{ChainCode, Derivation};
+use schnorrkel::{ExpansionMode, MiniSecretKey, SecretKey};
+use sha2::Sha512;
+
+pub const SECRET_KEY_SIZE: usize = 64;
+
+const CHAIN_CODE_SIZE: usize = 32;
+
+/// A Substrate derivation junction, `//hard` or `/soft`
+pub struct Junction {
+    chain_code: [u8; CHAIN_CODE_SIZE],
+    hard: bool,
+}
+
+impl Junction {
+    /// Numeric names are encoded as u64, others as SCALE strings hashed when longer than 32 bytes
+    pub fn new(name: &str, hard: bool) -> Result<Self, Error> {
+        let mut encoded = match name.parse::<u64>() {
+            Ok(index) => index.to_le_bytes().to_vec(),
+            Err(_) => [compact_length(name.len()), name.as_bytes().to_vec()].concat(),
+        };
+        if encoded.len() > CHAIN_CODE_SIZE {
+            encoded = Hasher::hash(Blake2b256, &encoded)?;
+        }
+        let mut chain_code = [0u8; CHAIN_CODE_SIZE];
+        chain_code[..encoded.len()].copy_from_slice(&encoded);
+        Ok(Junction { chain_code, hard })
+    }
+}
+
+/// Substrate mini-secret of a BIP39 entropy, derived from the entropy rather than the BIP39 seed
+pub fn mini_secret_from_entropy(entropy: &[u8], password: &str) -> Result<[u8; 32], Error> {
+    if entropy.len() < 16 || entropy.len() > 32 || entropy.len() & 3 != 0 {
+        return Err(Error::InvalidMnemonic);
+    }
+    let salt = format!("mnemonic{}", password);
+    let mut seed = [0u8; 64];
+    pbkdf2::pbkdf2::<Hmac<Sha512>>(entropy, salt.as_bytes(), 2048, &mut seed);
+
+    let mut mini_secret = [0u8; 32];
+    mini_secret.copy_from_slice(&seed[..32]);
+    Ok(mini_secret)
+}
+
+/// 64 bytes secret key of the account at `junctions` from the BIP39 entropy
+pub fn derive_secret_key(
+    entropy: &[u8],
+    password: &str,
+    junctions: &[Junction],
+) -> Result<Vec<u8>, Error> {
+    let mini_secret = mini_secret_from_entropy(entropy, password)?;
+    let root = MiniSecretKey::from_bytes(&mini_secret)
+        .map_err(|_| Error::InvalidSeed)?
+        .expand(ExpansionMode::Ed25519);
+    let secret = junctions.iter().fold(root, |secret, junction| {
+        let chain_code = ChainCode(junction.chain_code);
+        match junction.hard {
+            true => secret
+                .hard_derive_mini_secret_key(Some(chain_code), b"")
+                .0
+                .expand(ExpansionMode::Ed25519),
+            false => secret.derived_key_simple(chain_code, []).0,
+        }
+    });
+    Ok(secret.to_bytes().to_vec())
+}
+
+/// Secret key of a 64 bytes private key, or of a 32 bytes mini-secret
+pub fn secret_key(private_key: &[u8]) -> Result<SecretKey, Error> {
+    match private_key.len() {
+        SECRET_KEY_SIZE => SecretKey::from_bytes(private_key).map_err(|_| Error::InvalidPrivateKey),
+        _ => MiniSecretKey::from_bytes(private_key)
+            .map(|mini_secret| mini_secret.expand(ExpansionMode::Ed25519))
+            .map_err(|_| Error::InvalidPrivateKey),
+    }
+}
+
+pub fn public_key(private_key: &[u8]) -> Result<Vec<u8>, Error> {
+    Ok(secret_key(private_key)?.to_public().to_bytes().to_vec())
+}
+
+fn compact_length(length: usize) -> Vec<u8> {
+    match length {
+        0..=0x3f => vec![(length << 2) as u8],
+        0x40..=0x3fff => (((length << 2) | 0b01) as u16).to_le_bytes().to_vec(),
+        _ => (((length << 2) | 0b10) as u32).to_le_bytes().to_vec(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bip39::Mnemonic;
+
+    const DEV_PHRASE: &str =
+        "bottom drive obey lake curtain smoke basket hold race lonely fit walk";
+
+    fn public_key_at(path: &[(&str, bool)], password: &str) -> String {
+        let entropy = Mnemonic::new(DEV_PHRASE, "").unwrap().entropy;
+        let junctions: Vec<Junction> = path
+            .iter()
+            .map(|(name, hard)| Junction::new(name, *hard).unwrap())
+            .collect();
+        let secret = derive_secret_key(&entropy, password, &junctions).unwrap();
+        hex::encode(public_key(&secret).unwrap())
+    }
+
+    #[test]
+    fn test_derive_dev_accounts() {
+        assert_eq!(
+            public_key_at(&[], ""),
+            "46ebddef8cd9bb167dc30878d7113b7e168e6f0646beffd77d69d39bad76b47a"
+        );
+        assert_eq!(
+            public_key_at(&[("Alice", true)], ""),
+            "d43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d"
+        );
+        assert_eq!(
+            public_key_at(&[("Bob", true)], ""),
+            "8eaf04151687736326c9fea17e25fc5287613693c912909cb226aa4794f26a48"
+        );
+        assert_ne!(
+            public_key_at(&[("Alice", true), ("0", false)], ""),
+            public_key_at(&[("Alice", true), ("0", true)], "")
+        );
+        assert_ne!(public_key_at(&[], "password"), public_key_at(&[], ""));
+    }
+
+    #[test]
+    fn test_junction_chain_code() {
+        let junction = Junction::new("Alice", true).unwrap();
+        assert_eq!(hex::encode(&junction.chain_code[..6]), "14416c696365");
+        let junction = Junction::new("1", false).unwrap();
+        assert_eq!(hex::encode(&junction.chain_code[..8]), "0100000000000000");
+        // Long names are hashed
+        let junction = Junction::new(&"a".repeat(40), true).unwrap();
+        assert_ne!(junction.chain_code[0], 40 << 2);
+    }
+}
@@ -14,11 +14,22 @@ lazy_static! {
         let mut coins_map: HashMap<String, Coin> = HashMap::new();
 
         coins.into_iter().for_each(|mut coin| {
+            // Transactions are only signed for the chain id of their network
+            assert!(
+                coin.blockchain != "Ethereum" || coin.chain_id.is_some(),
+                "EVM network {} has no chain id",
+                coin.id
+            );
             coin.all_info = coins_info_hashmaps.remove(0);
-            coins_map.insert(coin.name.to_lowercase(), coin);
+            coins_map.insert(coin.id.to_owned(), coin);
         });
         coins_map
     };
+    // Ids of the EVM networks by their chain id
+    static ref CHAIN_IDS_MAP: HashMap<u64, String> = COINS_MAP
+        .values()
+        .filter_map(|coin| coin.chain_id.map(|chain_id| (chain_id, coin.id.to_owned())))
+        .collect();
 }
 
 pub fn get_coin_info(coin_type: i32) -> Option<&'static Coin> {
@@ -28,13 +39,65 @@ pub fn get_coin_info(coin_type: i32) -> Option<&'static Coin> {
     }
 }
 
+/// Find a coin of the registry by its id, e.g. "polygon" for an EVM network
+pub fn get_coin_info_by_id(id: &str) -> Option<&'static Coin> {
+    COINS_MAP.get(id)
+}
+
+/// Find the EVM network with `chain_id`, none if the registry doesn't list it
+pub fn get_coin_info_by_chain_id(chain_id: u64) -> Option<&'static Coin> {
+    CHAIN_IDS_MAP
+        .get(&chain_id)
+        .and_then(|id| COINS_MAP.get(id))
+}
+
+/// The coin selected by the id of a network in the registry, or by its type if no network is set
+pub fn get_selected_coin_info(coin_type: i32, network: &str) -> Option<&'static Coin> {
+    if network.is_empty() {
+        get_coin_info(coin_type)
+    } else {
+        get_coin_info_by_id(network)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::COINS_MAP;
+    use super::{CHAIN_IDS_MAP, COINS_MAP};
     #[test]
     fn test_get_coin_info() {
-        assert_eq!(COINS_MAP.len(), 4);
+        assert_eq!(COINS_MAP.len(), 12);
         let coin_info = COINS_MAP.get("ethereum").unwrap();
         assert_eq!(coin_info.curve, "secp256k1");
+        assert_eq!(coin_info.chain_id, Some(1));
+        // No two networks share a chain id
+        let evm_networks = COINS_MAP.values().filter(|coin| coin.chain_id.is_some());
+        assert_eq!(evm_networks.count(), CHAIN_IDS_MAP.len());
+    }
+
+    #[test]
+    fn test_get_evm_network_info() {
+        use super::{get_coin_info_by_chain_id, get_coin_info_by_id, get_selected_coin_info};
+        use chain_common::api::Coin as CoinType;
+
+        let polygon = get_coin_info_by_id("polygon").unwrap();
+        assert_eq!(polygon.blockchain, "Ethereum");
+        assert_eq!(polygon.symbol, "POL");
+        assert_eq!(polygon.chain_id, Some(137));
+        assert_eq!(get_coin_info_by_id("bsc").unwrap().chain_id, Some(56));
+        assert_eq!(get_coin_info_by_id("solana").unwrap().chain_id, None);
+        assert_eq!(
+            get_coin_info_by_id("sepolia").unwrap().chain_id,
+            Some(11155111)
+        );
+
+        assert_eq!(get_coin_info_by_chain_id(8453).unwrap().id, "base");
+        assert_eq!(get_coin_info_by_chain_id(11155111).unwrap().id, "sepolia");
+        assert_eq!(get_coin_info_by_chain_id(1).unwrap().id, "ethereum");
+        assert!(get_coin_info_by_chain_id(5).is_none());
+
+        let selected = get_selected_coin_info(CoinType::Ethereum as i32, "arbitrum").unwrap();
+        assert_eq!(selected.chain_id, Some(42161));
+        let selected = get_selected_coin_info(CoinType::Ethereum as i32, "").unwrap();
+        assert_eq!(selected.id, "ethereum");
     }
 }
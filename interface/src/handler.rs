@@ -31,6 +31,23 @@ pub fn dispatch_request(request: mw_request::Request) -> MwResponse {
             account::create_stored_key_account_of_coin_at_path(param)
         }
         ParamSignTransaction(param) => sign::sign_transaction(param),
+        ParamSignTypedData(param) => sign::sign_typed_data(param),
+        ParamSignMessage(param) => sign::sign_message(param),
+        ParamVerifyMessage(param) => sign::verify_message(param),
+        ParamRecoverSigner(param) => sign::recover_signer(param),
+        ParamDecodeTransaction(param) => sign::decode_transaction(param),
+        ParamAbiEncode(param) => sign::abi_encode(param),
+        ParamAbiDecode(param) => sign::abi_decode(param),
+        ParamPreviewTransaction(param) => sign::preview_transaction(param),
+        ParamSiweBuild(param) => sign::siwe_build(param),
+        ParamSiweValidate(param) => sign::siwe_validate(param),
+        ParamSiweSign(param) => sign::siwe_sign(param),
+        ParamSignUserOperation(param) => sign::sign_user_operation(param),
+        ParamComputeAccountAddress(param) => sign::compute_account_address(param),
+        ParamSignSafeTransaction(param) => sign::sign_safe_transaction(param),
+        ParamCombineSafeSignatures(param) => sign::combine_safe_signatures(param),
+        ParamSignPermit(param) => sign::sign_permit(param),
+        ParamSignAuthorization(param) => sign::sign_authorization(param),
 
         ParamValidation(param) => validate::validate(param),
 
@@ -1,14 +1,19 @@
-use crate::coins::get_coin_info;
+use crate::coins::{get_coin_info, get_coin_info_by_chain_id, get_selected_coin_info};
 use crate::encode_message;
 use crate::response_util::*;
+use ::ethereum::signer::Signer as EthereumSigner;
+use ::ethereum::{abi, decoder, preview, recovery, safe, siwe, user_operation};
 use chain_common::api::mw_response::Response;
 use chain_common::api::*;
-use chain_common::ethereum;
+use chain_common::coin::Coin as CoinInfo;
+use chain_common::{arweave, ethereum, polkadot, solana};
 use prost::Message;
+use std::str::FromStr;
+use wallet::coin_dispatcher::CoinDispatcher;
 use wallet::stored_key::StoredKey;
 
 pub fn sign_transaction(param: SignTransactionParam) -> MwResponse {
-    let coin_info = get_coin_info(param.coin);
+    let coin_info = get_selected_coin_info(param.coin, &param.network);
     let coin = match coin_info {
         Some(coin_info) => coin_info,
         None => {
@@ -37,8 +42,25 @@ pub fn sign_transaction(param: SignTransactionParam) -> MwResponse {
             };
         }
     };
-    let sign_transaction_param::Input::SignInput(chain_input) = input_struct;
-    let encoded_input = match encode_message(&chain_input) {
+    // The input must be the one of the selected coin's blockchain
+    let input_blockchain = match &input_struct {
+        sign_transaction_param::Input::SignInput(_) => Coin::Ethereum,
+        sign_transaction_param::Input::SolanaSignInput(_) => Coin::Solana,
+        sign_transaction_param::Input::PolkadotSignInput(_) => Coin::Polkadot,
+        sign_transaction_param::Input::ArweaveSignInput(_) => Coin::Arweave,
+    };
+    if Coin::from_str(&coin.blockchain).ok() != Some(input_blockchain) {
+        return get_invalid_proto_resposne();
+    }
+    let encoded_input = match &input_struct {
+        sign_transaction_param::Input::SignInput(chain_input) => encode_message(chain_input),
+        sign_transaction_param::Input::SolanaSignInput(chain_input) => encode_message(chain_input),
+        sign_transaction_param::Input::PolkadotSignInput(chain_input) => {
+            encode_message(chain_input)
+        }
+        sign_transaction_param::Input::ArweaveSignInput(chain_input) => encode_message(chain_input),
+    };
+    let encoded_input = match encoded_input {
         Ok(encoded) => encoded,
         Err(_) => {
             return MwResponse {
@@ -61,8 +83,24 @@ pub fn sign_transaction(param: SignTransactionParam) -> MwResponse {
         }
     };
 
-    let ethereum::SignInput { .. } = chain_input;
-    let decoded_output_result = ethereum::SignOutput::decode(&sign_output[..]);
+    let decoded_output_result = match input_struct {
+        sign_transaction_param::Input::SignInput(_) => {
+            ethereum::SignOutput::decode(&sign_output[..])
+                .map(sign_transaction_resp::Output::SignOutput)
+        }
+        sign_transaction_param::Input::SolanaSignInput(_) => {
+            solana::SignOutput::decode(&sign_output[..])
+                .map(sign_transaction_resp::Output::SolanaSignOutput)
+        }
+        sign_transaction_param::Input::PolkadotSignInput(_) => {
+            polkadot::SignOutput::decode(&sign_output[..])
+                .map(sign_transaction_resp::Output::PolkadotSignOutput)
+        }
+        sign_transaction_param::Input::ArweaveSignInput(_) => {
+            arweave::SignOutput::decode(&sign_output[..])
+                .map(sign_transaction_resp::Output::ArweaveSignOutput)
+        }
+    };
 
     let decoded_output = match decoded_output_result {
         Ok(decoded) => decoded,
@@ -78,7 +116,428 @@ pub fn sign_transaction(param: SignTransactionParam) -> MwResponse {
 
     MwResponse {
         response: Some(Response::RespSignTransaction(SignTransactionResp {
-            output: Some(sign_transaction_resp::Output::SignOutput(decoded_output)),
+            output: Some(decoded_output),
+        })),
+    }
+}
+
+pub fn sign_typed_data(param: SignTypedDataParam) -> MwResponse {
+    let coin = match get_selected_coin_info(param.coin, &param.network) {
+        Some(coin_info) => coin_info,
+        None => {
+            return MwResponse {
+                response: Some(Response::Error(MwResponseError {
+                    error_code: "-1".to_owned(),
+                    error_msg: "Invalid Coin Type".to_owned(),
+                })),
+            };
+        }
+    };
+    let mut stored_key: StoredKey = match serde_json::from_slice(&param.stored_key_data) {
+        Ok(key) => key,
+        Err(_) => {
+            return get_json_error_response();
+        }
+    };
+    let version = match ethereum::TypedDataVersion::from_i32(param.version) {
+        Some(version) => version,
+        None => return get_invalid_proto_resposne(),
+    };
+    match stored_key.sign_typed_data(
+        coin,
+        &param.password,
+        &param.derivation_path,
+        &param.typed_data,
+        version,
+    ) {
+        Ok(signature) => MwResponse {
+            response: Some(Response::RespSignTypedData(SignTypedDataResp { signature })),
+        },
+        Err(error) => get_error_response_by_error(error),
+    }
+}
+
+pub fn sign_message(param: SignMessageParam) -> MwResponse {
+    let coin = match get_selected_coin_info(param.coin, &param.network) {
+        Some(coin_info) => coin_info,
+        None => {
+            return MwResponse {
+                response: Some(Response::Error(MwResponseError {
+                    error_code: "-1".to_owned(),
+                    error_msg: "Invalid Coin Type".to_owned(),
+                })),
+            };
+        }
+    };
+    let mut stored_key: StoredKey = match serde_json::from_slice(&param.stored_key_data) {
+        Ok(key) => key,
+        Err(_) => {
+            return get_json_error_response();
+        }
+    };
+    match stored_key.sign_message(
+        coin,
+        &param.password,
+        &param.derivation_path,
+        &param.message,
+    ) {
+        Ok(signature) => MwResponse {
+            response: Some(Response::RespSignMessage(SignMessageResp { signature })),
+        },
+        Err(error) => get_error_response_by_error(error),
+    }
+}
+
+pub fn verify_message(param: VerifyMessageParam) -> MwResponse {
+    let coin = match get_coin_info(param.coin) {
+        Some(coin_info) => coin_info,
+        None => {
+            return MwResponse {
+                response: Some(Response::Error(MwResponseError {
+                    error_code: "-1".to_owned(),
+                    error_msg: "Invalid Coin Type".to_owned(),
+                })),
+            };
+        }
+    };
+    let entry = match CoinDispatcher::get_entry(coin) {
+        Ok(entry) => entry,
+        Err(error) => {
+            return get_error_response_by_error(error);
+        }
+    };
+    match entry.verify_message(coin, &param.message, &param.signature, &param.address) {
+        Ok(valid) => MwResponse {
+            response: Some(Response::RespVerifyMessage(VerifyMessageResp { valid })),
+        },
+        Err(error) => get_error_response_by_error(error.into()),
+    }
+}
+
+pub fn recover_signer(param: RecoverSignerParam) -> MwResponse {
+    let (r, s, v) = (&param.r, &param.s, param.v);
+    let recovered = match &param.input {
+        Some(recover_signer_param::Input::Hash(hash)) => recovery::recover_from_hash(hash, r, s, v),
+        Some(recover_signer_param::Input::Message(message)) => {
+            recovery::recover_from_message(message, r, s, v)
+        }
+        Some(recover_signer_param::Input::TypedData(typed_data)) => {
+            match ethereum::TypedDataVersion::from_i32(param.typed_data_version) {
+                Some(version) => recovery::recover_from_typed_data(typed_data, version, r, s, v),
+                None => return get_invalid_proto_resposne(),
+            }
+        }
+        None => return get_invalid_proto_resposne(),
+    };
+    match recovered {
+        Ok(recovered) => MwResponse {
+            response: Some(Response::RespRecoverSigner(RecoverSignerResp {
+                public_key: recovered.public_key,
+                address: recovered.address,
+            })),
+        },
+        Err(error) => get_error_response_by_error(error.into()),
+    }
+}
+
+pub fn decode_transaction(param: DecodeTransactionParam) -> MwResponse {
+    match decoder::decode_transaction(&param.raw_transaction) {
+        Ok(transaction) => MwResponse {
+            response: Some(Response::RespDecodeTransaction(DecodeTransactionResp {
+                transaction: Some(transaction),
+            })),
+        },
+        Err(error) => get_error_response_by_error(error.into()),
+    }
+}
+
+pub fn abi_encode(param: AbiEncodeParam) -> MwResponse {
+    let args: serde_json::Value = match serde_json::from_str(&param.args) {
+        Ok(args) => args,
+        Err(_) => return get_json_error_response(),
+    };
+    let encoded = abi::find_function(&param.abi, &param.function_name).and_then(|function| {
+        let data = function.encode_input(&args)?;
+        Ok((function.signature(), data))
+    });
+    match encoded {
+        Ok((signature, data)) => MwResponse {
+            response: Some(Response::RespAbiEncode(AbiEncodeResp { data, signature })),
+        },
+        Err(error) => get_error_response_by_error(error.into()),
+    }
+}
+
+pub fn abi_decode(param: AbiDecodeParam) -> MwResponse {
+    match abi::decode_call(&param.abi, &param.data) {
+        Ok((function, args)) => MwResponse {
+            response: Some(Response::RespAbiDecode(AbiDecodeResp {
+                function_name: function.name.to_owned(),
+                signature: function.signature(),
+                args: args.to_string(),
+            })),
+        },
+        Err(error) => get_error_response_by_error(error.into()),
+    }
+}
+
+pub fn preview_transaction(param: PreviewTransactionParam) -> MwResponse {
+    let input = match param.input {
+        Some(input) => input,
+        None => return get_invalid_proto_resposne(),
+    };
+    match preview::preview_transaction(&input, param.recipient_is_contract) {
+        Ok(preview) => MwResponse {
+            response: Some(Response::RespPreviewTransaction(PreviewTransactionResp {
+                preview: Some(preview),
+            })),
+        },
+        Err(error) => get_error_response_by_error(error.into()),
+    }
+}
+
+pub fn siwe_build(param: SiweBuildParam) -> MwResponse {
+    let message = match param.message {
+        Some(message) => message,
+        None => return get_invalid_proto_resposne(),
+    };
+    match siwe::build_message(&message) {
+        Ok(text) => MwResponse {
+            response: Some(Response::RespSiweBuild(SiweBuildResp { text })),
+        },
+        Err(error) => get_error_response_by_error(error.into()),
+    }
+}
+
+pub fn siwe_validate(param: SiweValidateParam) -> MwResponse {
+    let validated = validate_siwe(
+        &param.text,
+        &param.domain,
+        param.chain_id,
+        &param.nonce,
+        param.now,
+    );
+    match validated {
+        Ok((message, status)) => MwResponse {
+            response: Some(Response::RespSiweValidate(SiweValidateResp {
+                message: Some(message),
+                status: status as i32,
+            })),
+        },
+        Err(error) => get_error_response_by_error(error.into()),
+    }
+}
+
+pub fn siwe_sign(param: SiweSignParam) -> MwResponse {
+    let validated = validate_siwe(
+        &param.text,
+        &param.domain,
+        param.chain_id,
+        &param.nonce,
+        param.now,
+    );
+    let (message, mut status) = match validated {
+        Ok(validated) => validated,
+        Err(error) => return get_error_response_by_error(error.into()),
+    };
+    let (coin, mut stored_key) = match ethereum_signer(
+        &param.stored_key_data,
+        &param.network,
+        Some(message.chain_id),
+    ) {
+        Ok(signer) => signer,
+        Err(response) => return *response,
+    };
+    let mut signature = vec![];
+    if status == ethereum::SiweStatus::Valid {
+        signature = match stored_key.sign_message(
+            coin,
+            &param.password,
+            &param.derivation_path,
+            param.text.as_bytes(),
+        ) {
+            Ok(signature) => signature,
+            Err(error) => return get_error_response_by_error(error),
+        };
+        // The account must be the one signing in
+        if !EthereumSigner::verify_message(param.text.as_bytes(), &signature, &message.address) {
+            status = ethereum::SiweStatus::AddressMismatch;
+            signature = vec![];
+        }
+    }
+    MwResponse {
+        response: Some(Response::RespSiweSign(SiweSignResp {
+            message: Some(message),
+            status: status as i32,
+            signature,
         })),
     }
 }
+
+pub fn sign_user_operation(param: SignUserOperationParam) -> MwResponse {
+    let user_operation = match param.user_operation {
+        Some(user_operation) => user_operation,
+        None => return get_invalid_proto_resposne(),
+    };
+    let (coin, mut stored_key) = match ethereum_signer(
+        &param.stored_key_data,
+        &param.network,
+        Some(user_operation.chain_id),
+    ) {
+        Ok(signer) => signer,
+        Err(response) => return *response,
+    };
+    match stored_key.sign_user_operation(
+        coin,
+        &param.password,
+        &param.derivation_path,
+        &user_operation,
+    ) {
+        Ok(output) => MwResponse {
+            response: Some(Response::RespSignUserOperation(SignUserOperationResp {
+                output: Some(output),
+            })),
+        },
+        Err(error) => get_error_response_by_error(error),
+    }
+}
+
+pub fn compute_account_address(param: ComputeAccountAddressParam) -> MwResponse {
+    match user_operation::counterfactual_address(&param.factory, &param.salt, &param.init_code_hash)
+    {
+        Ok(address) => MwResponse {
+            response: Some(Response::RespComputeAccountAddress(
+                ComputeAccountAddressResp { address },
+            )),
+        },
+        Err(error) => get_error_response_by_error(error.into()),
+    }
+}
+
+pub fn sign_safe_transaction(param: SignSafeTransactionParam) -> MwResponse {
+    let (transaction, signature_type) = match (
+        param.transaction,
+        ethereum::SafeSignatureType::from_i32(param.signature_type),
+    ) {
+        (Some(transaction), Some(signature_type)) => (transaction, signature_type),
+        _ => return get_invalid_proto_resposne(),
+    };
+    let (coin, mut stored_key) = match ethereum_signer(
+        &param.stored_key_data,
+        &param.network,
+        Some(transaction.chain_id),
+    ) {
+        Ok(signer) => signer,
+        Err(response) => return *response,
+    };
+    let safe_tx_hash = match safe::safe_transaction_hash(&transaction) {
+        Ok(hash) => hash,
+        Err(error) => return get_error_response_by_error(error.into()),
+    };
+    match stored_key.sign_safe_transaction(
+        coin,
+        &param.password,
+        &param.derivation_path,
+        &transaction,
+        signature_type,
+    ) {
+        Ok(signature) => MwResponse {
+            response: Some(Response::RespSignSafeTransaction(SignSafeTransactionResp {
+                safe_tx_hash,
+                signature,
+            })),
+        },
+        Err(error) => get_error_response_by_error(error),
+    }
+}
+
+pub fn combine_safe_signatures(param: CombineSafeSignaturesParam) -> MwResponse {
+    match safe::combine_signatures(&param.safe_tx_hash, &param.signatures) {
+        Ok((signatures, owners)) => MwResponse {
+            response: Some(Response::RespCombineSafeSignatures(
+                CombineSafeSignaturesResp { signatures, owners },
+            )),
+        },
+        Err(error) => get_error_response_by_error(error.into()),
+    }
+}
+
+pub fn sign_permit(param: SignPermitParam) -> MwResponse {
+    let input = match param.input {
+        Some(input) => input,
+        None => return get_invalid_proto_resposne(),
+    };
+    let (coin, mut stored_key) =
+        match ethereum_signer(&param.stored_key_data, &param.network, Some(input.chain_id)) {
+            Ok(signer) => signer,
+            Err(response) => return *response,
+        };
+    match stored_key.sign_permit(coin, &param.password, &param.derivation_path, &input) {
+        Ok(output) => MwResponse {
+            response: Some(Response::RespSignPermit(SignPermitResp {
+                output: Some(output),
+            })),
+        },
+        Err(error) => get_error_response_by_error(error),
+    }
+}
+
+pub fn sign_authorization(param: SignAuthorizationParam) -> MwResponse {
+    let authorization = match param.authorization {
+        Some(authorization) => authorization,
+        None => return get_invalid_proto_resposne(),
+    };
+    // An authorization for chain id 0 is valid on every network
+    let chain_id = Some(authorization.chain_id).filter(|&chain_id| chain_id != 0);
+    let (coin, mut stored_key) =
+        match ethereum_signer(&param.stored_key_data, &param.network, chain_id) {
+            Ok(signer) => signer,
+            Err(response) => return *response,
+        };
+    match stored_key.sign_authorization(
+        coin,
+        &param.password,
+        &param.derivation_path,
+        &authorization,
+    ) {
+        Ok(authorization) => MwResponse {
+            response: Some(Response::RespSignAuthorization(SignAuthorizationResp {
+                authorization: Some(authorization),
+            })),
+        },
+        Err(error) => get_error_response_by_error(error),
+    }
+}
+
+// The selected EVM network and the stored key signing Ethereum specific payloads, the payload's
+// `chain_id` must be the one of the network
+fn ethereum_signer(
+    stored_key_data: &[u8],
+    network: &str,
+    chain_id: Option<u64>,
+) -> Result<(&'static CoinInfo, StoredKey), Box<MwResponse>> {
+    let coin = get_selected_coin_info(Coin::Ethereum as i32, network)
+        .filter(|coin| coin.blockchain == "Ethereum")
+        .ok_or_else(|| Box::new(get_invalid_proto_resposne()))?;
+    if let Some(chain_id) = chain_id {
+        match get_coin_info_by_chain_id(chain_id) {
+            Some(payload_coin) if payload_coin.id == coin.id => {}
+            _ => return Err(Box::new(get_invalid_proto_resposne())),
+        }
+    }
+    let stored_key =
+        serde_json::from_slice(stored_key_data).map_err(|_| Box::new(get_json_error_response()))?;
+    Ok((coin, stored_key))
+}
+
+fn validate_siwe(
+    text: &str,
+    domain: &str,
+    chain_id: u64,
+    nonce: &str,
+    now: i64,
+) -> Result<(ethereum::SiweMessage, ethereum::SiweStatus), chain_common::Error> {
+    let message = siwe::parse_message(text)?;
+    let status = siwe::validate_message(&message, domain, chain_id, nonce, now)?;
+    Ok((message, status))
+}
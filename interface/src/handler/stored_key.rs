@@ -369,6 +369,7 @@ mod tests {
             derivation_path: derivation_path.to_owned(),
             curve: "secp256k1".to_owned(),
             public_key_type: "secp256k1Extended".to_owned(),
+            chain_id: Some(1),
             all_info: HashMap::new(),
         };
 
@@ -14,6 +14,7 @@ pub fn validate(param: ValidateParam) -> MwResponse {
         Some(input) => input,
         None => return get_json_error_response(),
     };
+    let mut address_checksum = AddressChecksum::NoChecksum;
     let valid = match input_param {
         validate_param::Input::Mnemonic(mnemonic) => Mnemonic::is_valid(&mnemonic),
         validate_param::Input::PrivateKey(private_key) => {
@@ -49,10 +50,18 @@ pub fn validate(param: ValidateParam) -> MwResponse {
                     return get_error_response_by_error(error);
                 }
             };
-            entry.validate_address(&addr_param.address)
+            let validation = ChecksumValidation::from_i32(addr_param.checksum_validation)
+                .unwrap_or(ChecksumValidation::ChecksumIfMixedCase);
+            let (valid, checksum) =
+                entry.validate_address_checksum(coin, &addr_param.address, validation);
+            address_checksum = checksum;
+            valid
         }
     };
     MwResponse {
-        response: Some(Response::RespValidate(ValidateResp { valid })),
+        response: Some(Response::RespValidate(ValidateResp {
+            valid,
+            address_checksum: address_checksum as i32,
+        })),
     }
 }
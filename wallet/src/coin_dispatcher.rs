@@ -1,4 +1,5 @@
 use crate::Error;
+use arweave::entry::ArweaveEntry;
 use chain_common::api::Coin as ProtoCoinType;
 use chain_common::coin::Coin;
 use chain_common::entry::Entry;
@@ -6,6 +7,7 @@ use chain_common::private_key::PrivateKey;
 use chain_common::public_key::PublicKey;
 use chain_common::Error as ChainError;
 use ethereum::entry::EthereumEntry;
+use polkadot::entry::PolkadotEntry;
 use solana::entry::SolanaEntry;
 use std::str::FromStr;
 
@@ -13,10 +15,13 @@ pub struct CoinDispatcher {}
 
 impl CoinDispatcher {
     pub fn get_entry(coin: &Coin) -> Result<Box<dyn Entry>, Error> {
-        let coin_proto_type = ProtoCoinType::from_str(&coin.name)?;
+        // Every EVM network in the registry is declared on the Ethereum blockchain
+        let coin_proto_type = ProtoCoinType::from_str(&coin.blockchain)?;
         match coin_proto_type {
             ProtoCoinType::Ethereum => Ok(Box::new(EthereumEntry {})),
+            ProtoCoinType::Polkadot => Ok(Box::new(PolkadotEntry {})),
             ProtoCoinType::Solana => Ok(Box::new(SolanaEntry {})),
+            ProtoCoinType::Arweave => Ok(Box::new(ArweaveEntry {})),
             _ => Err(Error::ChainError(ChainError::NotSupportedCoin)),
         }
     }
@@ -1,15 +1,41 @@
 use crate::Error;
+use chain_common::coin::Coin;
+use crypto::curve::Curve;
+use crypto::sr25519::Junction;
 use crypto::Error as CryptoError;
 use serde::{Deserialize, Serialize};
 use std::fmt::Debug;
+use std::str::FromStr;
 use std::string::ToString;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Serialize, Deserialize, Clone)]
 pub struct DerivationPath {
     indices: Vec<DerivationPathIndex>,
+    // Substrate paths like `//hard/soft///password` use junctions instead of BIP32 indices
+    #[serde(default)]
+    junctions: Vec<DerivationJunction>,
+    // The password is a secret, it is never stored nor printed and must be passed again with the
+    // path whenever the key is derived
+    #[serde(skip)]
+    password: Option<String>,
 }
 
 impl DerivationPath {
+    /// Parse the path of a key of `coin`, Substrate junctions for sr25519 keys and BIP32 indices
+    /// for the other curves. Keys of coins without a curve, like RSA keys, aren't derived
+    pub fn new_for_coin(path: &str, coin: &Coin) -> Result<DerivationPath, Error> {
+        match Curve::from_str(&coin.curve) {
+            Ok(Curve::Sr25519) => Self::new_substrate(path),
+            Ok(_) => Self::new(path),
+            Err(_) if path.is_empty() => Ok(DerivationPath {
+                indices: vec![],
+                junctions: vec![],
+                password: None,
+            }),
+            Err(error) => Err(error.into()),
+        }
+    }
+
     pub fn new(path: &str) -> Result<DerivationPath, Error> {
         let children: Vec<&str> = path.split('/').into_iter().collect();
         let mut indices: Vec<DerivationPathIndex> = vec![];
@@ -33,12 +59,76 @@ impl DerivationPath {
                 hardened,
             });
         }
-        Ok(DerivationPath { indices })
+        Ok(DerivationPath {
+            indices,
+            junctions: vec![],
+            password: None,
+        })
+    }
+
+    pub fn new_substrate(path: &str) -> Result<DerivationPath, Error> {
+        let (path, password) = match path.split_once("///") {
+            Some((path, password)) => (path, Some(password.to_owned())),
+            None => (path, None),
+        };
+        if !path.is_empty() && !path.starts_with('/') {
+            return Err(Error::CryptoError(CryptoError::InvalidDerivationpath));
+        }
+        let mut junctions: Vec<DerivationJunction> = vec![];
+        let mut hard = false;
+        // "//hard/soft" splits into ["", "", "hard", "soft"], an empty part marks a hard junction
+        for (position, part) in path.split('/').enumerate().skip(1) {
+            if !part.is_empty() {
+                junctions.push(DerivationJunction {
+                    value: part.to_owned(),
+                    hard,
+                });
+                hard = false;
+            } else if hard || position == path.split('/').count() - 1 {
+                return Err(Error::CryptoError(CryptoError::InvalidDerivationpath));
+            } else {
+                hard = true;
+            }
+        }
+        Ok(DerivationPath {
+            indices: vec![],
+            junctions,
+            password,
+        })
+    }
+
+    /// Whether the path has BIP32 indices
+    pub fn is_bip32(&self) -> bool {
+        !self.indices.is_empty()
+    }
+
+    /// Whether this is a Substrate junction path rather than a BIP32 path
+    pub fn is_substrate(&self) -> bool {
+        !self.junctions.is_empty() || self.password.is_some()
+    }
+
+    pub fn substrate_junctions(&self) -> Result<Vec<Junction>, Error> {
+        Ok(self
+            .junctions
+            .iter()
+            .map(|junction| Junction::new(&junction.value, junction.hard))
+            .collect::<Result<Vec<Junction>, CryptoError>>()?)
+    }
+
+    pub fn password(&self) -> Option<&str> {
+        self.password.as_deref()
     }
 }
 
 impl ToString for DerivationPath {
     fn to_string(&self) -> String {
+        if self.is_substrate() {
+            return self
+                .junctions
+                .iter()
+                .map(|junction| junction.to_string())
+                .collect();
+        }
         let mut path = String::from("m/");
         self.indices.iter().for_each(|index| {
             path.push_str(&index.value.to_string());
@@ -55,6 +145,14 @@ impl ToString for DerivationPath {
     }
 }
 
+impl Debug for DerivationPath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("DerivationPath")
+            .field(&self.to_string())
+            .finish()
+    }
+}
+
 impl PartialEq for DerivationPath {
     fn eq(&self, other: &DerivationPath) -> bool {
         (self.indices.len() == other.indices.len())
@@ -63,6 +161,8 @@ impl PartialEq for DerivationPath {
                 .iter()
                 .zip(other.indices.iter())
                 .all(|(left, right)| *left == *right)
+            && self.junctions == other.junctions
+            && self.password == other.password
     }
 }
 
@@ -72,6 +172,21 @@ struct DerivationPathIndex {
     hardened: bool,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+struct DerivationJunction {
+    value: String,
+    hard: bool,
+}
+
+impl std::fmt::Display for DerivationJunction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.hard {
+            true => write!(f, "//{}", self.value),
+            false => write!(f, "/{}", self.value),
+        }
+    }
+}
+
 impl ToString for DerivationPathIndex {
     fn to_string(&self) -> String {
         if self.hardened {
@@ -100,6 +215,77 @@ mod tests {
         assert_eq!(derivation_path.to_string(), test_path_str);
     }
 
+    #[test]
+    fn test_substrate_derivation_path_parse() {
+        let path = DerivationPath::new_substrate("//polkadot/0//hard///secret").unwrap();
+        assert!(path.is_substrate());
+        assert_eq!(path.substrate_junctions().unwrap().len(), 3);
+        assert_eq!(path.password(), Some("secret"));
+        // The password is never printed nor stored
+        assert_eq!(path.to_string(), "//polkadot/0//hard");
+        assert!(!format!("{:?}", path).contains("secret"));
+        let json = serde_json::to_string(&path).unwrap();
+        assert!(!json.contains("secret"));
+        let stored: DerivationPath = serde_json::from_str(&json).unwrap();
+        assert_eq!(stored.password(), None);
+        assert_eq!(stored.to_string(), "//polkadot/0//hard");
+
+        assert!(!DerivationPath::new_substrate("").unwrap().is_substrate());
+        assert_eq!(
+            DerivationPath::new_substrate("///").unwrap().password(),
+            Some("")
+        );
+        for invalid in ["/", "//", "//a/", "/a//"] {
+            assert_eq!(
+                DerivationPath::new_substrate(invalid).unwrap_err(),
+                Error::CryptoError(CryptoError::InvalidDerivationpath)
+            );
+        }
+        assert!(DerivationPath::new_substrate("//a") != DerivationPath::new_substrate("/a"));
+    }
+
+    #[test]
+    fn test_derivation_path_for_coin() {
+        use std::collections::HashMap;
+
+        let mut coin = Coin {
+            id: "ethereum".to_owned(),
+            name: "ethereum".to_owned(),
+            coin_id: 60,
+            symbol: "ETH".to_owned(),
+            decimals: 18,
+            blockchain: "Ethereum".to_owned(),
+            derivation_path: "m/44'/60'/0'/0/0".to_owned(),
+            curve: "secp256k1".to_owned(),
+            public_key_type: "secp256k1Extended".to_owned(),
+            chain_id: Some(1),
+            all_info: HashMap::new(),
+        };
+        // BIP32 curves never derive the master key from an empty or a Substrate path
+        for invalid in ["", "//Alice", "/0"] {
+            assert_eq!(
+                DerivationPath::new_for_coin(invalid, &coin).unwrap_err(),
+                Error::CryptoError(CryptoError::InvalidDerivationpath)
+            );
+        }
+        assert!(DerivationPath::new_for_coin("m/44'/60'/0'/0/0", &coin)
+            .unwrap()
+            .is_bip32());
+
+        coin.curve = "sr25519".to_owned();
+        assert!(DerivationPath::new_for_coin("//Alice", &coin)
+            .unwrap()
+            .is_substrate());
+        assert!(!DerivationPath::new_for_coin("", &coin)
+            .unwrap()
+            .is_substrate());
+        assert!(DerivationPath::new_for_coin("m/44'/60'", &coin).is_err());
+
+        coin.curve = "".to_owned();
+        assert!(DerivationPath::new_for_coin("", &coin).is_ok());
+        assert!(DerivationPath::new_for_coin("m/44'", &coin).is_err());
+    }
+
     #[test]
     fn test_derivation_path_eq() {
         let path1_str = "m/44'/60'/0'/0/0";
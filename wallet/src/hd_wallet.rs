@@ -6,6 +6,7 @@ use chain_common::private_key::{PrivateKey, PrivateKeyType};
 use crypto::bip32;
 use crypto::bip39::Mnemonic;
 use crypto::curve::Curve;
+use crypto::sr25519;
 use crypto::Error as CryptoError;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
@@ -47,6 +48,12 @@ impl HdWallet {
         derivation_path: &DerivationPath,
     ) -> Result<PrivateKey, Error> {
         let curve = Curve::from_str(&coin.curve)?;
+        if let Curve::Sr25519 = curve {
+            return self.get_sr25519_key(derivation_path);
+        }
+        if derivation_path.is_substrate() {
+            return Err(Error::CryptoError(CryptoError::InvalidDerivationpath));
+        }
         let private_key_type = PrivateKey::get_private_key_type(&curve);
         let node = bip32::HdNode::get_node(&self.seed, &derivation_path.to_string(), curve)?;
         match private_key_type {
@@ -59,6 +66,21 @@ impl HdWallet {
         }
     }
 
+    // sr25519 keys derive from the entropy with Substrate junctions, `///password` replaces the
+    // wallet password
+    fn get_sr25519_key(&self, derivation_path: &DerivationPath) -> Result<PrivateKey, Error> {
+        if derivation_path.is_bip32() {
+            return Err(Error::CryptoError(CryptoError::InvalidDerivationpath));
+        }
+        let password = derivation_path.password().unwrap_or(&self.password);
+        let secret_key = sr25519::derive_secret_key(
+            &self.entropy,
+            password,
+            &derivation_path.substrate_junctions()?,
+        )?;
+        Ok(PrivateKey::new(&secret_key)?)
+    }
+
     pub fn get_address_for_coin(&self, coin: &Coin) -> Result<String, Error> {
         self.get_address_for_coin_of_path(coin, &coin.derivation_path)
     }
@@ -68,7 +90,7 @@ impl HdWallet {
         coin: &Coin,
         derivation_path: &str,
     ) -> Result<String, Error> {
-        let derivation_path = DerivationPath::new(derivation_path)?;
+        let derivation_path = DerivationPath::new_for_coin(derivation_path, coin)?;
         let private_key = self.get_key(coin, &derivation_path)?;
         derive_address_with_private_key(coin, &private_key)
     }
@@ -133,6 +155,7 @@ mod tests {
             derivation_path: derivation_path.to_owned(),
             curve: "secp256k1".to_owned(),
             public_key_type: "secp256k1Extended".to_owned(),
+            chain_id: Some(1),
             all_info: HashMap::new(),
         };
         let address1 = wallet.get_address_for_coin(&coin).unwrap();
@@ -157,6 +180,7 @@ mod tests {
             derivation_path: "m/44'/60'/0'/0/0".to_owned(),
             curve: "secp256k1".to_owned(),
             public_key_type: "secp256k1Extended".to_owned(),
+            chain_id: Some(1),
             all_info: HashMap::new(),
         };
         let address1 = wallet.get_address_for_coin(&coin).unwrap();
@@ -190,6 +214,7 @@ mod tests {
             derivation_path: derivation_path.to_owned(),
             curve: "secp256k1".to_owned(),
             public_key_type: "secp256k1Extended".to_owned(),
+            chain_id: Some(1),
             all_info: HashMap::new(),
         };
         let extended_public_key = wallet.get_extended_public_key(&coin);
@@ -214,10 +239,81 @@ mod tests {
             derivation_path: derivation_path.to_owned(),
             curve: "ed25519".to_owned(),
             public_key_type: "ed25519".to_owned(),
+            chain_id: None,
             all_info: HashMap::new(),
         };
         let address1 = wallet.get_address_for_coin(&coin).unwrap();
         // let address2 = wallet.get_address_for_coin(&coin).unwrap();
         assert_eq!(address1, "2bUBiBNZyD29gP1oV6de7nxowMLoDBtopMMTGgMvjG5m");
     }
+
+    #[test]
+    fn test_get_address_for_coin_polkadot() {
+        let wallet = HdWallet::new_with_mnemonic(
+            "shoot island position soft burden budget tooth cruel issue economy destroy above",
+            "",
+        )
+        .unwrap();
+        let mut coin = Coin {
+            id: "polkadot".to_owned(),
+            name: "Polkadot".to_owned(),
+            coin_id: 354,
+            symbol: "DOT".to_owned(),
+            decimals: 10,
+            blockchain: "Polkadot".to_owned(),
+            derivation_path: "m/44'/354'/0'/0'/0'".to_owned(),
+            curve: "ed25519".to_owned(),
+            public_key_type: "ed25519".to_owned(),
+            chain_id: None,
+            all_info: HashMap::new(),
+        };
+        let address = wallet.get_address_for_coin(&coin).unwrap();
+        assert_eq!(address, "13nN6BGAoJwd7Nw1XxeBCx5YcBXuYnL94Mh7i3xBprqVSsFk");
+
+        // The network prefix comes from the coin registry
+        coin.all_info
+            .insert("ss58_prefix".to_owned(), serde_json::json!(42));
+        let address = wallet.get_address_for_coin(&coin).unwrap();
+        assert!(address.starts_with('5'));
+    }
+
+    #[test]
+    fn test_get_address_for_coin_polkadot_sr25519() {
+        let wallet = HdWallet::new_with_mnemonic(
+            "bottom drive obey lake curtain smoke basket hold race lonely fit walk",
+            "",
+        )
+        .unwrap();
+        let coin = Coin {
+            id: "polkadotsr25519".to_owned(),
+            name: "Polkadot (sr25519)".to_owned(),
+            coin_id: 354,
+            symbol: "DOT".to_owned(),
+            decimals: 10,
+            blockchain: "Polkadot".to_owned(),
+            derivation_path: "".to_owned(),
+            curve: "sr25519".to_owned(),
+            public_key_type: "sr25519".to_owned(),
+            chain_id: None,
+            all_info: HashMap::new(),
+        };
+        let address = wallet
+            .get_address_for_coin_of_path(&coin, "//Alice")
+            .unwrap();
+        assert_eq!(address, "15oF4uVJwmo4TdGW7VfQxNLavjCXviqxT9S1MgbjMNHr6Sp5");
+
+        // `///password` is the BIP39 password of the mini-secret
+        let protected = HdWallet::new_with_mnemonic(&wallet.mnemonic, "secret").unwrap();
+        assert_eq!(
+            wallet
+                .get_address_for_coin_of_path(&coin, "//Alice///secret")
+                .unwrap(),
+            protected
+                .get_address_for_coin_of_path(&coin, "//Alice")
+                .unwrap()
+        );
+        assert!(wallet
+            .get_address_for_coin_of_path(&coin, "m/44'/354'/0'")
+            .is_err());
+    }
 }
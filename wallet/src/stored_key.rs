@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::mem::discriminant;
 use std::str::FromStr;
 use uuid::Uuid;
 
@@ -8,13 +9,23 @@ use super::derivation_path::DerivationPath;
 use super::encryption_params::EncryptionParams;
 use super::hd_wallet::HdWallet;
 use crate::Error;
-use chain_common::api::{StoredKeyInfo, StoredKeyType as ProtoStoreKeyType};
+use arweave::jwk::RsaJwk;
+use chain_common::api::{Coin as ProtoCoinType, StoredKeyInfo, StoredKeyType as ProtoStoreKeyType};
 use chain_common::coin::Coin;
+use chain_common::ethereum::{
+    Authorization, PermitInput, PermitSignOutput, SafeSignatureType, SafeTransaction,
+    TypedDataVersion, UserOperation, UserOperationSignOutput,
+};
 use chain_common::private_key::PrivateKey;
+use chain_common::Error as ChainError;
 use crypto::bip39::Mnemonic;
+use crypto::curve::Curve;
 use crypto::hash;
 use crypto::key_store_json::KeyStoreJson;
+use crypto::polkadot_json::PolkadotJson;
+use crypto::public_key::PublicKeyType;
 use crypto::Error as CryptoError;
+use ethereum::signer::Signer as EthereumSigner;
 
 const VERSION: &str = "0.1.0";
 
@@ -77,7 +88,7 @@ impl StoredKey {
     ) -> Result<StoredKey, Error> {
         let priv_key_bytes =
             hex::decode(private_key).map_err(|_| CryptoError::InvalidPrivateKey)?;
-        PrivateKey::is_valid(&priv_key_bytes, &coin.curve)?;
+        PrivateKey::is_valid(&priv_key_bytes, coin)?;
         let stored_key = StoredKey::create_with_private_key(password, private_key)?;
         Ok(stored_key)
     }
@@ -105,6 +116,17 @@ impl StoredKey {
         json: &str,
         coin: &Coin,
     ) -> Result<StoredKey, Error> {
+        if let Ok(polkadot_json) = PolkadotJson::from_str(json) {
+            return Self::create_with_polkadot_json(
+                key_store_json_password,
+                password,
+                &polkadot_json,
+                coin,
+            );
+        }
+        if let Ok(jwk) = RsaJwk::from_str(json) {
+            return Self::create_with_arweave_jwk(password, &jwk, coin);
+        }
         let key_store_json_struct = KeyStoreJson::from_str(json)?;
         let (_, decrypted) = EncryptionParams::new_from_json_struct(
             &key_store_json_struct,
@@ -114,10 +136,37 @@ impl StoredKey {
         if Mnemonic::is_valid(&decrypted_str) {
             return Self::create_with_mnemonic(password, &decrypted_str);
         }
-        let private_key = PrivateKey::new(&decrypted)?;
+        let private_key = PrivateKey::new_for_coin(&decrypted, coin)?;
         let private_key_hex = hex::encode(&private_key.data);
         Self::create_with_private_key_and_coin(password, &private_key_hex, coin)
     }
+
+    /// Import an account exported by polkadot-js, whose key type must be the curve of `coin`
+    pub fn create_with_polkadot_json(
+        json_password: &str,
+        password: &str,
+        json: &PolkadotJson,
+        coin: &Coin,
+    ) -> Result<StoredKey, Error> {
+        let (curve, secret) = json.decrypt(json_password.as_bytes())?;
+        if discriminant(&curve) != discriminant(&Curve::from_str(&coin.curve)?) {
+            return Err(Error::CryptoError(CryptoError::NotSupportedCurve));
+        }
+        Self::create_with_private_key_and_coin(password, &hex::encode(&secret), coin)
+    }
+
+    /// Import an Arweave wallet file, for a coin whose public key type is RSA
+    pub fn create_with_arweave_jwk(
+        password: &str,
+        jwk: &RsaJwk,
+        coin: &Coin,
+    ) -> Result<StoredKey, Error> {
+        if PublicKeyType::from_str(&coin.public_key_type) != Ok(PublicKeyType::Rsa) {
+            return Err(Error::CryptoError(CryptoError::NotSupportedPublicKeyType));
+        }
+        let private_key = jwk.to_private_key()?;
+        Self::create_with_data(StoredKeyType::PrivateKey, password, &private_key)
+    }
 }
 
 // Update methods
@@ -146,7 +195,7 @@ impl StoredKey {
             return Err(Error::RequestNotSupportedOnPrivateKeyTypeStoredKey);
         }
         let wallet = self.get_wallet(password)?;
-        let derivation_path = DerivationPath::new(derivation_path)?;
+        let derivation_path = DerivationPath::new_for_coin(derivation_path, coin)?;
         let private_key = wallet.get_key(coin, &derivation_path)?;
         Ok(private_key.to_string())
     }
@@ -184,7 +233,7 @@ impl StoredKey {
         } else {
             // 1. If this StoredKey is created from a mnemonic, derive to the specific path to get the private key
             let wallet = self.get_wallet(password)?;
-            let derivation_path_struct = DerivationPath::new(derivation_path)?;
+            let derivation_path_struct = DerivationPath::new_for_coin(derivation_path, coin)?;
             let private_key = wallet.get_key(coin, &derivation_path_struct)?;
 
             // 2. Create a temp EncryptionParam with new password for exporting
@@ -209,7 +258,7 @@ impl StoredKey {
         }
         // 2. If this StoredKey is created from a mnemonic, derive to the specific path to get the private key
         let wallet = self.get_wallet(password)?;
-        let derivation_path = DerivationPath::new(derivation_path)?;
+        let derivation_path = DerivationPath::new_for_coin(derivation_path, coin)?;
         let private_key = wallet.get_key(coin, &derivation_path)?;
 
         // 3. Create a temp EncryptionParam with new password for exporting
@@ -262,7 +311,7 @@ impl StoredKey {
     ) -> Result<Account, Error> {
         if self.r#type == StoredKeyType::PrivateKey {
             let decrypted = self.payload.decrypt(password.as_bytes())?;
-            let private_key = PrivateKey::new(&decrypted)?;
+            let private_key = PrivateKey::new_for_coin(&decrypted, coin)?;
 
             let public_key = private_key.get_public_key(&coin.public_key_type)?;
             let address =
@@ -304,10 +353,43 @@ impl StoredKey {
             }
             StoredKeyType::PrivateKey => {
                 let decrypted = self.payload.decrypt(password.as_bytes())?;
-                Ok(PrivateKey::new(&decrypted)?)
+                Ok(PrivateKey::new_for_coin(&decrypted, coin)?)
+            }
+        }
+    }
+
+    // The derivation path is disregarded for a private key type StoredKey
+    fn decrypt_private_key_of_path(
+        &self,
+        password: &str,
+        coin: &Coin,
+        derivation_path: &str,
+    ) -> Result<PrivateKey, Error> {
+        match self.r#type {
+            StoredKeyType::Mnemonic => {
+                let deriation_path_struct = DerivationPath::new_for_coin(derivation_path, coin)?;
+                let wallet = self.get_wallet(password)?;
+                wallet.get_key(coin, &deriation_path_struct)
+            }
+            StoredKeyType::PrivateKey => {
+                let decrypted = self.payload.decrypt(password.as_bytes())?;
+                Ok(PrivateKey::new_for_coin(&decrypted, coin)?)
             }
         }
     }
+
+    // The key signing Ethereum specific payloads, which the coin must be on
+    fn decrypt_ethereum_private_key(
+        &self,
+        password: &str,
+        coin: &Coin,
+        derivation_path: &str,
+    ) -> Result<PrivateKey, Error> {
+        if ProtoCoinType::from_str(&coin.blockchain)? != ProtoCoinType::Ethereum {
+            return Err(Error::ChainError(ChainError::NotSupportedCoin));
+        }
+        self.decrypt_private_key_of_path(password, coin, derivation_path)
+    }
 }
 
 // Sign methods
@@ -319,19 +401,97 @@ impl StoredKey {
         derivation_path: &str,
         payload: &[u8],
     ) -> Result<Vec<u8>, Error> {
-        let private_key = match self.r#type {
-            StoredKeyType::Mnemonic => {
-                let deriation_path_struct = DerivationPath::new(derivation_path)?;
-                let wallet = self.get_wallet(password)?;
-                wallet.get_key(coin, &deriation_path_struct)?
-            }
-            StoredKeyType::PrivateKey => {
-                let decrypted = self.payload.decrypt(password.as_bytes())?;
-                PrivateKey::new(&decrypted)?
-            }
-        };
+        let private_key = self.decrypt_private_key_of_path(password, coin, derivation_path)?;
         Ok(CoinDispatcher::get_entry(coin)?.sign(coin, &private_key, payload)?)
     }
+
+    /// Sign an off-chain message with the coin's message signing scheme
+    pub fn sign_message(
+        &mut self,
+        coin: &Coin,
+        password: &str,
+        derivation_path: &str,
+        message: &[u8],
+    ) -> Result<Vec<u8>, Error> {
+        let private_key = self.decrypt_private_key_of_path(password, coin, derivation_path)?;
+        Ok(CoinDispatcher::get_entry(coin)?.sign_message(coin, &private_key, message)?)
+    }
+
+    /// Sign EIP-712 typed structured data, only supported on Ethereum
+    pub fn sign_typed_data(
+        &mut self,
+        coin: &Coin,
+        password: &str,
+        derivation_path: &str,
+        typed_data: &str,
+        version: TypedDataVersion,
+    ) -> Result<Vec<u8>, Error> {
+        let private_key = self.decrypt_ethereum_private_key(password, coin, derivation_path)?;
+        Ok(EthereumSigner::sign_typed_data(
+            &private_key,
+            typed_data,
+            version,
+        )?)
+    }
+
+    /// Sign an ERC-4337 UserOperation, only supported on Ethereum
+    pub fn sign_user_operation(
+        &mut self,
+        coin: &Coin,
+        password: &str,
+        derivation_path: &str,
+        user_operation: &UserOperation,
+    ) -> Result<UserOperationSignOutput, Error> {
+        let private_key = self.decrypt_ethereum_private_key(password, coin, derivation_path)?;
+        Ok(EthereumSigner::sign_user_operation(
+            &private_key,
+            user_operation,
+        )?)
+    }
+
+    /// Sign an EIP-7702 authorization delegating the account, only supported on Ethereum
+    pub fn sign_authorization(
+        &mut self,
+        coin: &Coin,
+        password: &str,
+        derivation_path: &str,
+        authorization: &Authorization,
+    ) -> Result<Authorization, Error> {
+        let private_key = self.decrypt_ethereum_private_key(password, coin, derivation_path)?;
+        Ok(EthereumSigner::sign_authorization(
+            &private_key,
+            authorization,
+        )?)
+    }
+
+    /// Sign an EIP-2612 or Permit2 permit, only supported on Ethereum
+    pub fn sign_permit(
+        &mut self,
+        coin: &Coin,
+        password: &str,
+        derivation_path: &str,
+        input: &PermitInput,
+    ) -> Result<PermitSignOutput, Error> {
+        let private_key = self.decrypt_ethereum_private_key(password, coin, derivation_path)?;
+        Ok(EthereumSigner::sign_permit(&private_key, input)?)
+    }
+
+    /// Sign a Safe multisig transaction as an owner, only supported on Ethereum
+    pub fn sign_safe_transaction(
+        &mut self,
+        coin: &Coin,
+        password: &str,
+        derivation_path: &str,
+        transaction: &SafeTransaction,
+        signature_type: SafeSignatureType,
+    ) -> Result<Vec<u8>, Error> {
+        let private_key = self.decrypt_ethereum_private_key(password, coin, derivation_path)?;
+        Ok(EthereumSigner::sign_safe_transaction(
+            &private_key,
+            transaction,
+            signature_type,
+        )?)
+    }
 }
 
 impl From<StoredKey> for StoredKeyInfo {
@@ -375,6 +535,7 @@ mod tests {
             derivation_path: derivation_path.to_owned(),
             curve: "secp256k1".to_owned(),
             public_key_type: "secp256k1Extended".to_owned(),
+            chain_id: Some(1),
             all_info: HashMap::new(),
         };
 
@@ -408,6 +569,7 @@ mod tests {
             derivation_path: derivation_path.to_owned(),
             curve: "secp256k1".to_owned(),
             public_key_type: "secp256k1Extended".to_owned(),
+            chain_id: Some(1),
             all_info: HashMap::new(),
         };
 
@@ -469,6 +631,7 @@ mod tests {
             derivation_path: derivation_path.to_owned(),
             curve: "secp256k1".to_owned(),
             public_key_type: "secp256k1Extended".to_owned(),
+            chain_id: Some(1),
             all_info: HashMap::new(),
         };
         let key_store_json_password = "Maskbook123";
@@ -523,6 +686,7 @@ mod tests {
             derivation_path: derivation_path.to_owned(),
             curve: "secp256k1".to_owned(),
             public_key_type: "secp256k1Extended".to_owned(),
+            chain_id: Some(1),
             all_info: HashMap::new(),
         };
 
@@ -584,6 +748,7 @@ mod tests {
             derivation_path: derivation_path.to_owned(),
             curve: "ed25519".to_owned(),
             public_key_type: "ed25519".to_owned(),
+            chain_id: None,
             all_info: HashMap::new(),
         };
 
@@ -620,6 +785,7 @@ mod tests {
             derivation_path: derivation_path.to_owned(),
             curve: "ed25519".to_owned(),
             public_key_type: "ed25519".to_owned(),
+            chain_id: None,
             all_info: HashMap::new(),
         };
 
@@ -651,4 +817,214 @@ mod tests {
             "3TMqEHi5cVT86NeSuXmSfGf4PJZH1NQeh6rzc6bneKZ7"
         );
     }
+
+    #[test]
+    fn test_sign_message() {
+        let mnemonic =
+            "hurt behind mosquito scare enable gym learn unlock render grab reflect world";
+        let password = "";
+        let derivation_path = "m/44'/501'/0'/0'";
+        let coin = Coin {
+            id: "501".to_owned(),
+            name: "solana".to_owned(),
+            coin_id: 501,
+            symbol: "SOL".to_owned(),
+            decimals: 9,
+            blockchain: "Solana".to_owned(),
+            derivation_path: "m/44'/501'/0'".to_owned(),
+            curve: "ed25519".to_owned(),
+            public_key_type: "ed25519".to_owned(),
+            chain_id: None,
+            all_info: HashMap::new(),
+        };
+
+        let mut stored_key = StoredKey::create_with_mnemonic(password, mnemonic).unwrap();
+        let signature = stored_key
+            .sign_message(&coin, password, derivation_path, b"Sign in to Mask")
+            .unwrap();
+        let entry = CoinDispatcher::get_entry(&coin).unwrap();
+        assert!(entry
+            .verify_message(
+                &coin,
+                b"Sign in to Mask",
+                &signature,
+                "HixTFWqKrey5xETK7yibumEttc4VPDVC5QJqj5qcXtM3"
+            )
+            .unwrap());
+        assert!(!entry
+            .verify_message(
+                &coin,
+                b"Sign in to Mask",
+                &signature,
+                "3TMqEHi5cVT86NeSuXmSfGf4PJZH1NQeh6rzc6bneKZ7"
+            )
+            .unwrap());
+    }
+
+    #[test]
+    fn test_sign_typed_data() {
+        let mnemonic =
+            "suffer artefact burst review network fantasy easy century mom unique pupil boy";
+        let password = "mask wallet";
+        let derivation_path = "m/44'/60'/0'/0/1";
+        let mut coin = Coin {
+            id: "60".to_owned(),
+            name: "ethereum".to_owned(),
+            coin_id: 60,
+            symbol: "ETH".to_owned(),
+            decimals: 18,
+            blockchain: "Ethereum".to_owned(),
+            derivation_path: "m/44'/60'/0'/0/0".to_owned(),
+            curve: "secp256k1".to_owned(),
+            public_key_type: "secp256k1Extended".to_owned(),
+            chain_id: Some(1),
+            all_info: HashMap::new(),
+        };
+        let typed_data = r#"{
+            "types": {
+                "EIP712Domain": [{ "name": "name", "type": "string" }],
+                "Message": [{ "name": "contents", "type": "string" }]
+            },
+            "primaryType": "Message",
+            "domain": { "name": "Mask" },
+            "message": { "contents": "Hello" }
+        }"#;
+
+        let mut stored_key = StoredKey::create_with_mnemonic(password, mnemonic).unwrap();
+        let signature = stored_key
+            .sign_typed_data(
+                &coin,
+                password,
+                derivation_path,
+                typed_data,
+                TypedDataVersion::V4,
+            )
+            .unwrap();
+        assert_eq!(signature.len(), 65);
+
+        // The key is resolved by the derivation path
+        let private_key = stored_key
+            .export_private_key_of_path(password, &coin, derivation_path)
+            .unwrap();
+        let mut private_stored_key =
+            StoredKey::create_with_private_key_and_coin(password, &private_key, &coin).unwrap();
+        let private_key_signature = private_stored_key
+            .sign_typed_data(&coin, password, "", typed_data, TypedDataVersion::V4)
+            .unwrap();
+        assert_eq!(signature, private_key_signature);
+
+        coin.blockchain = "Solana".to_owned();
+        assert_eq!(
+            stored_key
+                .sign_typed_data(
+                    &coin,
+                    password,
+                    derivation_path,
+                    typed_data,
+                    TypedDataVersion::V4
+                )
+                .unwrap_err(),
+            Error::ChainError(ChainError::NotSupportedCoin)
+        );
+    }
+
+    #[test]
+    fn test_create_with_polkadot_json() {
+        let json = r#"
+        {
+            "address": "5DfhGyQdFobKM8NsWvEeAKk5EQQgYe9AydgJ7rMB6E1EqRzV",
+            "encoded": "AAECAwQFBgcICQoLDA0ODxAREhMUFRYXGBkaGxwdHh8AgAAAAQAAAAgAAABkZWZnaGlqa2xtbm9wcXJzdHV2d3h5ens0TnEcVllzAC/J5oJ+9E7pX0/hR0f4SfmZ0gcOukEBz58Pb8O44mCahbJQQtV2p9FIB7KkKMaN2/uAkvIc3+QOof8/9Pmc7tCuRpFul0c0fgifkaMuGEVxU7mwOsB75VYoeZnJDmsNih73/fPVEXYHqcqS2vGeQAGY2mYWzje64eK5YF15",
+            "encoding": {
+                "content": ["pkcs8", "sr25519"],
+                "type": ["scrypt", "xsalsa20-poly1305"],
+                "version": "3"
+            },
+            "meta": {
+                "genesisHash": "",
+                "name": "dev",
+                "whenCreated": 1600000000000
+            }
+        }
+        "#;
+        let mut coin = Coin {
+            id: "polkadotsr25519".to_owned(),
+            name: "Polkadot (sr25519)".to_owned(),
+            coin_id: 354,
+            symbol: "DOT".to_owned(),
+            decimals: 10,
+            blockchain: "Polkadot".to_owned(),
+            derivation_path: "".to_owned(),
+            curve: "sr25519".to_owned(),
+            public_key_type: "sr25519".to_owned(),
+            chain_id: None,
+            all_info: HashMap::new(),
+        };
+        let password = "password_new";
+        let stored_key = StoredKey::create_with_json("password", password, json, &coin).unwrap();
+        assert!(stored_key.r#type == StoredKeyType::PrivateKey);
+        let account = stored_key
+            .add_new_account_of_coin_and_derivation_path_by_password("mask", &coin, "", password)
+            .unwrap();
+        assert_eq!(
+            account.address,
+            "12bzRJfh7arnnfPPUZHeJUaE62QLEwhK48QnH9LXeK2m1iZU"
+        );
+
+        assert_eq!(
+            StoredKey::create_with_json("wrong", password, json, &coin).err(),
+            Some(Error::CryptoError(CryptoError::PasswordIncorrect))
+        );
+        // An sr25519 account can't be imported as an ed25519 one
+        coin.curve = "ed25519".to_owned();
+        assert_eq!(
+            StoredKey::create_with_json("password", password, json, &coin).err(),
+            Some(Error::CryptoError(CryptoError::NotSupportedCurve))
+        );
+    }
+
+    #[test]
+    fn test_create_with_arweave_jwk() {
+        let json = r#"
+        {
+            "kty": "RSA",
+            "n": "rSZAlWKVI-SWqLCNNwEKCZIfLVpgbj0Nrt_Bt_NiQRbmrUzY51Aiwun66UIiMf1BJcAiwz5gPSPxVMTjr0rvow8rymerVVuVhBQsQc2_AmVMOPHdDsF3bf4xVE3gLED85FL05hA8_qyVEvY32zdLcGw7zqqCe5OaY-wpl4fNINZzR7vLdcqhtYyO8hJz_FWclhkbjjXYoId6O1_VIp4Mc90MtQ9BGlCQ8zX2H7EZHM8Sg2hQSDi_Lc6pltRo0prVferrvOL-Gju1_g6hzUtzf51cB9xE0utfZl0Uz2M_DB67EiMLTim2rJxgqsdMLP5lu20DSGp5UBXgOitUFBjrOw",
+            "e": "AQAB",
+            "d": "ESjsV6G4hCEgaUFuDVh2BTLNEjKVwfcdBwq36SIex7KcU-p8yJfhig2kRz-9odSmgzZCe7UP1p9cSHlm0zWLAE79mQajLUdxTvCs3iyk1Hjzj-UwckjsLCN4AhHhuKEMLxilmrNbSCYUdBegu4IEYPTpDHdLjTG4qFR8Xr8frjRIozcYUDaiWAF7WKKpv6L91QAAjMDhKAUAr6ZC63v-gPvyvRwjEJUZcocNQFeiZRuojOQ_u07AXKy5IHvnDbQQkwzfguICav9LYh3Kr9MPOg92FCkRdCN5QjmdXucno3nLXq08NjUMzu6DpiSYTdJ4xVXX7cmqHRFv99zw49DXmQ",
+            "p": "67IF5tIoKI3D8AauMDCloQ0ftGq04f3BXPDGVwgZLvApkRFET7ttlclyNAZI0gv0yAEOM7BruGBuRSiEdt1YuVRenxhZKjZBrtjAHyqx3cf3Kqe5ZS855NoGYb9N_flgQxPmUjJvtfGJvvG25_EnHu218tDBeB8Tld1gBJGNVPc",
+            "q": "vBDacwh_4mhQE3b_vdoViU-XtgSBt0K2os2yGr-hrZY1U6GhPCbfHgVJQ-qgVgOlhyWn7n62xSja8O_eNj2AS7tJbfU9WdJ83tjz-2DTj-stQyqk9UUjxC_qZzVFHzr4N7prbyYA6OKsDRpKgs2lRmrCZ8k5x5PM69VoT1yjft0",
+            "dp": "38QoLjgfwEC3YU42WbIVzY00KImWKWhNB_vfkgkyP4nrLLsD696575dNQW8l-6U2RBwca5525plGl4ocGO2Uqn8bRlhLbvQL8AAAhpZ2P2T1mB2cuh_YCxmjLXH-jb0QUx61cBxKSqaCbDH3wMUGqyprr5fZ1j1KzuFZo3y8fYk",
+            "dq": "XXF2SR_ZAvLXjsRaz8czWjV86QiWNytjucLAkv_SYoC24GX3yf56b90qiR9kKTjInzz7ksnNIGtrVbmpTIQEYhydoI_wuteWCdf8alOy6thsdeoTana_DbTNIBjczmarfG0ZAnH8k7pEyIHesd1iGHnlPFJEbkCm2isAGCasadU",
+            "qi": "ejXqJgjClfZn6KaPUeWqfbI4X14mphKa8U3U7KtWKfVPGMlSF9zM1NZT9cGDZLLIS8EI4JXLDGOxpjREpbgPtJczrrFar0rg_qnoFHx9FxbaxTtVXln2ga3ynTOV_n4rkOM7Hogv_lnwQh4MmKcXq5KPXbesWpH9QnHsrH4BsGI"
+        }
+        "#;
+        let mut coin = Coin {
+            id: "arweave".to_owned(),
+            name: "Ar".to_owned(),
+            coin_id: 999,
+            symbol: "AR".to_owned(),
+            decimals: 9,
+            blockchain: "Arweave".to_owned(),
+            derivation_path: "".to_owned(),
+            curve: "".to_owned(),
+            public_key_type: "rsa".to_owned(),
+            chain_id: None,
+            all_info: HashMap::new(),
+        };
+        let password = "password_new";
+        let stored_key = StoredKey::create_with_json("", password, json, &coin).unwrap();
+        assert!(stored_key.r#type == StoredKeyType::PrivateKey);
+        let account = stored_key
+            .add_new_account_of_coin_and_derivation_path_by_password("mask", &coin, "", password)
+            .unwrap();
+        assert_eq!(
+            account.address,
+            "pJDD7ACZwCuTUcdJ5KbPdJjIExqbfydss0ve6g6UD3k"
+        );
+
+        coin.public_key_type = "secp256k1".to_owned();
+        assert_eq!(
+            StoredKey::create_with_json("", password, json, &coin).err(),
+            Some(Error::CryptoError(CryptoError::NotSupportedPublicKeyType))
+        );
+    }
 }